@@ -0,0 +1,27 @@
+//! Runs a random soup in the terminal.
+//!
+//! ```text
+//! cargo run --example terminal --features terminal [WIDTH HEIGHT]
+//! ```
+
+use std::{env, error::Error, time::Duration};
+
+use game_of_life::{game::World, runner::TerminalRunner};
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args = env::args().skip(1).map(|arg| arg.parse::<u32>());
+    let width = args.next().transpose()?.unwrap_or(200);
+    let height = args.next().transpose()?.unwrap_or(100);
+
+    let world = World::new_random(width, height, 0.3)?;
+    let mut runner = TerminalRunner::new(world, Duration::from_millis(100));
+    runner.run()?;
+
+    let world = runner.into_world();
+    println!(
+        "Stopped at generation {} with {} cells alive.",
+        world.generation(),
+        world.population()
+    );
+    Ok(())
+}
@@ -27,7 +27,7 @@ pub fn get_args() -> MyResult<Config> {
         )
         .get_matches();
 
-    return Ok(Config {
+    Ok(Config {
         lifespan: matches.get_one::<i32>("lifespan").unwrap().to_owned(),
-    });
+    })
 }
@@ -1,64 +1,672 @@
-use rand::Rng;
-use std::ops::Index;
-use world_parts::Cell;
+use kernel::WordBuffers;
+use lut::Transitions;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::{
+    fmt, mem,
+    ops::{Index, IndexMut},
+};
+use world_parts::{AnsiStyle, AnsiWorld, Cell, DisplayConfig, IntoCells, Row, Rows, WorldDisplay};
 
+pub use bounds::Rect;
+pub use builder::WorldBuilder;
+pub use census::{Census, UnknownObject};
+pub use components::Component;
+pub use diff::WorldDiff;
+pub use error::WorldError;
+pub use framed::FrameOptions;
+pub use hashing::FnvHasher;
+pub use hashlife::Hashlife;
+pub use heatmap::Heatmap;
+pub use history::History;
+pub use immigration::{ColoredRow, ColoredWorld, Team};
+pub use insert::Overflow;
+pub use neighbours::{Boundary, EdgeMode, Neighborhood, Topology};
+pub use packed::PackedWorld;
+pub use pattern::Pattern;
+pub use period::{CycleResult, Period, StabilizationResult};
+#[cfg(feature = "image")]
+pub use raster::{ImageError, RenderStyle};
+pub use repeat::{StepStatus, StopCondition};
+pub use resize::Anchor;
+pub use rule::{Rule, RuleError};
+pub use share::ShareError;
+pub use snapshot::SnapshotError;
+pub use spaceship::Spaceship;
+pub use sparse::SparseWorld;
+pub use stats::GenerationStats;
+pub use tracked::CellChange;
+pub use transform::Transform;
+pub use view::{ViewRows, WorldView};
+pub use viewport::{PanMode, Viewport};
+
+mod active;
+mod ages;
+mod analysis;
+mod bounds;
+mod braille;
+mod builder;
+mod census;
+mod components;
+mod compose;
+mod crop;
+mod diff;
+mod error;
+mod expand;
+#[cfg(test)]
+mod fixtures;
+pub mod formats;
+mod framed;
+mod halfblock;
+mod hashing;
+mod hashlife;
+mod heatmap;
+mod history;
+mod immigration;
+mod insert;
+mod kernel;
+mod lut;
+mod neighbours;
+mod packed;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod pattern;
+mod period;
+#[cfg(feature = "image")]
+mod raster;
+mod region;
+mod repeat;
+mod resize;
+mod rule;
+#[cfg(feature = "serde")]
+mod serialize;
+mod share;
+mod shift;
+mod snapshot;
+mod spaceship;
+mod sparse;
+mod stats;
+mod stochastic;
+mod tracked;
+mod transform;
+mod view;
+mod viewport;
 pub mod world_parts;
 
-#[derive(Clone, Debug, PartialEq)]
 pub struct World {
-    pub grid: Vec<Cell>,
-    pub width: u32,
-    pub height: u32,
+    grid: Vec<Cell>,
+    // The buffer the next generation is written into, kept around so that
+    // evolving doesn't allocate a new grid every step. Its contents are
+    // meaningless between steps.
+    scratch: Vec<Cell>,
+    // The same for the cells packed into bits by `evolve_words`.
+    words: WordBuffers,
+    // The cells `evolve_sparse` changed in its last step, as long as nothing
+    // else has touched the world since. Anything that changes cells or how
+    // they evolve has to reset it.
+    changed: Option<Vec<usize>>,
+    // Looked up by `evolve_lut`, always built from `rule`.
+    transitions: Transitions,
+    // The number of live cells, kept up to date by everything that changes
+    // cells so it never needs counting. Unknown once a cell was handed out
+    // through `IndexMut`, until the next generation counts it again.
+    population: Option<usize>,
+    width: u32,
+    height: u32,
+    rule: Rule,
+    neighborhood: Neighborhood,
+    topology: Topology,
+    boundary: Boundary,
+    // Where the top left cell lies relative to where it started out, which
+    // only changes when the world grows, see `evolve_expanding`.
+    origin: (i64, i64),
+    // The number of generations evolved so far.
+    generation: u64,
+    // Counts changes while evolving once enabled, see `enable_heatmap`.
+    heatmap: Option<Heatmap>,
+    // How long each cell has been alive once enabled, see `enable_ages`.
+    ages: Option<Vec<u32>>,
 }
 
 impl World {
+    /// The size of `World::default()`, that of a classic 80 by 24 terminal.
+    pub const DEFAULT_WIDTH: u32 = 80;
+    pub const DEFAULT_HEIGHT: u32 = 24;
+
+    /// Creates a randomly filled world.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a dimension is zero or the world would have more cells than
+    /// fit in memory at once, see `try_new`.
     pub fn new(width: u32, height: u32) -> Self {
-        // The new function will create a new grid and set the initial state of
-        // all cells to dead. After which it will use an iterator to set random
-        // cells to Alive.
-        let mut grid = vec![Cell::Dead; (width * height) as usize];
-        let mut rng = rand::thread_rng();
-
-        for cell in grid.iter_mut() {
-            if rng.gen::<bool>() {
-                *cell = Cell::Alive;
-            }
+        Self::new_with_seed(width, height, rand::random())
+    }
+
+    /// Same as `new`, but returns an error for invalid dimensions.
+    pub fn try_new(width: u32, height: u32) -> Result<Self, WorldError> {
+        check_dimensions(width, height)?;
+        Ok(Self::new(width, height))
+    }
+
+    /// Creates a randomly filled world that is fully determined by `seed`.
+    /// The cells come from `ChaCha8Rng`, so the same seed gives the same world
+    /// on every platform and with every version of `rand`.
+    ///
+    /// ```
+    /// use game_of_life::game::World;
+    ///
+    /// let a = World::new_with_seed(20, 10, 42);
+    /// let b = World::new_with_seed(20, 10, 42);
+    /// assert_eq!(a, b);
+    /// ```
+    pub fn new_with_seed(width: u32, height: u32, seed: u64) -> Self {
+        Self::new_with_rng(width, height, &mut ChaCha8Rng::seed_from_u64(seed))
+    }
+
+    /// Creates a world where every cell drawn from `rng` has an even chance
+    /// of being alive.
+    ///
+    /// # Panics
+    ///
+    /// Panics for the same dimensions as `new`.
+    pub fn new_with_rng(width: u32, height: u32, rng: &mut impl Rng) -> Self {
+        let mut world = Self::empty(width, height);
+        world.fill_random(rng, 0.5);
+        world
+    }
+
+    /// Creates a randomly filled world where every cell is alive with
+    /// probability `density`, which has to lie within `0.0..=1.0`.
+    pub fn new_random(width: u32, height: u32, density: f64) -> Result<Self, WorldError> {
+        check_dimensions(width, height)?;
+        check_density(density)?;
+
+        let mut world = Self::empty(width, height);
+        world.fill_random(&mut rand::thread_rng(), density);
+        Ok(world)
+    }
+
+    /// # Panics
+    ///
+    /// Panics for the same dimensions as `new`.
+    pub fn empty(width: u32, height: u32) -> Self {
+        // Creates a grid of the same size as `new` would, but leaves every
+        // cell dead so a specific pattern can be placed on it afterwards.
+        if let Err(e) = check_dimensions(width, height) {
+            panic!("invalid world dimensions: {}", e);
         }
 
         Self {
+            grid: vec![Cell::Dead; width as usize * height as usize],
+            scratch: Vec::new(),
+            words: WordBuffers::default(),
+            changed: None,
+            transitions: Transitions::new(&Rule::default()),
+            population: Some(0),
+            width,
+            height,
+            rule: Rule::default(),
+            neighborhood: Neighborhood::default(),
+            topology: Topology::default(),
+            boundary: Boundary::default(),
+            origin: (0, 0),
+            generation: 0,
+            heatmap: None,
+            ages: None,
+        }
+    }
+
+    /// Builds a world from existing cells laid out row by row.
+    pub fn from_cells(width: u32, height: u32, cells: Vec<Cell>) -> Result<Self, WorldError> {
+        check_dimensions(width, height)?;
+
+        let expected = width as usize * height as usize;
+        if cells.len() != expected {
+            return Err(WorldError::LengthMismatch {
+                expected,
+                actual: cells.len(),
+            });
+        }
+
+        Ok(Self {
+            population: Some(cells.iter().filter(|cell| cell.is_alive()).count()),
+            grid: cells,
+            scratch: Vec::new(),
+            words: WordBuffers::default(),
+            changed: None,
+            transitions: Transitions::new(&Rule::default()),
+            width,
+            height,
+            rule: Rule::default(),
+            neighborhood: Neighborhood::default(),
+            topology: Topology::default(),
+            boundary: Boundary::default(),
+            origin: (0, 0),
+            generation: 0,
+            heatmap: None,
+            ages: None,
+        })
+    }
+
+    /// Same as `from_cells`, with `true` meaning alive.
+    pub fn from_bools(width: u32, height: u32, cells: Vec<bool>) -> Result<Self, WorldError> {
+        Self::from_cells(width, height, cells.into_iter().map(Cell::from).collect())
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The number of cells, live or not.
+    pub fn area(&self) -> usize {
+        self.grid.len()
+    }
+
+    /// Replaces the rule used by `evolve`, which is Conway's unless changed.
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.set_rule(rule);
+        self
+    }
+
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+        self.transitions = Transitions::new(&rule);
+        self.changed = None;
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// Replaces which cells count as neighbours, the Moore neighborhood
+    /// unless changed.
+    pub fn with_neighborhood(mut self, neighborhood: Neighborhood) -> Self {
+        self.set_neighborhood(neighborhood);
+        self
+    }
+
+    pub fn set_neighborhood(&mut self, neighborhood: Neighborhood) {
+        self.neighborhood = neighborhood;
+        self.changed = None;
+    }
+
+    pub fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+
+    /// The cell at `(row, col)`, or `None` outside the world.
+    pub fn get(&self, row: u32, col: u32) -> Option<Cell> {
+        if row >= self.height || col >= self.width {
+            return None;
+        }
+        Some(self.grid[self.get_index(row, col)])
+    }
+
+    /// Same as `get`, without checking the coordinates.
+    ///
+    /// # Safety
+    ///
+    /// `row` has to be less than the height and `col` less than the width.
+    pub unsafe fn get_unchecked(&self, row: u32, col: u32) -> Cell {
+        *self.grid.get_unchecked(self.get_index(row, col))
+    }
+
+    /// Replaces the cell at `(row, col)`, or returns an error outside the
+    /// world.
+    pub fn set(&mut self, row: u32, col: u32, cell: Cell) -> Result<(), WorldError> {
+        if row >= self.height || col >= self.width {
+            return Err(WorldError::OutOfBounds {
+                row,
+                col,
+                width: self.width,
+                height: self.height,
+            });
+        }
+
+        let idx = self.get_index(row, col);
+        self.set_cell(idx, cell);
+        Ok(())
+    }
+
+    /// Replaces every cell of a row, from left to right.
+    ///
+    /// Returns an error if the row lies outside the world, or `cells` isn't
+    /// exactly as long as the world is wide.
+    pub fn set_row(&mut self, row: u32, cells: &[Cell]) -> Result<(), WorldError> {
+        if row >= self.height {
+            return Err(WorldError::OutOfBounds {
+                row,
+                col: 0,
+                width: self.width,
+                height: self.height,
+            });
+        }
+        if cells.len() != self.width as usize {
+            return Err(WorldError::LengthMismatch {
+                expected: self.width as usize,
+                actual: cells.len(),
+            });
+        }
+
+        let start = self.get_index(row, 0);
+        for (idx, &cell) in (start..).zip(cells) {
+            self.set_cell(idx, cell);
+        }
+        Ok(())
+    }
+
+    /// Same as `set_row`, with live cells for `true`.
+    pub fn set_row_from_bools(&mut self, row: u32, cells: &[bool]) -> Result<(), WorldError> {
+        let cells: Vec<Cell> = cells.iter().map(|&alive| Cell::from(alive)).collect();
+        self.set_row(row, &cells)
+    }
+
+    /// Brings a dead or dying cell to life, and kills a live one.
+    pub fn toggle(&mut self, row: u32, col: u32) -> Result<(), WorldError> {
+        let cell = self.get(row, col).unwrap_or_default().toggled();
+        self.set(row, col, cell)
+    }
+
+    /// The cells of a row, from left to right.
+    pub fn get_row(&self, row: u32) -> &[Cell] {
+        let start = self.get_index(row, 0);
+        &self.grid[start..start + self.width as usize]
+    }
+
+    /// The cells of a column, from top to bottom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the column lies outside the world, like `get_row` does for
+    /// a row.
+    pub fn column(
+        &self,
+        col: u32,
+    ) -> impl DoubleEndedIterator<Item = Cell> + ExactSizeIterator + '_ {
+        assert!(
+            col < self.width,
+            "column {} lies outside the {}x{} world",
+            col,
+            self.width,
+            self.height
+        );
+        self.grid[col as usize..]
+            .iter()
+            .step_by(self.width as usize)
+            .copied()
+    }
+
+    /// Every column from left to right, see `column`.
+    pub fn columns(
+        &self,
+    ) -> impl DoubleEndedIterator<
+        Item = impl DoubleEndedIterator<Item = Cell> + ExactSizeIterator + '_,
+    > + ExactSizeIterator
+           + '_ {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// A row ready for printing, indented when it is a shifted row of a
+    /// hexagonal world.
+    pub fn row(&self, row: u32) -> Row<'_> {
+        Row::new(self.get_row(row)).with_indent(self.topology == Topology::Hex && row % 2 == 1)
+    }
+
+    /// Every cell with its row and column, row by row from the top left.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (u32, u32, Cell)> + '_ {
+        let width = self.width as usize;
+        self.grid
+            .iter()
+            .enumerate()
+            .map(move |(i, &cell)| ((i / width) as u32, (i % width) as u32, cell))
+    }
+
+    /// Same as `iter`, to change the cells in place. Like `world[(row, col)]`
+    /// this leaves `population` counting the cells again, until the next
+    /// generation.
+    pub fn iter_mut(&mut self) -> impl ExactSizeIterator<Item = (u32, u32, &mut Cell)> + '_ {
+        self.population = None;
+        self.changed = None;
+        let width = self.width as usize;
+        self.grid
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, cell)| ((i / width) as u32, (i % width) as u32, cell))
+    }
+
+    /// The row and column of every live cell, row by row from the top left.
+    pub fn live_cells(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.iter()
+            .filter(|(_, _, cell)| cell.is_alive())
+            .map(|(row, col, _)| (row, col))
+    }
+
+    /// Every row ready for printing, from top to bottom, see `row`.
+    pub fn rows(&self) -> Rows<'_> {
+        Rows::new(self)
+    }
+
+    /// Draws the world with the characters of `config`, like `Display` does
+    /// with the default ones.
+    pub fn display_with<'a>(&'a self, config: &'a DisplayConfig) -> WorldDisplay<'a> {
+        WorldDisplay::new(self, config)
+    }
+
+    /// Draws the world with the colors of `style`, one row per line, see
+    /// `Row::display_ansi`.
+    pub fn display_ansi<'a>(&'a self, style: &'a AnsiStyle) -> AnsiWorld<'a> {
+        AnsiWorld::new(self, style)
+    }
+
+    /// The number of live cells, without counting them. After cells were
+    /// changed through `world[(row, col)]` they are counted once more, up
+    /// until the next generation.
+    pub fn population(&self) -> usize {
+        let count = || self.grid.iter().filter(|cell| cell.is_alive()).count();
+        match self.population {
+            Some(population) => {
+                debug_assert_eq!(population, count(), "cached population is out of date");
+                population
+            }
+            None => count(),
+        }
+    }
+
+    /// The number of generations evolved since the world was created.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Sets the number of generations evolved, such as for a run resumed
+    /// from a saved world.
+    pub fn set_generation(&mut self, generation: u64) {
+        self.generation = generation;
+    }
+
+    /// Whether no cell is alive.
+    pub fn is_empty(&self) -> bool {
+        self.population() == 0
+    }
+
+    // Replaces a cell, keeping the population up to date. Anything that
+    // changes cells outside of `evolve` goes through here.
+    fn set_cell(&mut self, idx: usize, cell: Cell) {
+        self.replace_in_population(self.grid[idx], cell);
+        self.grid[idx] = cell;
+        self.changed = None;
+        self.reset_age(idx);
+    }
+
+    // Updates the population for `old` being replaced by `new`.
+    fn replace_in_population(&mut self, old: Cell, new: Cell) {
+        if let Some(population) = &mut self.population {
+            *population = *population - old.is_alive() as usize + new.is_alive() as usize;
+        }
+    }
+
+    /// Kills every cell, keeping the grid's memory for reuse. Counting the
+    /// generations starts over as well.
+    pub fn clear(&mut self) {
+        self.fill(Cell::Dead);
+    }
+
+    /// Sets every cell to `cell`, keeping the grid's memory for reuse, and
+    /// starts counting the generations over.
+    pub fn fill(&mut self, cell: Cell) {
+        self.grid.fill(cell);
+        self.generation = 0;
+        self.population = Some(if cell.is_alive() { self.grid.len() } else { 0 });
+        self.changed = None;
+        self.reset_ages();
+    }
+
+    // A world with the same rule, edges and origin as this one, but other
+    // cells.
+    fn with_cells(&self, width: u32, height: u32, grid: Vec<Cell>) -> World {
+        World {
+            population: Some(grid.iter().filter(|cell| cell.is_alive()).count()),
             grid,
+            scratch: Vec::new(),
+            words: WordBuffers::default(),
+            changed: None,
+            transitions: self.transitions,
             width,
             height,
+            rule: self.rule,
+            neighborhood: self.neighborhood,
+            topology: self.topology,
+            boundary: self.boundary,
+            origin: self.origin,
+            generation: self.generation,
+            heatmap: None,
+            ages: None,
+        }
+    }
+
+    /// Refills the world at random in place, with every cell alive with
+    /// probability `density`, which has to lie within `0.0..=1.0`. Counting
+    /// the generations starts over.
+    pub fn randomize(&mut self, rng: &mut impl Rng, density: f64) -> Result<(), WorldError> {
+        check_density(density)?;
+        self.fill_random(rng, density);
+        self.generation = 0;
+        Ok(())
+    }
+
+    fn fill_random(&mut self, rng: &mut impl Rng, density: f64) {
+        let mut population = 0;
+        for (_, _, cell) in self.iter_mut() {
+            *cell = if rng.gen_bool(density) {
+                population += 1;
+                Cell::Alive
+            } else {
+                Cell::Dead
+            };
+        }
+        self.population = Some(population);
+        self.reset_ages();
+    }
+
+    // Fails unless `other` is as wide and as high as this world.
+    fn check_same_size(&self, other: &World) -> Result<(), WorldError> {
+        if (self.width, self.height) != (other.width, other.height) {
+            return Err(WorldError::DimensionMismatch {
+                expected: (self.width, self.height),
+                actual: (other.width, other.height),
+            });
+        }
+        Ok(())
+    }
+
+    // The index of `(row, col)`, panicking if it lies outside the world.
+    fn checked_index(&self, row: u32, col: u32) -> usize {
+        if row >= self.height || col >= self.width {
+            panic!(
+                "cell ({}, {}) lies outside the {}x{} world",
+                row, col, self.width, self.height
+            );
         }
+        self.get_index(row, col)
     }
 
     // TODO: Create custom error for out of bound situations
     fn get_index(&self, row: u32, col: u32) -> usize {
-        (row * self.width + col) as usize
+        // Done in `usize`, as the product overflows `u32` for worlds with
+        // more than `u32::MAX` cells. `check_dimensions` makes sure it fits.
+        row as usize * self.width as usize + col as usize
     }
 
     pub fn evolve(&mut self) {
-        // The function creates a clone of the old grid and then sets the state of each
-        // new cell based on the circumstances of the old grid. Finally it sets the grid
-        // field of World to the new grid.
-        let mut new_grid = self.grid.clone();
-
-        for row in 0..self.height {
-            for col in 0..self.width {
-                let idx = self.get_index(row, col);
-                let num_neighbours = self.get_num_alive_neighbours(row, col);
-                new_grid[idx] = self.grid[idx].set_state(num_neighbours);
+        // The function sets the state of each cell in the scratch buffer based on the
+        // circumstances of the old grid. Finally it swaps the two, so the old grid
+        // becomes the scratch buffer for the next generation.
+        let mut new_grid = mem::take(&mut self.scratch);
+        new_grid.resize(self.grid.len(), Cell::Dead);
+        let mut words = mem::take(&mut self.words);
+        self.changed = None;
+
+        // Every path counts the live cells as it writes them.
+        self.population = Some(if self.uses_word_kernel() {
+            self.evolve_words(&mut words, &mut new_grid)
+        } else if self.uses_lut() {
+            self.evolve_lut(&mut new_grid)
+        } else if self.uses_window_counts() {
+            let counts = self.window_counts();
+            let mut population = 0;
+            for (idx, cell) in new_grid.iter_mut().enumerate() {
+                *cell = self.grid[idx].set_state(counts[idx], &self.rule);
+                population += cell.is_alive() as usize;
             }
-        }
+            population
+        } else {
+            let mut population = 0;
+            for row in 0..self.height {
+                for col in 0..self.width {
+                    let idx = self.get_index(row, col);
+                    let num_neighbours = self.get_num_alive_neighbours(row, col);
+                    new_grid[idx] = self.grid[idx].set_state(num_neighbours, &self.rule);
+                    population += new_grid[idx].is_alive() as usize;
+                }
+            }
+            population
+        });
+
+        self.scratch = mem::replace(&mut self.grid, new_grid);
+        self.words = words;
+        self.generation += 1;
+        self.record_heat();
+        self.record_ages();
+    }
 
-        self.grid = new_grid;
+    // Counting every cell's window separately gets slow for large radii, so
+    // where possible all counts are computed in one go instead.
+    fn uses_window_counts(&self) -> bool {
+        self.rule.radius() > 1
+            && self.neighborhood == Neighborhood::Moore
+            && self.topology == Topology::Square
+            && !self.boundary.is_twisted()
     }
 
-    fn get_num_alive_neighbours(&self, row: u32, col: u32) -> u8 {
+    fn get_num_alive_neighbours(&self, row: u32, col: u32) -> u32 {
+        if self.topology == Topology::Hex {
+            return self.get_num_alive_hex(row, col);
+        }
+        if self.rule.radius() > 1
+            || self.neighborhood != Neighborhood::Moore
+            || self.boundary != Boundary::TORUS
+        {
+            return self.get_num_alive_in_window(row, col);
+        }
+
         let mut count = 0;
 
-        for delta_row in [self.height - 1, 0, 1] {
-            for delta_col in [self.width - 1, 0, 1] {
+        for &delta_row in distinct_deltas(&[self.height - 1, 0, 1], self.height) {
+            for &delta_col in distinct_deltas(&[self.width - 1, 0, 1], self.width) {
                 if delta_row == 0 && delta_col == 0 {
                     continue;
                 }
@@ -76,6 +684,164 @@ impl World {
     }
 }
 
+/// An empty world of `DEFAULT_WIDTH` by `DEFAULT_HEIGHT` cells. Unlike `new`
+/// it is never random, so everything built from it is the same every time.
+impl Default for World {
+    fn default() -> Self {
+        Self::empty(Self::DEFAULT_WIDTH, Self::DEFAULT_HEIGHT)
+    }
+}
+
+/// Every row as printed by `Row`, one per line and without a newline at
+/// the end.
+///
+/// ```
+/// use game_of_life::game::World;
+///
+/// let blinker: World = "...\nOOO\n...".parse().unwrap();
+/// assert_eq!(blinker.to_string(), "   \n###\n   ");
+/// ```
+impl fmt::Display for World {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display_with(&DisplayConfig::default()))
+    }
+}
+
+/// The rows of the world from top to bottom, the same as `World::rows`.
+///
+/// ```
+/// use game_of_life::game::World;
+///
+/// let world: World = ".O.\n..O\nOOO".parse().unwrap();
+/// for row in &world {
+///     println!("{}", row);
+/// }
+/// assert_eq!((&world).into_iter().len(), 3);
+/// ```
+impl<'a> IntoIterator for &'a World {
+    type Item = Row<'a>;
+    type IntoIter = Rows<'a>;
+
+    fn into_iter(self) -> Rows<'a> {
+        self.rows()
+    }
+}
+
+/// Every cell with its row and column, row by row from the top left like
+/// `World::iter`, but taking the world apart instead of borrowing it.
+///
+/// ```
+/// use game_of_life::game::World;
+///
+/// let world: World = ".O\nO.".parse().unwrap();
+/// let alive: Vec<(u32, u32)> = world
+///     .into_iter()
+///     .filter(|(_, _, cell)| cell.is_alive())
+///     .map(|(row, col, _)| (row, col))
+///     .collect();
+/// assert_eq!(alive, [(0, 1), (1, 0)]);
+/// ```
+impl IntoIterator for World {
+    type Item = (u32, u32, Cell);
+    type IntoIter = IntoCells;
+
+    fn into_iter(self) -> IntoCells {
+        IntoCells::new(self.grid, self.width)
+    }
+}
+
+// The scratch buffer is left out, it holds no state of the world.
+impl Clone for World {
+    fn clone(&self) -> Self {
+        Self {
+            grid: self.grid.clone(),
+            scratch: Vec::new(),
+            words: WordBuffers::default(),
+            changed: None,
+            transitions: self.transitions,
+            population: self.population,
+            width: self.width,
+            height: self.height,
+            rule: self.rule,
+            neighborhood: self.neighborhood,
+            topology: self.topology,
+            boundary: self.boundary,
+            origin: self.origin,
+            generation: self.generation,
+            heatmap: self.heatmap.clone(),
+            ages: self.ages.clone(),
+        }
+    }
+}
+
+impl fmt::Debug for World {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("World")
+            .field("grid", &self.grid)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("rule", &self.rule)
+            .field("neighborhood", &self.neighborhood)
+            .field("topology", &self.topology)
+            .field("boundary", &self.boundary)
+            .field("origin", &self.origin)
+            .finish()
+    }
+}
+
+impl PartialEq for World {
+    fn eq(&self, other: &Self) -> bool {
+        self.grid == other.grid
+            && self.width == other.width
+            && self.height == other.height
+            && self.rule == other.rule
+            && self.neighborhood == other.neighborhood
+            && self.topology == other.topology
+            && self.boundary == other.boundary
+            && self.origin == other.origin
+    }
+}
+
+impl Eq for World {}
+
+// On axes shorter than 3 cells some of the deltas wrap to the same cell, which
+// must only be counted once.
+fn distinct_deltas(deltas: &[u32; 3], len: u32) -> &[u32] {
+    match len {
+        1 => &deltas[1..2],
+        2 => &deltas[1..],
+        _ => deltas,
+    }
+}
+
+// Every world has at least one cell, and its cells can be indexed with `u32`.
+fn check_dimensions(width: u32, height: u32) -> Result<(), WorldError> {
+    if width == 0 || height == 0 {
+        return Err(WorldError::ZeroDimension { width, height });
+    }
+    // Besides the cell count fitting in a `usize`, the grid can't take up more
+    // than `isize::MAX` bytes.
+    let too_large = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|cells| cells.checked_mul(mem::size_of::<Cell>()))
+        .is_none_or(|bytes| bytes > isize::MAX as usize);
+    if too_large {
+        return Err(WorldError::TooLarge { width, height });
+    }
+    Ok(())
+}
+
+fn check_density(density: f64) -> Result<(), WorldError> {
+    // NaN fails the range check as well, as it never compares as contained.
+    if !(0.0..=1.0).contains(&density) {
+        return Err(WorldError::InvalidDensity(density));
+    }
+    Ok(())
+}
+
+/// The cell at a position in the grid laid out row by row, so index `i` is
+/// row `i / width` and column `i % width`. Indexing by `(row, col)` doesn't
+/// depend on that layout.
 impl Index<usize> for World {
     type Output = Cell;
 
@@ -84,18 +850,45 @@ impl Index<usize> for World {
     }
 }
 
+/// The cell at `(row, col)`.
+///
+/// Panics if it lies outside the world.
+impl Index<(u32, u32)> for World {
+    type Output = Cell;
+
+    fn index(&self, (row, col): (u32, u32)) -> &Cell {
+        let idx = self.checked_index(row, col);
+        &self.grid[idx]
+    }
+}
+
+/// The cell at `(row, col)`, to change it in place. As the cell can be
+/// changed to anything, `population` counts the cells again afterwards,
+/// until the next generation.
+///
+/// Panics if it lies outside the world.
+impl IndexMut<(u32, u32)> for World {
+    fn index_mut(&mut self, (row, col): (u32, u32)) -> &mut Cell {
+        let idx = self.checked_index(row, col);
+        self.population = None;
+        self.changed = None;
+        &mut self.grid[idx]
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
     use crate::game::world_parts::Cell::*;
-    use crate::game::World;
+    use crate::game::world_parts::{AnsiStyle, DisplayConfig};
+    use crate::game::{Neighborhood, Pattern, Rule, Topology, World, WorldError};
+    use crate::patterns;
 
     #[test]
     fn test_get_index() {
-        let world = World {
-            grid: vec![Dead, Dead, Dead, Alive, Alive, Alive],
-            width: 3,
-            height: 2,
-        };
+        let world = World::from_cells(3, 2, vec![Dead, Dead, Dead, Alive, Alive, Alive]).unwrap();
 
         let idx = world.get_index(1, 1);
         assert_eq!(idx, 4);
@@ -103,26 +896,568 @@ mod test {
         let idx = world.get_index(0, 2);
         assert_eq!(idx, 2);
 
-        let idx = world.get_index(2, 4);
-        assert_eq!(idx, 7);
+        let idx = world.get_index(1, 2);
+        assert_eq!(idx, 5);
     }
 
     #[test]
-    fn test_get_num_alive_neighbours() {
-        let world = World {
-            grid: vec![Dead, Dead, Alive, Alive, Dead, Dead, Alive, Dead, Dead],
-            width: 3,
+    fn test_empty() {
+        let world = World::empty(4, 3);
+        assert_eq!(world.grid.len(), 12);
+        assert!(world.grid.iter().all(|cell| !cell.is_alive()));
+
+        let result = world.get_num_alive_neighbours(1, 1);
+        assert_eq!(result, 0);
+    }
+
+    #[test]
+    fn test_from_cells() {
+        let world = World::from_cells(3, 2, vec![Dead, Dead, Dead, Alive, Alive, Alive]).unwrap();
+        assert_eq!(world.get_row(1), &[Alive, Alive, Alive]);
+
+        let result = World::from_cells(3, 2, vec![Dead, Alive]);
+        assert_eq!(
+            result,
+            Err(WorldError::LengthMismatch {
+                expected: 6,
+                actual: 2
+            })
+        );
+
+        let result = World::from_cells(0, 2, vec![]);
+        assert_eq!(
+            result,
+            Err(WorldError::ZeroDimension {
+                width: 0,
+                height: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_new() {
+        let world = World::try_new(7, 3).unwrap();
+        assert_eq!((world.width(), world.height()), (7, 3));
+
+        for (width, height) in [(0, 10), (10, 0), (0, 0)] {
+            assert_eq!(
+                World::try_new(width, height),
+                Err(WorldError::ZeroDimension { width, height })
+            );
+        }
+        assert_eq!(
+            World::try_new(u32::MAX, u32::MAX),
+            Err(WorldError::TooLarge {
+                width: u32::MAX,
+                height: u32::MAX
+            })
+        );
+
+        assert!(World::new_random(0, 10, 0.5).is_err());
+        assert!(World::from_cells(u32::MAX, u32::MAX, vec![]).is_err());
+        assert_eq!(
+            World::from_cells(10, 0, vec![]),
+            Err(WorldError::ZeroDimension {
+                width: 10,
+                height: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_seeded_golden() {
+        // Catches any change to the generator behind `new_with_seed`, which
+        // would silently change every recorded run.
+        let world = World::new_with_seed(16, 4, 42);
+        assert_eq!(world.row(0).to_string(), "  # ###  #     #");
+    }
+
+    #[test]
+    fn test_new_with_rng() {
+        let a = World::new_with_rng(20, 10, &mut ChaCha8Rng::seed_from_u64(7));
+        assert_eq!(a, World::new_with_seed(20, 10, 7));
+    }
+
+    #[test]
+    fn test_get() {
+        let world: World = "O..\n...\n..O\nO.O".parse().unwrap();
+        assert_eq!(world.get(0, 0), Some(Alive));
+        assert_eq!(world.get(0, 2), Some(Dead));
+        assert_eq!(world.get(3, 0), Some(Alive));
+        assert_eq!(world.get(3, 2), Some(Alive));
+        assert_eq!(world.get(1, 1), Some(Dead));
+
+        assert_eq!(world.get(0, 3), None);
+        assert_eq!(world.get(4, 0), None);
+        assert_eq!(world.get(4, 3), None);
+        assert_eq!(world.get(u32::MAX, u32::MAX), None);
+
+        for (row, col) in [(0, 0), (0, 2), (3, 0), (3, 2), (2, 2)] {
+            // SAFETY: all of them lie within the world.
+            let cell = unsafe { world.get_unchecked(row, col) };
+            assert_eq!(Some(cell), world.get(row, col));
+        }
+    }
+
+    #[test]
+    fn test_rows() {
+        let world: World = "O..\n.O.\n..O\nOOO".parse().unwrap();
+        let rows: Vec<String> = world.rows().map(|row| row.to_string()).collect();
+        assert_eq!(rows, ["#  ", " # ", "  #", "###"]);
+
+        let mut rows = world.rows();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows.next_back().unwrap().to_string(), "###");
+        assert_eq!(rows.next().unwrap().to_string(), "#  ");
+        assert_eq!(rows.len(), 2);
+
+        let bottom_up: Vec<String> = world.rows().rev().map(|row| row.to_string()).collect();
+        assert_eq!(bottom_up, ["###", "  #", " # ", "#  "]);
+    }
+
+    #[test]
+    fn test_iter() {
+        let world: World = "O..\n.O.\n..O\nOO.".parse().unwrap();
+        let cells: Vec<_> = world.iter().collect();
+        assert_eq!(cells.len(), 12);
+        assert_eq!(world.iter().len(), 12);
+        assert_eq!(cells[0], (0, 0, Alive));
+        assert_eq!(cells[5], (1, 2, Dead));
+        assert_eq!(cells[11], (3, 2, Dead));
+        assert_eq!(world.iter().last(), Some((3, 2, Dead)));
+        for (row, col, cell) in world.iter() {
+            assert_eq!(world.get(row, col), Some(cell));
+        }
+    }
+
+    #[test]
+    fn test_live_cells() {
+        let world: World = ".O...\n..O..\nOOO..\n.....".parse().unwrap();
+        let cells: Vec<_> = world.live_cells().collect();
+        assert_eq!(cells, [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+        assert_eq!(World::empty(4, 4).live_cells().next(), None);
+    }
+
+    #[test]
+    fn test_columns() {
+        let world = World::new_with_seed(13, 7, 4);
+        for row in 0..7 {
+            for col in 0..13 {
+                assert_eq!(world.column(col).nth(row as usize), world.get(row, col));
+            }
+        }
+        assert_eq!(world.column(12).len(), 7);
+        assert_eq!(world.column(12).next_back(), world.get(6, 12));
+
+        let world: World = "O..\n.O.\nOO.".parse().unwrap();
+        let alive: Vec<usize> = world
+            .columns()
+            .map(|column| column.filter(|cell| cell.is_alive()).count())
+            .collect();
+        assert_eq!(alive, [2, 2, 0]);
+        assert_eq!(world.columns().len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 lies outside the 3x2 world")]
+    fn test_column_outside() {
+        let _ = World::empty(3, 2).column(3);
+    }
+
+    #[test]
+    fn test_display() {
+        let mut world = patterns::glider();
+        world.set(0, 0, Dying(1)).unwrap();
+        assert_eq!(world.to_string(), "+# \n  #\n###");
+        assert_eq!(
+            world.display_with(&DisplayConfig::PLAINTEXT).to_string(),
+            "+O.\n..O\nOOO"
+        );
+
+        let hex: World = "O.\n.O".parse().unwrap();
+        let hex = hex.with_topology(Topology::Hex).unwrap();
+        assert_eq!(hex.to_string(), "# \n  #");
+
+        let style = AnsiStyle {
+            no_color: true,
+            ..AnsiStyle::default()
+        };
+        assert_eq!(hex.display_ansi(&style).to_string(), hex.to_string());
+        assert_eq!(
+            hex.display_ansi(&AnsiStyle::default())
+                .to_string()
+                .lines()
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let world = World::new_with_seed(5, 4, 9);
+        let rows: Vec<String> = (&world).into_iter().map(|row| row.to_string()).collect();
+        let expected: Vec<String> = world.rows().map(|row| row.to_string()).collect();
+        assert_eq!(rows, expected);
+
+        let cells: Vec<_> = world.iter().collect();
+        let mut owned = world.into_iter();
+        assert_eq!(owned.size_hint(), (20, Some(20)));
+        assert_eq!(owned.next_back(), cells.last().copied());
+        assert_eq!(owned.len(), 19);
+        assert_eq!(owned.collect::<Vec<_>>(), cells[..19]);
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut world = World::empty(7, 4);
+        assert_eq!(world.iter_mut().len(), 28);
+        for (row, col, cell) in world.iter_mut() {
+            if (row + col).is_multiple_of(5) {
+                *cell = Alive;
+            }
+        }
+        assert_eq!(world.population(), 5);
+        assert_eq!(world.get(3, 2), Some(Alive));
+        assert_eq!(world.get(0, 5), Some(Alive));
+        assert_eq!(world.get(3, 6), Some(Dead));
+        assert_eq!(
+            world.iter_mut().last().map(|(row, col, _)| (row, col)),
+            Some((3, 6))
+        );
+    }
+
+    #[test]
+    fn test_set_row() {
+        let mut world = World::empty(4, 3);
+        world.set_row(2, &[Alive, Dead, Dying(1), Alive]).unwrap();
+        assert_eq!(world.get_row(2), [Alive, Dead, Dying(1), Alive]);
+        assert_eq!(world.population(), 2);
+
+        world
+            .set_row_from_bools(0, &[true, true, false, true])
+            .unwrap();
+        world.set_row_from_bools(2, &[false; 4]).unwrap();
+        assert_eq!(world.get_row(0), [Alive, Alive, Dead, Alive]);
+        assert_eq!(world.get_row(2), [Dead; 4]);
+        assert_eq!(world.population(), 3);
+
+        assert_eq!(
+            world.set_row(1, &[Alive; 3]),
+            Err(WorldError::LengthMismatch {
+                expected: 4,
+                actual: 3
+            })
+        );
+        assert_eq!(
+            world.set_row_from_bools(3, &[true; 4]),
+            Err(WorldError::OutOfBounds {
+                row: 3,
+                col: 0,
+                width: 4,
+                height: 3
+            })
+        );
+        assert_eq!(world.population(), 3);
+    }
+
+    #[test]
+    fn test_index_by_position() {
+        let mut world: World = "O..\n...\n..O\nO.O".parse().unwrap();
+        assert_eq!(world[(0, 0)], Alive);
+        assert_eq!(world[(3, 1)], Dead);
+        assert_eq!(world[(2, 2)], world[2 * 3 + 2]);
+
+        world[(1, 1)] = Alive;
+        world[(0, 0)] = Dying(1);
+        assert_eq!(world.get(1, 1), Some(Alive));
+        assert_eq!(world.population(), 4);
+
+        // Evolving counts the population again.
+        world.evolve();
+        assert_eq!(world.population, Some(world.population()));
+    }
+
+    #[test]
+    #[should_panic(expected = "cell (0, 3) lies outside the 3x4 world")]
+    fn test_index_outside() {
+        let world = World::empty(3, 4);
+        let _ = world[(0, 3)];
+    }
+
+    #[test]
+    #[should_panic(expected = "cell (4, 0) lies outside the 3x4 world")]
+    fn test_index_mut_outside() {
+        let mut world = World::empty(3, 4);
+        world[(4, 0)] = Alive;
+    }
+
+    #[test]
+    fn test_set_and_toggle() {
+        let mut world = World::empty(4, 3);
+        world.set(2, 3, Alive).unwrap();
+        world.set(0, 0, Dying(1)).unwrap();
+        assert_eq!(world.get(2, 3), Some(Alive));
+        assert_eq!(world.population(), 1);
+
+        world.toggle(2, 3).unwrap();
+        world.toggle(0, 0).unwrap();
+        world.toggle(1, 1).unwrap();
+        assert_eq!(world.get(2, 3), Some(Dead));
+        assert_eq!(world.get(0, 0), Some(Alive));
+        assert_eq!(world.get(1, 1), Some(Alive));
+        assert_eq!(world.population(), 2);
+
+        let error = WorldError::OutOfBounds {
+            row: 3,
+            col: 0,
+            width: 4,
             height: 3,
         };
+        assert_eq!(world.set(3, 0, Alive), Err(error.clone()));
+        assert_eq!(world.toggle(3, 0), Err(error));
+        assert!(world.set(0, 4, Alive).is_err());
+        assert_eq!(world.population(), 2);
+    }
 
-        let result = world.get_num_alive_neighbours(1, 1);
-        assert_eq!(result, 3);
+    #[test]
+    fn test_area_and_is_empty() {
+        let mut world = World::empty(7, 3);
+        assert_eq!(world.area(), 21);
+        assert_eq!(world.population(), 0);
+        assert!(world.is_empty());
+
+        world.set(1, 1, Dying(1)).unwrap();
+        assert!(world.is_empty());
+        world.set(1, 1, Alive).unwrap();
+        assert!(!world.is_empty());
+        world.evolve();
+        assert!(world.is_empty());
+
+        let mut blinker: World = ".....\n..O..\n..O..\n..O..\n.....".parse().unwrap();
+        assert_eq!(blinker.area(), 25);
+        assert_eq!(blinker.population(), 3);
+        blinker.evolve();
+        assert_eq!(blinker.population(), 3);
+    }
+
+    #[test]
+    fn test_clear_and_fill() {
+        let mut world = World::new_with_seed(9, 6, 3);
+        let grid = world.grid.as_ptr();
+
+        world.fill(Alive);
+        assert!(world.grid.iter().all(|&cell| cell == Alive));
+        assert_eq!(world.population(), 54);
+
+        world.fill(Dying(1));
+        assert_eq!(world.get(5, 8), Some(Dying(1)));
+        assert!(world.is_empty());
+
+        world.clear();
+        assert_eq!(world, World::empty(9, 6));
+        assert_eq!(world.grid.as_ptr(), grid);
+    }
+
+    #[test]
+    fn test_randomize() {
+        let mut world = World::empty(16, 4);
+        let grid = world.grid.as_ptr();
+        world
+            .randomize(&mut ChaCha8Rng::seed_from_u64(42), 0.5)
+            .unwrap();
+        assert_eq!(world, World::new_with_seed(16, 4, 42));
+        assert_eq!((world.width(), world.height()), (16, 4));
+        assert_eq!(world.grid.as_ptr(), grid);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        world.randomize(&mut rng, 0.2).unwrap();
+        assert_eq!(world.population(), 13);
+        world.randomize(&mut rng, 1.0).unwrap();
+        assert_eq!(world.population(), 64);
 
+        assert_eq!(
+            world.randomize(&mut rng, 1.5),
+            Err(WorldError::InvalidDensity(1.5))
+        );
+        assert_eq!(world.population(), 64);
+    }
+
+    #[test]
+    fn test_generation() {
+        let mut world = World::new_with_seed(12, 10, 5).with_rule(Rule::BRIANS_BRAIN);
+        assert_eq!(world.generation(), 0);
+        world.evolve();
+        world.evolve_sparse();
+        world.evolve_sparse();
+        assert_eq!(world.evolve_n(4), 4);
+        assert_eq!(world.generation(), 7);
+
+        world.evolve_region(0, 0, 3, 3).unwrap();
+        assert_eq!(world.generation(), 7);
+        assert_eq!(world.clone().crop().generation(), 7);
+
+        world.set_generation(100);
+        world.evolve();
+        assert_eq!(world.generation(), 101);
+        world.clear();
+        assert_eq!(world.generation(), 0);
+    }
+
+    #[test]
+    fn test_default() {
+        let world = World::default();
+        assert_eq!((world.width(), world.height()), (80, 24));
+        assert!(world.is_empty());
+        assert_eq!(world, World::default());
+        assert_eq!(world, World::empty(80, 24));
+    }
+
+    #[test]
+    fn test_population() {
+        let world =
+            World::from_cells(3, 2, vec![Alive, Dead, Alive, Dying(1), Dead, Alive]).unwrap();
+        assert_eq!(world.population(), 3);
+
+        // `population` checks the cached count against a recount in debug
+        // builds, whichever way the cells got there.
+        let worlds = [
+            World::new_with_seed(30, 20, 1),
+            World::new_with_seed(30, 20, 2).with_rule(Rule::BRIANS_BRAIN),
+            World::new_with_seed(30, 20, 3).with_rule(Rule::BUGS),
+            World::new_with_seed(30, 20, 4).with_neighborhood(Neighborhood::VonNeumann),
+            World::new_with_seed(2, 20, 5),
+        ];
+        for mut world in worlds {
+            for _ in 0..5 {
+                world.evolve();
+                world.population();
+                world.evolve_sparse();
+                world.population();
+                world.evolve_region(1, 0, 2, 2).unwrap();
+                world.population();
+            }
+        }
+
+        let mut world = World::new_random(30, 20, 0.0).unwrap();
+        assert_eq!(world.population(), 0);
+        let glider = Pattern::from(patterns::glider());
+        world.place(&glider, 1, 1).unwrap();
+        world.place(&glider, 10, 10).unwrap();
+        assert_eq!(world.population(), 10);
+        world.evolve();
+        assert_eq!(world.population(), 10);
+    }
+
+    #[test]
+    fn test_evolve_reuses_buffers() {
+        let mut world = World::new_with_seed(20, 20, 5);
+        world.evolve();
+        let buffers = (world.grid.as_ptr(), world.scratch.as_ptr());
+
+        // From then on the same two buffers take turns holding the grid.
+        for _ in 0..5 {
+            world.evolve();
+            assert_eq!((world.scratch.as_ptr(), world.grid.as_ptr()), buffers);
+            world.evolve();
+            assert_eq!((world.grid.as_ptr(), world.scratch.as_ptr()), buffers);
+        }
+
+        // The scratch buffer takes no part in comparisons.
+        let mut fresh = world.clone();
+        assert_eq!(world, fresh);
+        world.evolve();
+        fresh.evolve();
+        assert_eq!(world, fresh);
+    }
+
+    #[test]
+    fn test_dimensions_beyond_u32_cells() {
+        // 70_000 x 70_000 has more cells than a `u32` can count.
+        let result = super::check_dimensions(70_000, 70_000);
+        if cfg!(target_pointer_width = "64") {
+            assert_eq!(result, Ok(()));
+        } else {
+            assert!(matches!(result, Err(WorldError::TooLarge { .. })));
+        }
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_index_beyond_u32_cells() {
+        // Only the dimensions matter for indexing, so the grid is left empty
+        // rather than allocating billions of cells.
         let world = World {
-            grid: vec![Dead, Alive, Dead, Dead, Alive, Dead, Dead, Alive, Dead],
-            width: 3,
-            height: 3,
+            grid: Vec::new(),
+            width: 70_000,
+            height: 70_000,
+            ..World::empty(1, 1)
         };
+        assert_eq!(world.get_index(0, 69_999), 69_999);
+        assert_eq!(world.get_index(1, 0), 70_000);
+        assert_eq!(world.get_index(69_999, 69_999), 4_899_999_999);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid world dimensions")]
+    fn test_new_rejects_zero_width() {
+        World::new(0, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "too many cells")]
+    fn test_empty_rejects_overflow() {
+        World::empty(u32::MAX, u32::MAX);
+    }
+
+    #[test]
+    fn test_from_bools() {
+        let world = World::from_bools(2, 2, vec![true, false, false, true]).unwrap();
+        assert_eq!(world.grid, vec![Alive, Dead, Dead, Alive]);
+    }
+
+    #[test]
+    fn test_new_with_seed() {
+        let a = World::new_with_seed(8, 8, 7);
+        let b = World::new_with_seed(8, 8, 7);
+        assert_eq!(a, b);
+
+        let c = World::new_with_seed(8, 8, 8);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_new_random() {
+        let world = World::new_random(10, 10, 0.0).unwrap();
+        assert_eq!(world, World::empty(10, 10));
+
+        let world = World::new_random(10, 10, 1.0).unwrap();
+        assert!(world.grid.iter().all(|cell| cell.is_alive()));
+
+        assert!(World::new_random(10, 10, f64::NAN).is_err());
+        assert_eq!(
+            World::new_random(10, 10, 1.5),
+            Err(WorldError::InvalidDensity(1.5))
+        );
+    }
+
+    #[test]
+    fn test_get_num_alive_neighbours() {
+        let world = World::from_cells(
+            3,
+            3,
+            vec![Dead, Dead, Alive, Alive, Dead, Dead, Alive, Dead, Dead],
+        )
+        .unwrap();
+
+        let result = world.get_num_alive_neighbours(1, 1);
+        assert_eq!(result, 3);
+
+        let world = World::from_cells(
+            3,
+            3,
+            vec![Dead, Alive, Dead, Dead, Alive, Dead, Dead, Alive, Dead],
+        )
+        .unwrap();
 
         let result = world.get_num_alive_neighbours(1, 1);
         assert_eq!(result, 2);
@@ -130,16 +1465,57 @@ mod test {
         assert_eq!(result, 3);
     }
 
+    #[test]
+    fn test_neighbours_on_narrow_worlds() {
+        // A 1x5 ring: each cell has the two cells above and below it.
+        let world = World::from_cells(1, 5, vec![Alive, Dead, Alive, Alive, Dead]).unwrap();
+        let counts: Vec<u32> = (0..5)
+            .map(|row| world.get_num_alive_neighbours(row, 0))
+            .collect();
+        assert_eq!(counts, vec![0, 2, 1, 1, 2]);
+
+        // On 2x2 every cell touches the other three exactly once.
+        let world = World::from_cells(2, 2, vec![Alive, Dead, Dead, Dead]).unwrap();
+        assert_eq!(world.get_num_alive_neighbours(0, 0), 0);
+        assert_eq!(world.get_num_alive_neighbours(0, 1), 1);
+        assert_eq!(world.get_num_alive_neighbours(1, 0), 1);
+        assert_eq!(world.get_num_alive_neighbours(1, 1), 1);
+
+        // A 3x1 ring: both other cells are neighbours.
+        let world = World::from_cells(3, 1, vec![Alive, Alive, Dead]).unwrap();
+        let counts: Vec<u32> = (0..3)
+            .map(|col| world.get_num_alive_neighbours(0, col))
+            .collect();
+        assert_eq!(counts, vec![1, 1, 2]);
+
+        // A single cell has no neighbours at all.
+        let world = World::from_cells(1, 1, vec![Alive]).unwrap();
+        assert_eq!(world.get_num_alive_neighbours(0, 0), 0);
+    }
+
+    #[test]
+    fn test_evolve_narrow_ring() {
+        // With two neighbours at most nothing is ever born on a 1xN ring
+        // under Conway's rule, and runs of three shrink from both ends.
+        let mut world =
+            World::from_cells(1, 6, vec![Dead, Alive, Alive, Alive, Dead, Dead]).unwrap();
+        world.evolve();
+        assert_eq!(world.grid, vec![Dead, Dead, Alive, Dead, Dead, Dead]);
+        world.evolve();
+        assert_eq!(world, World::empty(1, 6));
+    }
+
     #[test]
     fn test_evolve() {
-        let mut world = World {
-            grid: vec![
+        let mut world = World::from_cells(
+            5,
+            5,
+            vec![
                 Dead, Dead, Dead, Dead, Dead, Dead, Dead, Alive, Dead, Dead, Dead, Dead, Alive,
                 Dead, Dead, Dead, Dead, Alive, Dead, Dead, Dead, Dead, Dead, Dead, Dead,
             ],
-            width: 5,
-            height: 5,
-        };
+        )
+        .unwrap();
 
         world.evolve();
         let assertion = vec![
@@ -148,4 +1524,65 @@ mod test {
         ];
         assert_eq!(world.grid, assertion);
     }
+
+    #[test]
+    fn test_evolve_with_rule() {
+        // Under Seeds (B2/S) every live cell dies, and the cells above and
+        // below the pair, which touch both of its members, are born.
+        let mut world = World::from_cells(
+            4,
+            3,
+            vec![
+                Dead, Dead, Dead, Dead, Dead, Alive, Alive, Dead, Dead, Dead, Dead, Dead,
+            ],
+        )
+        .unwrap()
+        .with_rule(Rule::new(&[2], &[]));
+        assert_eq!(world.rule(), Rule::new(&[2], &[]));
+
+        world.evolve();
+        let assertion = vec![
+            Dead, Alive, Alive, Dead, Dead, Dead, Dead, Dead, Dead, Alive, Alive, Dead,
+        ];
+        assert_eq!(world.grid, assertion);
+    }
+
+    #[test]
+    fn test_neighbours_at_corners() {
+        // Only the corner opposite to (0, 0) is alive, which touches it
+        // diagonally across both edges.
+        let world: World = "...\n...\n..O".parse().unwrap();
+        assert_eq!(world.neighborhood(), Neighborhood::Moore);
+        assert_eq!(world.get_num_alive_neighbours(0, 0), 1);
+
+        let world = world.with_neighborhood(Neighborhood::VonNeumann);
+        assert_eq!(world.get_num_alive_neighbours(0, 0), 0);
+        assert_eq!(world.get_num_alive_neighbours(0, 2), 1);
+        assert_eq!(world.get_num_alive_neighbours(2, 0), 1);
+        assert_eq!(world.get_num_alive_neighbours(1, 1), 0);
+
+        // The orthogonal neighbours of a corner wrap around both edges.
+        let world: World = ".O.O\n....\n....\nO..O".parse().unwrap();
+        let world = world.with_neighborhood(Neighborhood::VonNeumann);
+        assert_eq!(world.get_num_alive_neighbours(0, 0), 3);
+        assert_eq!(world.get_num_alive_neighbours(3, 3), 2);
+        assert_eq!(world.get_num_alive_neighbours(0, 3), 1);
+    }
+
+    #[test]
+    fn test_evolve_von_neumann() {
+        // With only orthogonal neighbours the blinker's ends see one live
+        // cell each and the centre two, so under B1/S it turns into a ring.
+        let mut world: World = ".....\n.....\n.OOO.\n.....\n.....".parse().unwrap();
+        world.set_neighborhood(Neighborhood::VonNeumann);
+        world.set_rule(Rule::new(&[1], &[]));
+        world.evolve();
+        let expected: World = ".....\n.OOO.\nO...O\n.OOO.\n.....".parse().unwrap();
+        assert_eq!(
+            world,
+            expected
+                .with_neighborhood(Neighborhood::VonNeumann)
+                .with_rule(Rule::new(&[1], &[]))
+        );
+    }
 }
@@ -0,0 +1,140 @@
+use super::World;
+
+impl World {
+    /// Same as `evolve`, but only looks at the cells around those that
+    /// changed in the previous call, as nothing else can change. For a few
+    /// small patterns on a large, mostly empty world this is far faster.
+    ///
+    /// The first call, and the first one after the world was changed in any
+    /// other way, evolves every cell to find out what changed.
+    pub fn evolve_sparse(&mut self) {
+        let changed = match self.changed.take() {
+            Some(changed) => {
+                self.generation += 1;
+                let changed = self.evolve_around(&changed);
+                self.record_heat_at(&changed);
+                self.record_ages_at(&changed);
+                changed
+            }
+            None => {
+                self.evolve();
+                // After evolving the scratch buffer holds the previous
+                // generation.
+                (0..self.grid.len())
+                    .filter(|&idx| self.grid[idx] != self.scratch[idx])
+                    .collect()
+            }
+        };
+        self.changed = Some(changed);
+    }
+
+    // Evolves the cells that have one of `changed` among their neighbours,
+    // or are one of them, and returns the ones that changed.
+    fn evolve_around(&mut self, changed: &[usize]) -> Vec<usize> {
+        // Every cell a changed one could be a neighbour of lies within the
+        // rule's radius of it. Hexagonal neighbours are within a radius of 1.
+        let radius = self.rule.radius() as i64;
+        let width = self.width as usize;
+        let mut candidates = Vec::new();
+        for &idx in changed {
+            let (row, col) = ((idx / width) as i64, (idx % width) as i64);
+            for d_row in -radius..=radius {
+                for d_col in -radius..=radius {
+                    if let Some((row, col)) =
+                        self.boundary
+                            .resolve(row + d_row, col + d_col, self.width, self.height)
+                    {
+                        candidates.push(row * width + col);
+                    }
+                }
+            }
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        // Work out all new states before writing any of them, as they depend
+        // on the old ones.
+        let updates: Vec<_> = candidates
+            .into_iter()
+            .filter_map(|idx| {
+                let (row, col) = ((idx / width) as u32, (idx % width) as u32);
+                let n = self.get_num_alive_neighbours(row, col);
+                let cell = self.grid[idx].set_state(n, &self.rule);
+                (cell != self.grid[idx]).then_some((idx, cell))
+            })
+            .collect();
+
+        for &(idx, cell) in &updates {
+            self.replace_in_population(self.grid[idx], cell);
+            self.grid[idx] = cell;
+        }
+        updates.into_iter().map(|(idx, _)| idx).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{
+        world_parts::Cell, Boundary, EdgeMode, Neighborhood, Pattern, Rule, Topology, World,
+    };
+    use crate::patterns;
+
+    #[test]
+    fn test_evolve_sparse_matches_evolve() {
+        let worlds = [
+            World::new_with_seed(30, 20, 1),
+            World::new_with_seed(30, 20, 2).with_rule(Rule::BRIANS_BRAIN),
+            World::new_with_seed(30, 20, 3).with_rule(Rule::DAY_AND_NIGHT),
+            World::new_with_seed(40, 40, 4).with_rule(Rule::BUGS),
+            World::new_with_seed(30, 20, 5).with_neighborhood(Neighborhood::VonNeumann),
+            World::new_with_seed(30, 20, 6)
+                .with_topology(Topology::Hex)
+                .unwrap()
+                .with_rule(Rule::hex(&[2], &[3, 4])),
+            World::new_with_seed(30, 20, 7).with_boundary(Boundary::uniform(EdgeMode::Mirror)),
+            World::new_with_seed(30, 20, 8).with_boundary(Boundary::KLEIN_BOTTLE),
+            World::new_with_seed(2, 5, 9),
+            // Births out of nothing change cells far away from anything.
+            World::empty(10, 10).with_rule("B0/S8".parse().unwrap()),
+        ];
+        for mut world in worlds {
+            let mut sparse = world.clone();
+            for _ in 0..30 {
+                world.evolve();
+                sparse.evolve_sparse();
+                assert_eq!(sparse, world);
+            }
+        }
+    }
+
+    #[test]
+    fn test_evolve_sparse_after_changes() {
+        let glider = Pattern::from(patterns::glider());
+        let mut world = World::empty(300, 300);
+        world.place(&glider, 10, 10).unwrap();
+        let mut sparse = world.clone();
+        for _ in 0..8 {
+            world.evolve();
+            sparse.evolve_sparse();
+        }
+
+        // A pattern placed far away from the glider still comes to life.
+        world.place(&glider, 150, 150).unwrap();
+        sparse.place(&glider, 150, 150).unwrap();
+        for _ in 0..8 {
+            world.evolve();
+            sparse.evolve_sparse();
+            assert_eq!(sparse, world);
+        }
+
+        // So does a change of rule.
+        world.set_rule(Rule::LIFE_WITHOUT_DEATH);
+        sparse.set_rule(Rule::LIFE_WITHOUT_DEATH);
+        for _ in 0..8 {
+            world.evolve();
+            sparse.evolve_sparse();
+            assert_eq!(sparse, world);
+        }
+        assert!(sparse.grid.contains(&Cell::Alive));
+    }
+}
@@ -0,0 +1,161 @@
+use super::World;
+
+impl World {
+    /// Starts counting for how many generations each live cell has been
+    /// alive, from 0 in the generation it was born. Every live cell starts
+    /// out at 0 here. Does nothing if already enabled.
+    ///
+    /// Ages are kept up by `evolve` and everything built on it as well as
+    /// `evolve_parallel`, `evolve_sparse` and, within its region,
+    /// `evolve_region`. A cell changed through `set`, `toggle` or
+    /// `union_with` and its kin starts over at 0. Until this is called
+    /// evolving doesn't spend any time on them.
+    pub fn enable_ages(&mut self) {
+        if self.ages.is_none() {
+            self.ages = Some(vec![0; self.grid.len()]);
+        }
+    }
+
+    /// Stops counting ages and drops them.
+    pub fn disable_ages(&mut self) {
+        self.ages = None;
+    }
+
+    /// How many generations the cell at `(row, col)` has been alive, 0 for a
+    /// dead one. `None` if it lies outside the world, or ages aren't being
+    /// counted, see `enable_ages`.
+    pub fn age(&self, row: u32, col: u32) -> Option<u32> {
+        let ages = self.ages.as_ref()?;
+        let cell = self.get(row, col)?;
+        Some(if cell.is_alive() {
+            ages[self.get_index(row, col)]
+        } else {
+            0
+        })
+    }
+
+    /// The age of the oldest live cell, see `age`.
+    pub fn max_age(&self) -> Option<u32> {
+        let ages = self.ages.as_ref()?;
+        Some(
+            ages.iter()
+                .zip(&self.grid)
+                .filter(|(_, cell)| cell.is_alive())
+                .map(|(&age, _)| age)
+                .max()
+                .unwrap_or(0),
+        )
+    }
+
+    // Ages the cells that survived the last generation, which the scratch
+    // buffer still holds.
+    pub(super) fn record_ages(&mut self) {
+        if let Some(ages) = &mut self.ages {
+            for ((age, now), before) in ages.iter_mut().zip(&self.grid).zip(&self.scratch) {
+                *age = if now.is_alive() && before.is_alive() {
+                    age.saturating_add(1)
+                } else {
+                    0
+                };
+            }
+        }
+    }
+
+    // Same as `record_ages`, with the cells at `changed` being the only
+    // ones that changed.
+    pub(super) fn record_ages_at(&mut self, changed: &[usize]) {
+        if let Some(ages) = &mut self.ages {
+            for (age, cell) in ages.iter_mut().zip(&self.grid) {
+                *age = if cell.is_alive() {
+                    age.saturating_add(1)
+                } else {
+                    0
+                };
+            }
+            for &idx in changed {
+                ages[idx] = 0;
+            }
+        }
+    }
+
+    // Starts the cell at `idx` over, after it was set to something else.
+    pub(super) fn reset_age(&mut self, idx: usize) {
+        if let Some(ages) = &mut self.ages {
+            ages[idx] = 0;
+        }
+    }
+
+    // Starts every cell over, after all of them were replaced.
+    pub(super) fn reset_ages(&mut self) {
+        if let Some(ages) = &mut self.ages {
+            ages.clear();
+            ages.resize(self.grid.len(), 0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::fixtures::blinker_and_block;
+    use crate::game::{world_parts::Cell::*, Rule, World};
+
+    #[test]
+    fn test_ages() {
+        let mut world = blinker_and_block();
+        assert_eq!(world.age(1, 1), None);
+        assert_eq!(world.max_age(), None);
+
+        world.enable_ages();
+        for _ in 0..5 {
+            world.evolve();
+        }
+        assert_eq!(world.age(1, 5), Some(5));
+        assert_eq!(world.age(2, 1), Some(5));
+        // The blinker's ends are born every other generation.
+        assert_eq!(world.age(2, 0), Some(0));
+        assert_eq!(world.age(1, 1), Some(0));
+        assert_eq!(world.age(0, 0), Some(0));
+        assert_eq!(world.age(9, 0), None);
+        assert_eq!(world.max_age(), Some(5));
+
+        world.set(1, 5, Dead).unwrap();
+        world.set(1, 5, Alive).unwrap();
+        assert_eq!(world.age(1, 5), Some(0));
+        world.toggle(2, 1).unwrap();
+        world.toggle(2, 1).unwrap();
+        assert_eq!(world.age(2, 1), Some(0));
+        assert_eq!(world.max_age(), Some(5));
+
+        world.clear();
+        assert_eq!(world.max_age(), Some(0));
+        world.disable_ages();
+        assert_eq!(world.age(1, 5), None);
+    }
+
+    #[test]
+    fn test_ages_every_evolve() {
+        let mut world = World::new_with_seed(30, 20, 2).with_rule(Rule::BRIANS_BRAIN);
+        world.set_rule(Rule::CONWAY);
+        world.enable_ages();
+        let mut sparse = world.clone();
+        for _ in 0..20 {
+            world.evolve();
+            sparse.evolve_sparse();
+        }
+        for (row, col, _) in world.iter() {
+            assert_eq!(sparse.age(row, col), world.age(row, col));
+        }
+        assert!(world.max_age().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_ages_expanding() {
+        let mut world = blinker_and_block();
+        world.enable_ages();
+        for _ in 0..4 {
+            world.evolve_expanding(3);
+        }
+        let (row, col) = (-world.origin().0 as u32, -world.origin().1 as u32);
+        assert_eq!(world.age(row + 1, col + 5), Some(4));
+    }
+}
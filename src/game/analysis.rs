@@ -0,0 +1,94 @@
+use super::World;
+
+impl World {
+    /// The share of cells that are alive, within `0.0..=1.0`.
+    pub fn density(&self) -> f64 {
+        self.population() as f64 / self.area() as f64
+    }
+
+    /// The density of every `block` by `block` square of the world, row by
+    /// row, with `width.div_ceil(block)` squares to a row. Squares cut off by
+    /// the right or bottom edge count only the cells they hold.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block` is zero.
+    pub fn density_grid(&self, block: u32) -> Vec<f64> {
+        assert!(block > 0, "block size must be at least 1");
+        let (width, block) = (self.width as usize, block as usize);
+        let cols = width.div_ceil(block);
+        let rows = (self.height as usize).div_ceil(block);
+
+        let mut alive = vec![0usize; cols * rows];
+        for (row, cells) in self.grid.chunks(width).enumerate() {
+            let counts = &mut alive[row / block * cols..][..cols];
+            for (count, cells) in counts.iter_mut().zip(cells.chunks(block)) {
+                *count += cells.iter().filter(|cell| cell.is_alive()).count();
+            }
+        }
+
+        let side = |i: usize, len: usize| block.min(len - i * block);
+        alive
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| {
+                let area = side(i / cols, self.height as usize) * side(i % cols, width);
+                count as f64 / area as f64
+            })
+            .collect()
+    }
+
+    /// The average position of the live cells as `(row, col)`, or `None` if
+    /// there are none. Like `bounding_box` this only looks at coordinates
+    /// within the grid, whatever the edges.
+    pub fn centroid(&self) -> Option<(f64, f64)> {
+        let (mut rows, mut cols, mut count) = (0.0, 0.0, 0usize);
+        for (row, col) in self.live_cells() {
+            rows += row as f64;
+            cols += col as f64;
+            count += 1;
+        }
+        (count > 0).then(|| (rows / count as f64, cols / count as f64))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::World;
+
+    #[test]
+    fn test_density() {
+        let world: World = "OO..\nO...".parse().unwrap();
+        assert_eq!(world.density(), 3.0 / 8.0);
+        assert_eq!(World::empty(3, 3).density(), 0.0);
+    }
+
+    #[test]
+    fn test_density_grid() {
+        let world: World = "OO.O.\nO..O.\n.....\n....O\nOOOOO".parse().unwrap();
+        assert_eq!(
+            world.density_grid(2),
+            [
+                0.75, 0.5, 0.0, //
+                0.0, 0.0, 0.5, //
+                1.0, 1.0, 1.0,
+            ]
+        );
+        assert_eq!(world.density_grid(5), [world.density()]);
+        assert_eq!(world.density_grid(10), [0.44]);
+        assert_eq!(world.density_grid(1).len(), 25);
+    }
+
+    #[test]
+    #[should_panic(expected = "block size must be at least 1")]
+    fn test_density_grid_zero_block() {
+        World::empty(3, 3).density_grid(0);
+    }
+
+    #[test]
+    fn test_centroid() {
+        let world: World = "O...\n....\n...O".parse().unwrap();
+        assert_eq!(world.centroid(), Some((1.0, 1.5)));
+        assert_eq!(World::empty(3, 3).centroid(), None);
+    }
+}
@@ -0,0 +1,106 @@
+use super::World;
+
+/// A rectangle of cells, with all four sides inclusive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub top: u32,
+    pub left: u32,
+    pub bottom: u32,
+    pub right: u32,
+}
+
+impl Rect {
+    pub fn width(&self) -> u32 {
+        self.right - self.left + 1
+    }
+
+    pub fn height(&self) -> u32 {
+        self.bottom - self.top + 1
+    }
+
+    // The smallest rectangle holding every one of `cells`, given as
+    // `(row, col)`.
+    pub(super) fn enclosing(cells: impl IntoIterator<Item = (u32, u32)>) -> Option<Rect> {
+        let mut cells = cells.into_iter();
+        let (row, col) = cells.next()?;
+        let first = Rect {
+            top: row,
+            left: col,
+            bottom: row,
+            right: col,
+        };
+        Some(cells.fold(first, |rect, (row, col)| Rect {
+            top: rect.top.min(row),
+            left: rect.left.min(col),
+            bottom: rect.bottom.max(row),
+            right: rect.right.max(col),
+        }))
+    }
+
+    /// Whether the cell at `(row, col)` lies within the rectangle.
+    pub fn contains(&self, row: u32, col: u32) -> bool {
+        (self.top..=self.bottom).contains(&row) && (self.left..=self.right).contains(&col)
+    }
+}
+
+impl World {
+    /// The smallest rectangle holding every live cell, or `None` if there
+    /// are none.
+    ///
+    /// This only looks at the coordinates within the grid. A pattern lying
+    /// across a wrapping edge gets a box reaching from one side to the
+    /// other, not the small one it would have across the edge.
+    pub fn bounding_box(&self) -> Option<Rect> {
+        Rect::enclosing(self.live_cells())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Rect;
+    use crate::game::{world_parts::Cell, World};
+
+    #[test]
+    fn test_bounding_box() {
+        let mut world = World::empty(6, 5);
+        assert_eq!(world.bounding_box(), None);
+
+        world.set(2, 3, Cell::Alive).unwrap();
+        let single = Rect {
+            top: 2,
+            left: 3,
+            bottom: 2,
+            right: 3,
+        };
+        assert_eq!(world.bounding_box(), Some(single));
+        assert_eq!((single.width(), single.height()), (1, 1));
+
+        // Dying cells don't count.
+        world.set(0, 0, Cell::Dying(1)).unwrap();
+        assert_eq!(world.bounding_box(), Some(single));
+
+        world.fill(Cell::Alive);
+        let full = world.bounding_box().unwrap();
+        assert_eq!((full.top, full.left, full.bottom, full.right), (0, 0, 4, 5));
+        assert_eq!((full.width(), full.height()), (6, 5));
+    }
+
+    #[test]
+    fn test_bounding_box_across_edges() {
+        // Both ends of the blinker lie on an edge, so the box spans the
+        // world even though it wraps round to just three rows.
+        let world: World = "..O.\n....\n....\n..O.\n..O.".parse().unwrap();
+        let rect = world.bounding_box().unwrap();
+        assert_eq!(
+            rect,
+            Rect {
+                top: 0,
+                left: 2,
+                bottom: 4,
+                right: 2
+            }
+        );
+        assert!(rect.contains(1, 2));
+        assert!(!rect.contains(1, 3));
+    }
+}
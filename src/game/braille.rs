@@ -0,0 +1,78 @@
+use super::World;
+
+// The bit of each dot of a Braille character, by its row and column in the
+// 2 by 4 block. Dots 1 to 3 and 7 make up the left column, 4 to 6 and 8 the
+// right one.
+const DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+impl World {
+    /// Draws the world with every 2 by 4 block of cells as one Braille
+    /// character, a dot for each live cell, so a large world fits in a
+    /// small terminal. Blocks cut off by the right or bottom edge are
+    /// filled up with dead cells.
+    ///
+    /// Rows are drawn square, without the shift of a hexagonal world.
+    pub fn render_braille(&self) -> String {
+        let lines = (0..self.height.div_ceil(4)).map(|line| {
+            (0..self.width.div_ceil(2))
+                .map(|block| {
+                    let mut bits = 0;
+                    for (d_row, dots) in DOTS.iter().enumerate() {
+                        for (d_col, dot) in dots.iter().enumerate() {
+                            let row = line * 4 + d_row as u32;
+                            let col = block * 2 + d_col as u32;
+                            if self.get(row, col).is_some_and(|cell| cell.is_alive()) {
+                                bits |= dot;
+                            }
+                        }
+                    }
+                    char::from_u32(0x2800 + bits).expect("Braille patterns are characters")
+                })
+                .collect::<String>()
+        });
+        lines.collect::<Vec<_>>().join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::World;
+
+    #[test]
+    fn test_render_braille_dots() {
+        // Each dot on its own, numbered as in Braille.
+        let dots = [
+            ("O.\n..\n..\n..", '⠁'),
+            ("..\nO.\n..\n..", '⠂'),
+            ("..\n..\nO.\n..", '⠄'),
+            (".O\n..\n..\n..", '⠈'),
+            ("..\n.O\n..\n..", '⠐'),
+            ("..\n..\n.O\n..", '⠠'),
+            ("..\n..\n..\nO.", '⡀'),
+            ("..\n..\n..\n.O", '⢀'),
+        ];
+        for (cells, glyph) in dots {
+            let world: World = cells.parse().unwrap();
+            assert_eq!(world.render_braille(), glyph.to_string(), "{}", cells);
+        }
+
+        let world: World = "OO\nOO\nOO\nOO".parse().unwrap();
+        assert_eq!(world.render_braille(), "⣿");
+        let world: World = "..\n..\n..\n..".parse().unwrap();
+        assert_eq!(world.render_braille(), "⠀");
+    }
+
+    #[test]
+    fn test_render_braille_edges() {
+        // A glider over dots 3, 4 and 6 of the first block and dots 2 and 3
+        // of the second, with the blocks on the right and at the bottom
+        // padded.
+        let world: World = ".O.\n..O\nOOO\n...\n.O.".parse().unwrap();
+        assert_eq!(world.render_braille(), "\u{282c}\u{2806}\n\u{2808}\u{2800}");
+
+        let world = World::empty(800, 400);
+        let rendered = world.render_braille();
+        assert_eq!(rendered.lines().count(), 100);
+        assert!(rendered.lines().all(|line| line.chars().count() == 400));
+    }
+}
@@ -0,0 +1,189 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use super::{
+    check_density, check_dimensions, world_parts::Cell, Boundary, Neighborhood, Rule, World,
+    WorldError,
+};
+
+/// Collects the options for creating a `World` and validates them all at once
+/// in `build`.
+///
+/// ```
+/// use game_of_life::game::WorldBuilder;
+///
+/// let world = WorldBuilder::new(80, 25)
+///     .seed(42)
+///     .density(0.3)
+///     .alive_at(10, 12)
+///     .alive_at(10, 13)
+///     .build()
+///     .unwrap();
+/// assert_eq!(world.width(), 80);
+/// ```
+#[derive(Clone, Debug)]
+pub struct WorldBuilder {
+    width: u32,
+    height: u32,
+    seed: Option<u64>,
+    density: Option<f64>,
+    rule: Rule,
+    neighborhood: Neighborhood,
+    boundary: Boundary,
+    alive: Vec<(u32, u32)>,
+}
+
+impl WorldBuilder {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            seed: None,
+            density: None,
+            rule: Rule::default(),
+            neighborhood: Neighborhood::default(),
+            boundary: Boundary::default(),
+            alive: Vec::new(),
+        }
+    }
+
+    /// Fills the world randomly from the given seed. Without a `density` each
+    /// cell has an even chance of being alive.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Fills the world randomly with the given chance of a cell being alive.
+    /// Without a `seed` the fill differs from run to run.
+    pub fn density(mut self, density: f64) -> Self {
+        self.density = Some(density);
+        self
+    }
+
+    pub fn rule(mut self, rule: Rule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    pub fn neighborhood(mut self, neighborhood: Neighborhood) -> Self {
+        self.neighborhood = neighborhood;
+        self
+    }
+
+    pub fn boundary(mut self, boundary: Boundary) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
+    /// Marks a cell as alive after any random fill has been applied.
+    pub fn alive_at(mut self, row: u32, col: u32) -> Self {
+        self.alive.push((row, col));
+        self
+    }
+
+    pub fn build(self) -> Result<World, WorldError> {
+        check_dimensions(self.width, self.height)?;
+
+        let mut world = World::empty(self.width, self.height)
+            .with_rule(self.rule)
+            .with_neighborhood(self.neighborhood)
+            .with_boundary(self.boundary);
+
+        if self.seed.is_some() || self.density.is_some() {
+            let density = self.density.unwrap_or(0.5);
+            check_density(density)?;
+
+            match self.seed {
+                Some(seed) => world.fill_random(&mut ChaCha8Rng::seed_from_u64(seed), density),
+                None => world.fill_random(&mut rand::thread_rng(), density),
+            }
+        }
+
+        for (row, col) in self.alive {
+            world.set(row, col, Cell::Alive)?;
+        }
+
+        Ok(world)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::WorldBuilder;
+    use crate::game::{world_parts::Cell::*, Rule, World, WorldError};
+
+    #[test]
+    fn test_build_empty() {
+        let world = WorldBuilder::new(5, 4).build().unwrap();
+        assert_eq!(world, World::empty(5, 4));
+    }
+
+    #[test]
+    fn test_build_alive_at() {
+        let world = WorldBuilder::new(3, 2)
+            .alive_at(0, 1)
+            .alive_at(1, 2)
+            .build()
+            .unwrap();
+        assert_eq!(world.grid, vec![Dead, Alive, Dead, Dead, Dead, Alive]);
+    }
+
+    #[test]
+    fn test_build_rule() {
+        let highlife = Rule::new(&[3, 6], &[2, 3]);
+        let world = WorldBuilder::new(3, 3).rule(highlife).build().unwrap();
+        assert_eq!(world.rule(), highlife);
+    }
+
+    #[test]
+    fn test_build_seeded() {
+        let a = WorldBuilder::new(20, 20).seed(3).density(0.3).build();
+        let b = WorldBuilder::new(20, 20).seed(3).density(0.3).build();
+        assert_eq!(a, b);
+
+        // Explicit cells are applied on top of the random fill.
+        let world = WorldBuilder::new(20, 20)
+            .seed(3)
+            .density(0.0)
+            .alive_at(19, 19)
+            .build()
+            .unwrap();
+        assert_eq!(world.grid.iter().filter(|cell| cell.is_alive()).count(), 1);
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let result = WorldBuilder::new(0, 3).build();
+        assert_eq!(
+            result,
+            Err(WorldError::ZeroDimension {
+                width: 0,
+                height: 3
+            })
+        );
+
+        let result = WorldBuilder::new(u32::MAX, u32::MAX).build();
+        assert_eq!(
+            result,
+            Err(WorldError::TooLarge {
+                width: u32::MAX,
+                height: u32::MAX
+            })
+        );
+
+        let result = WorldBuilder::new(3, 3).density(-0.1).build();
+        assert_eq!(result, Err(WorldError::InvalidDensity(-0.1)));
+
+        let result = WorldBuilder::new(3, 3).alive_at(1, 3).build();
+        assert_eq!(
+            result,
+            Err(WorldError::OutOfBounds {
+                row: 1,
+                col: 3,
+                width: 3,
+                height: 3
+            })
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::{BTreeMap, HashSet};
+
+use super::{bounds::Rect, world_parts::Cell, Boundary, EdgeMode, Rule, World};
+
+/// The objects `World::census` knows by name, as they look under Conway's
+/// rule.
+const OBJECTS: [(&str, &str); 7] = [
+    ("block", "OO\nOO"),
+    ("beehive", ".OO.\nO..O\n.OO."),
+    ("blinker", "OOO"),
+    ("loaf", ".OO.\nO..O\n.O.O\n..O."),
+    ("boat", "OO.\nO.O\n.O."),
+    ("tub", ".O.\nO.O\n.O."),
+    ("glider", ".O.\n..O\nOOO"),
+];
+
+// No known object has a period longer than this.
+const MAX_PERIOD: u32 = 4;
+
+/// The objects a world is made up of, see `World::census`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Census {
+    /// How many of each known object there are, by name. Objects that
+    /// don't occur are left out.
+    pub objects: BTreeMap<&'static str, usize>,
+    /// Every object that isn't one of the known ones.
+    pub unrecognized: Vec<UnknownObject>,
+}
+
+impl Census {
+    /// How many of the object called `name` there are.
+    pub fn count(&self, name: &str) -> usize {
+        self.objects.get(name).copied().unwrap_or(0)
+    }
+
+    /// The number of objects, known or not.
+    pub fn total(&self) -> usize {
+        self.objects.values().sum::<usize>() + self.unrecognized.len()
+    }
+}
+
+/// An object `World::census` doesn't know.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownObject {
+    pub population: usize,
+    pub bounding_box: Rect,
+}
+
+impl World {
+    /// Counts the blocks, beehives, blinkers, loaves, boats, tubs and
+    /// gliders among the world's components, in whatever phase and
+    /// orientation they are. Meant for the ash a soup leaves behind, see
+    /// `run_until_stable`.
+    ///
+    /// Objects are told apart by their shape, so the names only mean
+    /// something under Conway's rule. Objects touching each other form a
+    /// single component and aren't recognized.
+    pub fn census(&self) -> Census {
+        let known: Vec<_> = OBJECTS
+            .iter()
+            .map(|&(name, cells)| {
+                let world: World = cells.parse().expect("known objects parse");
+                let cells: Vec<_> = world
+                    .live_cells()
+                    .map(|(row, col)| (row as i64, col as i64))
+                    .collect();
+                (name, canonical_phase(&cells, Rule::CONWAY))
+            })
+            .collect();
+
+        let mut census = Census::default();
+        for component in self.components() {
+            let cells = self.unwrapped(&component.cells);
+            let shape = canonical_phase(&cells, self.rule);
+            match known.iter().find(|(_, known)| *known == shape) {
+                Some(&(name, _)) => *census.objects.entry(name).or_default() += 1,
+                None => census.unrecognized.push(UnknownObject {
+                    population: component.population(),
+                    bounding_box: component.bounding_box,
+                }),
+            }
+        }
+        census
+    }
+
+    // The cells of a component as they lie next to each other, following
+    // them across wrapping edges.
+    fn unwrapped(&self, cells: &[(u32, u32)]) -> Vec<(i64, i64)> {
+        let mut left: HashSet<_> = cells.iter().copied().collect();
+        let mut unwrapped = Vec::with_capacity(cells.len());
+        let mut stack = vec![(cells[0], (cells[0].0 as i64, cells[0].1 as i64))];
+        left.remove(&cells[0]);
+        while let Some((_, (row, col))) = stack.pop() {
+            unwrapped.push((row, col));
+            for d_row in -1..=1 {
+                for d_col in -1..=1 {
+                    let (row, col) = (row + d_row, col + d_col);
+                    let Some((grid_row, grid_col)) =
+                        self.boundary.resolve(row, col, self.width, self.height)
+                    else {
+                        continue;
+                    };
+                    let cell = (grid_row as u32, grid_col as u32);
+                    if left.remove(&cell) {
+                        stack.push((cell, (row, col)));
+                    }
+                }
+            }
+        }
+        unwrapped
+    }
+}
+
+// The canonical shape of `cells` over every phase they go through under
+// `rule`, as long as they come back to their shape within `MAX_PERIOD`
+// generations. Otherwise it's only that of the cells as they are.
+fn canonical_phase(cells: &[(i64, i64)], rule: Rule) -> Vec<(u32, u32)> {
+    let first = canonical(cells);
+    let (height, width) = first.iter().fold((0, 0), |(h, w), &(row, col)| {
+        (h.max(row + 1), w.max(col + 1))
+    });
+
+    // Room enough for the object to move or grow in every direction.
+    let margin = MAX_PERIOD + 1;
+    let mut world = World::empty(width + 2 * margin, height + 2 * margin)
+        .with_rule(rule)
+        .with_boundary(Boundary::uniform(EdgeMode::Dead));
+    for &(row, col) in &first {
+        world.set(row + margin, col + margin, Cell::Alive).unwrap();
+    }
+
+    let mut phases = vec![first.clone()];
+    for _ in 0..MAX_PERIOD {
+        world.evolve();
+        let cells: Vec<_> = world
+            .live_cells()
+            .map(|(row, col)| (row as i64, col as i64))
+            .collect();
+        let phase = canonical(&cells);
+        if phase == first {
+            return phases.into_iter().min().unwrap();
+        }
+        phases.push(phase);
+    }
+    first
+}
+
+// The smallest of the eight rotations and reflections of `cells`, moved
+// to touch the top and left edges and sorted.
+fn canonical(cells: &[(i64, i64)]) -> Vec<(u32, u32)> {
+    // Each orientation flips the rows, the columns and swaps the two or not.
+    (0..8)
+        .map(|orientation| {
+            let turned: Vec<_> = cells
+                .iter()
+                .map(|&(row, col)| {
+                    let row = if orientation & 1 == 0 { row } else { -row };
+                    let col = if orientation & 2 == 0 { col } else { -col };
+                    if orientation & 4 == 0 {
+                        (row, col)
+                    } else {
+                        (col, row)
+                    }
+                })
+                .collect();
+            let top = turned.iter().map(|&(row, _)| row).min().unwrap_or(0);
+            let left = turned.iter().map(|&(_, col)| col).min().unwrap_or(0);
+            let mut shape: Vec<_> = turned
+                .into_iter()
+                .map(|(row, col)| ((row - top) as u32, (col - left) as u32))
+                .collect();
+            shape.sort_unstable();
+            shape
+        })
+        .min()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{canonical, canonical_phase, UnknownObject};
+    use crate::game::{Pattern, Rect, Rule, World};
+
+    fn cells(text: &str) -> Vec<(i64, i64)> {
+        let world: World = text.parse().unwrap();
+        world
+            .live_cells()
+            .map(|(row, col)| (row as i64, col as i64))
+            .collect()
+    }
+
+    #[test]
+    fn test_canonical() {
+        // Every orientation of a boat, wherever it lies, has the same shape.
+        let boat = canonical(&cells("OO.\nO.O\n.O."));
+        assert_eq!(boat, canonical(&cells(".O.\nO.O\n.OO")));
+        assert_eq!(boat, canonical(&cells("....\n..OO\n.O.O\n..O.")));
+        assert_eq!(boat, canonical(&cells(".O.\nO.O\nOO.")));
+        assert_eq!(boat, [(0, 0), (0, 1), (1, 0), (1, 2), (2, 1)]);
+        assert_ne!(boat, canonical(&cells(".O.\nO.O\n.O.")));
+
+        let blinker = canonical(&cells("OOO"));
+        assert_eq!(blinker, canonical(&cells("O\nO\nO")));
+        assert_eq!(blinker, [(0, 0), (0, 1), (0, 2)]);
+    }
+
+    #[test]
+    fn test_canonical_phase() {
+        let glider = canonical_phase(&cells(".O.\n..O\nOOO"), Rule::CONWAY);
+        let mut world: World = ".O....\n..O...\nOOO...\n......\n......\n......"
+            .parse()
+            .unwrap();
+        for _ in 0..4 {
+            world.evolve();
+            let phase: Vec<_> = world
+                .live_cells()
+                .map(|(row, col)| (row as i64, col as i64))
+                .collect();
+            assert_eq!(canonical_phase(&phase, Rule::CONWAY), glider);
+        }
+
+        // A pre-block turns into a block, but isn't one yet.
+        let pre_block = canonical_phase(&cells("OO\nO."), Rule::CONWAY);
+        assert_eq!(pre_block, canonical(&cells("OO\nO.")));
+    }
+
+    #[test]
+    fn test_census() {
+        let mut world = World::empty(30, 20);
+        let objects = [
+            ("OO\nOO", 1, 1),
+            ("OO\nOO", 1, 10),
+            (".OO.\nO..O\n.OO.", 6, 1),
+            ("O\nO\nO", 6, 10),
+            (".OO.\nO..O\n.O.O\n..O.", 12, 1),
+            (".O.\nO.O\nOO.", 12, 10),
+            (".O.\nO.O\n.O.", 1, 20),
+            ("OO.\nO.O\nO..", 7, 20),
+            ("O..O\n.OO.", 14, 20),
+        ];
+        for (text, row, col) in objects {
+            let pattern = Pattern::from(text.parse::<World>().unwrap());
+            world.place(&pattern, row, col).unwrap();
+        }
+
+        let census = world.census();
+        assert_eq!(census.count("block"), 2);
+        for name in ["beehive", "blinker", "loaf", "boat", "tub"] {
+            assert_eq!(census.count(name), 1, "{}", name);
+        }
+        // In another phase and orientation than the known one.
+        assert_eq!(census.count("glider"), 1);
+        assert_eq!(census.count("pond"), 0);
+        assert_eq!(
+            census.unrecognized,
+            [UnknownObject {
+                population: 4,
+                bounding_box: Rect {
+                    top: 14,
+                    left: 20,
+                    bottom: 15,
+                    right: 23
+                }
+            }]
+        );
+        assert_eq!(census.total(), 9);
+    }
+
+    #[test]
+    fn test_census_across_edges() {
+        // A beehive split over the left and right edges, and a blinker over
+        // the top and bottom ones.
+        let world: World = "...O...\n...O...\n.......\nO.....O\n.O...O.\nO.....O\n...O..."
+            .parse()
+            .unwrap();
+        let census = world.census();
+        assert_eq!(census.count("beehive"), 1);
+        assert_eq!(census.count("blinker"), 1);
+        assert_eq!(census.total(), 2);
+    }
+}
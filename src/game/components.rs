@@ -0,0 +1,118 @@
+use super::{bounds::Rect, World};
+
+/// A group of live cells that touch each other, see `World::components`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Component {
+    /// The cells as `(row, col)`, in row order.
+    pub cells: Vec<(u32, u32)>,
+    /// The smallest rectangle holding every cell, see `World::bounding_box`
+    /// for components lying across a wrapping edge.
+    pub bounding_box: Rect,
+}
+
+impl Component {
+    pub fn population(&self) -> usize {
+        self.cells.len()
+    }
+}
+
+impl World {
+    /// Splits the live cells into groups of cells that touch, including
+    /// diagonally. Cells touch across edges the same way they see each
+    /// other as neighbours, so on a torus a group lying across an edge is
+    /// one component.
+    ///
+    /// The components come in the order of their first cell, in row order.
+    pub fn components(&self) -> Vec<Component> {
+        let width = self.width as usize;
+        let mut seen = vec![false; self.grid.len()];
+        let mut components = Vec::new();
+        let mut stack = Vec::new();
+
+        for start in 0..self.grid.len() {
+            if seen[start] || !self.grid[start].is_alive() {
+                continue;
+            }
+
+            seen[start] = true;
+            stack.push(start);
+            let mut cells = Vec::new();
+            while let Some(idx) = stack.pop() {
+                let (row, col) = (idx / width, idx % width);
+                cells.push((row as u32, col as u32));
+                for d_row in -1..=1 {
+                    for d_col in -1..=1 {
+                        let Some((row, col)) = self.boundary.resolve(
+                            row as i64 + d_row,
+                            col as i64 + d_col,
+                            self.width,
+                            self.height,
+                        ) else {
+                            continue;
+                        };
+                        let idx = row * width + col;
+                        if !seen[idx] && self.grid[idx].is_alive() {
+                            seen[idx] = true;
+                            stack.push(idx);
+                        }
+                    }
+                }
+            }
+
+            cells.sort_unstable();
+            // A component holds at least the cell it started from.
+            let bounding_box = Rect::enclosing(cells.iter().copied()).unwrap();
+            components.push(Component {
+                cells,
+                bounding_box,
+            });
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{Boundary, EdgeMode, Rect, World};
+
+    #[test]
+    fn test_components_blocks() {
+        let world: World = ".......\n.OO....\n.OO.OO.\n....OO.\n......."
+            .parse()
+            .unwrap();
+        let components = world.components();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].cells, [(1, 1), (1, 2), (2, 1), (2, 2)]);
+        assert_eq!(
+            components[1].bounding_box,
+            Rect {
+                top: 2,
+                left: 4,
+                bottom: 3,
+                right: 5
+            }
+        );
+        assert!(components.iter().all(|c| c.population() == 4));
+        assert!(World::empty(3, 3).components().is_empty());
+    }
+
+    #[test]
+    fn test_components_glider() {
+        let world: World = "......\n..O...\n...O..\n.OOO..\n......".parse().unwrap();
+        let components = world.components();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].population(), 5);
+    }
+
+    #[test]
+    fn test_components_across_edges() {
+        // A block split over all four corners.
+        let world: World = "O...O\n.....\n.....\nO...O".parse().unwrap();
+        let components = world.components();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].population(), 4);
+
+        let world = world.with_boundary(Boundary::uniform(EdgeMode::Dead));
+        assert_eq!(world.components().len(), 4);
+    }
+}
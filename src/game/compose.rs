@@ -0,0 +1,139 @@
+use super::{world_parts::Cell, World, WorldError};
+
+impl World {
+    /// A world with the cells alive in either of the worlds, and the rule
+    /// and edges of this one. See `union_with`.
+    pub fn union(&self, other: &World) -> Result<World, WorldError> {
+        let mut world = self.clone();
+        world.union_with(other)?;
+        Ok(world)
+    }
+
+    /// A world with the cells alive in both of the worlds, see `union`.
+    pub fn intersect(&self, other: &World) -> Result<World, WorldError> {
+        let mut world = self.clone();
+        world.intersect_with(other)?;
+        Ok(world)
+    }
+
+    /// A world with the cells alive in exactly one of the worlds, see
+    /// `union`.
+    pub fn xor(&self, other: &World) -> Result<World, WorldError> {
+        let mut world = self.clone();
+        world.xor_with(other)?;
+        Ok(world)
+    }
+
+    /// Brings every cell to life that is alive in `other`. All other cells
+    /// die, as dying cells count as dead.
+    ///
+    /// Returns an error if the worlds differ in size.
+    pub fn union_with(&mut self, other: &World) -> Result<(), WorldError> {
+        self.combine_with(other, |a, b| a || b)
+    }
+
+    /// Kills every cell that isn't alive in `other`, see `union_with`.
+    pub fn intersect_with(&mut self, other: &World) -> Result<(), WorldError> {
+        self.combine_with(other, |a, b| a && b)
+    }
+
+    /// Keeps the cells alive in only one of the worlds, see `union_with`.
+    pub fn xor_with(&mut self, other: &World) -> Result<(), WorldError> {
+        self.combine_with(other, |a, b| a != b)
+    }
+
+    // Sets every cell to whether `op` holds for it being alive here and in
+    // `other`.
+    fn combine_with(
+        &mut self,
+        other: &World,
+        op: fn(bool, bool) -> bool,
+    ) -> Result<(), WorldError> {
+        self.check_same_size(other)?;
+
+        let mut population = 0;
+        for (idx, theirs) in other.grid.iter().enumerate() {
+            let cell = Cell::from(op(self.grid[idx].is_alive(), theirs.is_alive()));
+            // Cells that stay alive keep their age, as with `set`.
+            if cell != self.grid[idx] {
+                self.grid[idx] = cell;
+                self.reset_age(idx);
+            }
+            population += cell.is_alive() as usize;
+        }
+        self.population = Some(population);
+        self.changed = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{world_parts::Cell, World, WorldError};
+
+    fn worlds() -> (World, World) {
+        let a = "OO..\nO...".parse().unwrap();
+        let b = ".O.O\n.O..".parse().unwrap();
+        (a, b)
+    }
+
+    #[test]
+    fn test_union() {
+        let (a, b) = worlds();
+        let expected: World = "OO.O\nOO..".parse().unwrap();
+        assert_eq!(a.union(&b).unwrap(), expected);
+        assert_eq!(b.union(&a).unwrap(), expected);
+        assert_eq!(a.union(&b).unwrap().population(), 5);
+    }
+
+    #[test]
+    fn test_intersect() {
+        let (a, b) = worlds();
+        let expected: World = ".O..\n....".parse().unwrap();
+        assert_eq!(a.intersect(&b).unwrap(), expected);
+        assert_eq!(a.intersect(&a).unwrap(), a);
+    }
+
+    #[test]
+    fn test_xor() {
+        let (mut a, b) = worlds();
+        let expected: World = "O..O\nOO..".parse().unwrap();
+        assert_eq!(a.xor(&b).unwrap(), expected);
+        assert!(a.xor(&a).unwrap().is_empty());
+
+        // Dying cells count as dead.
+        a.set(1, 3, Cell::Dying(1)).unwrap();
+        a.xor_with(&b).unwrap();
+        assert_eq!(a, expected);
+        assert_eq!(a.population(), 4);
+    }
+
+    #[test]
+    fn test_combine_ages() {
+        let mut world: World = "OO..\nOO..\n....\n....".parse().unwrap();
+        world.enable_ages();
+        world.evolve();
+        world.evolve();
+
+        let other: World = "O...\n....\n....\n...O".parse().unwrap();
+        world.xor_with(&other).unwrap();
+        world.union_with(&other).unwrap();
+        assert_eq!(world.age(0, 1), Some(2));
+        // Cells that died or came to life start over.
+        assert_eq!(world.age(0, 0), Some(0));
+        assert_eq!(world.age(3, 3), Some(0));
+    }
+
+    #[test]
+    fn test_combine_dimension_mismatch() {
+        let (mut a, _) = worlds();
+        let before = a.clone();
+        let error = WorldError::DimensionMismatch {
+            expected: (4, 2),
+            actual: (2, 4),
+        };
+        assert_eq!(a.union(&World::empty(2, 4)), Err(error.clone()));
+        assert_eq!(a.intersect_with(&World::empty(2, 4)), Err(error));
+        assert_eq!(a, before);
+    }
+}
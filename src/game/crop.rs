@@ -0,0 +1,132 @@
+use super::{check_dimensions, world_parts::Cell, Topology, World};
+
+impl World {
+    /// The smallest part of the world that holds every live cell, with the
+    /// same rule and edges. See `crop_with_margin`.
+    pub fn crop(&self) -> World {
+        self.crop_with_margin(0)
+    }
+
+    /// Same as `crop`, with `margin` dead cells added on every side. Cells
+    /// the margin takes in from the world around are copied as they are, and
+    /// those beyond its edges are dead. The origin moves along, so cells keep
+    /// their coordinates.
+    ///
+    /// A world without live cells crops to a single dead cell at its top
+    /// left corner. A hexagonal world keeps an even height and its shifted
+    /// rows shifted, so it may get an extra row at the top or bottom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the margin makes the world too large, see `World::new`.
+    pub fn crop_with_margin(&self, margin: u32) -> World {
+        let Some(rect) = self.bounding_box() else {
+            let mut world = self.with_cells(1, 1, vec![Cell::Dead]);
+            if self.topology == Topology::Hex {
+                world.height = 2;
+                world.grid.push(Cell::Dead);
+            }
+            return world;
+        };
+
+        // Bottom and right exclusive from here on.
+        let margin = margin as i64;
+        let (mut top, mut bottom) = (rect.top as i64 - margin, rect.bottom as i64 + 1 + margin);
+        let (left, right) = (rect.left as i64 - margin, rect.right as i64 + 1 + margin);
+        if self.topology == Topology::Hex {
+            top -= top.rem_euclid(2);
+            bottom += (bottom - top) % 2;
+        }
+
+        // A side longer than `u32::MAX` is too large as well.
+        let side = |len: i64| u32::try_from(len).unwrap_or(u32::MAX);
+        let (width, height) = (side(right - left), side(bottom - top));
+        if let Err(e) = check_dimensions(width, height) {
+            panic!("invalid world dimensions: {}", e);
+        }
+
+        let mut grid = Vec::with_capacity(width as usize * height as usize);
+        for row in top..bottom {
+            for col in left..right {
+                let inside =
+                    (0..self.height as i64).contains(&row) && (0..self.width as i64).contains(&col);
+                grid.push(if inside {
+                    self.grid[self.get_index(row as u32, col as u32)]
+                } else {
+                    Cell::Dead
+                });
+            }
+        }
+
+        let mut world = self.with_cells(width, height, grid);
+        world.origin = (self.origin.0 + top, self.origin.1 + left);
+        world
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{world_parts::Cell, Rule, Topology, World};
+    use crate::patterns;
+
+    #[test]
+    fn test_crop() {
+        let world: World = "......\n..O...\n...O..\n.OOO..\n......\n......"
+            .parse()
+            .unwrap();
+        let world = world.with_rule(Rule::HIGHLIFE);
+        let cropped = world.crop();
+        let glider = patterns::glider();
+        assert_eq!(cropped.grid, glider.grid);
+        assert_eq!(cropped.rule(), Rule::HIGHLIFE);
+        assert_eq!(cropped.origin(), (1, 1));
+        assert_eq!(cropped.population(), 5);
+    }
+
+    #[test]
+    fn test_crop_with_margin() {
+        let world: World = "O..\n...\n..O".parse().unwrap();
+        let cropped = world.crop_with_margin(1);
+        let expected: World = ".....\n.O...\n.....\n...O.\n.....".parse().unwrap();
+        assert_eq!(cropped.grid, expected.grid);
+        assert_eq!(cropped.origin(), (-1, -1));
+
+        // The margin takes in the cells around, live or not.
+        let mut world: World = ".....\n..O..\n.....".parse().unwrap();
+        world.set(1, 1, Cell::Dying(1)).unwrap();
+        let cropped = world.crop_with_margin(1);
+        assert_eq!(
+            cropped.get_row(1),
+            [Cell::Dying(1), Cell::Alive, Cell::Dead]
+        );
+        assert_eq!(cropped.population(), 1);
+    }
+
+    #[test]
+    fn test_crop_empty() {
+        let world = World::empty(10, 8);
+        let cropped = world.crop_with_margin(3);
+        assert_eq!((cropped.width(), cropped.height()), (1, 1));
+        assert_eq!(cropped.grid, [Cell::Dead]);
+
+        let world = World::empty(10, 8).with_topology(Topology::Hex).unwrap();
+        assert_eq!(world.crop().height(), 2);
+    }
+
+    #[test]
+    fn test_crop_hex() {
+        let mut world = World::empty(6, 6).with_topology(Topology::Hex).unwrap();
+        world.set(3, 2, Cell::Alive).unwrap();
+        let cropped = world.crop();
+        assert_eq!((cropped.width(), cropped.height()), (1, 2));
+        assert_eq!(cropped.origin(), (2, 2));
+        assert_eq!(cropped.grid, [Cell::Dead, Cell::Alive]);
+    }
+
+    #[test]
+    #[should_panic(expected = "has too many cells")]
+    fn test_crop_margin_too_large() {
+        let world: World = "O".parse().unwrap();
+        world.crop_with_margin(u32::MAX);
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt;
+
+use super::{World, WorldError};
+
+/// Where two worlds of the same size differ in which cells are alive, see
+/// `World::diff`. Both lists are in row order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WorldDiff {
+    width: u32,
+    height: u32,
+    /// The cells alive only in the world `diff` was called on.
+    pub removed: Vec<(u32, u32)>,
+    /// The cells alive only in the other world.
+    pub added: Vec<(u32, u32)>,
+}
+
+impl WorldDiff {
+    /// The number of cells that differ.
+    pub fn len(&self) -> usize {
+        self.removed.len() + self.added.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Draws the world with `-` for cells alive only in the first world, `+`
+/// for those alive only in the other, and `.` for the rest.
+impl fmt::Display for WorldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (mut removed, mut added) =
+            (self.removed.iter().peekable(), self.added.iter().peekable());
+        for row in 0..self.height {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            for col in 0..self.width {
+                let symbol = if removed.next_if_eq(&&(row, col)).is_some() {
+                    '-'
+                } else if added.next_if_eq(&&(row, col)).is_some() {
+                    '+'
+                } else {
+                    '.'
+                };
+                write!(f, "{}", symbol)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl World {
+    /// The cells that are alive in only one of the worlds. Dying cells count
+    /// as dead, so only a change of what is alive shows up.
+    ///
+    /// Returns an error if the worlds differ in size.
+    pub fn diff(&self, other: &World) -> Result<WorldDiff, WorldError> {
+        self.check_same_size(other)?;
+
+        let mut diff = WorldDiff {
+            width: self.width,
+            height: self.height,
+            removed: Vec::new(),
+            added: Vec::new(),
+        };
+        for ((row, col, before), (_, _, after)) in self.iter().zip(other.iter()) {
+            match (before.is_alive(), after.is_alive()) {
+                (true, false) => diff.removed.push((row, col)),
+                (false, true) => diff.added.push((row, col)),
+                _ => {}
+            }
+        }
+        Ok(diff)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{World, WorldError};
+
+    #[test]
+    fn test_diff_blinker() {
+        let world: World = ".....\n..O..\n..O..\n..O..\n.....".parse().unwrap();
+        let mut next = world.clone();
+        next.evolve();
+
+        let diff = world.diff(&next).unwrap();
+        assert_eq!(diff.removed, [(1, 2), (3, 2)]);
+        assert_eq!(diff.added, [(2, 1), (2, 3)]);
+        assert_eq!(diff.len(), 4);
+        assert_eq!(diff.to_string(), ".....\n..-..\n.+.+.\n..-..\n.....");
+
+        assert!(world.diff(&world).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_diff_dimension_mismatch() {
+        assert_eq!(
+            World::empty(4, 3).diff(&World::empty(3, 4)),
+            Err(WorldError::DimensionMismatch {
+                expected: (4, 3),
+                actual: (3, 4)
+            })
+        );
+    }
+}
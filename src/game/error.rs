@@ -0,0 +1,77 @@
+use std::{error::Error, fmt};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WorldError {
+    ZeroDimension {
+        width: u32,
+        height: u32,
+    },
+    TooLarge {
+        width: u32,
+        height: u32,
+    },
+    LengthMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    InvalidDensity(f64),
+    InvalidProbability(f64),
+    OutOfBounds {
+        row: u32,
+        col: u32,
+        width: u32,
+        height: u32,
+    },
+    OddHexHeight(u32),
+    /// Two worlds that have to be the same size aren't, as `(width, height)`.
+    DimensionMismatch {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+}
+
+impl fmt::Display for WorldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorldError::ZeroDimension { width, height } => {
+                write!(
+                    f,
+                    "world dimensions must be non-zero, got {}x{}",
+                    width, height
+                )
+            }
+            WorldError::TooLarge { width, height } => {
+                write!(f, "a {}x{} world has too many cells", width, height)
+            }
+            WorldError::LengthMismatch { expected, actual } => {
+                write!(f, "expected {} cells, got {}", expected, actual)
+            }
+            WorldError::InvalidDensity(density) => {
+                write!(f, "density must lie within 0.0..=1.0, got {}", density)
+            }
+            WorldError::InvalidProbability(p) => {
+                write!(f, "probability must lie within 0.0..=1.0, got {}", p)
+            }
+            WorldError::OutOfBounds {
+                row,
+                col,
+                width,
+                height,
+            } => write!(
+                f,
+                "cell ({}, {}) lies outside the {}x{} world",
+                row, col, width, height
+            ),
+            WorldError::OddHexHeight(height) => {
+                write!(f, "a hexagonal world needs an even height, got {}", height)
+            }
+            WorldError::DimensionMismatch { expected, actual } => write!(
+                f,
+                "expected a {}x{} world, got {}x{}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+        }
+    }
+}
+
+impl Error for WorldError {}
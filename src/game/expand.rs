@@ -0,0 +1,173 @@
+use super::{world_parts::Cell, Topology, World};
+
+impl World {
+    /// Where the top left cell lies relative to where it was when the world
+    /// was created, as `(row, col)`. Growing the world with
+    /// `evolve_expanding` moves it up and to the left, so a cell keeps its
+    /// position `(row - origin.0, col - origin.1)` in the grid.
+    pub fn origin(&self) -> (i64, i64) {
+        self.origin
+    }
+
+    /// Like `evolve`, but first grows the world wherever live cells have come
+    /// within `margin` cells of an edge, so they never meet the edge. Each
+    /// side grows by at least half of the world's size at a time, which keeps
+    /// the reallocations rare.
+    pub fn evolve_expanding(&mut self, margin: u32) {
+        // Cells closer to an edge than the rule's radius would already see
+        // the other side.
+        let margin = margin.max(self.rule.radius() as u32) as usize;
+        let (width, height) = (self.width as usize, self.height as usize);
+
+        let (mut top, mut bottom, mut left, mut right) = (0, 0, 0, 0);
+        for row in 0..height {
+            let cells = &self.grid[row * width..(row + 1) * width];
+            let (Some(first), Some(last)) = (
+                cells.iter().position(Cell::is_alive),
+                cells.iter().rposition(Cell::is_alive),
+            ) else {
+                continue;
+            };
+
+            if row < margin {
+                top = top.max(margin - row);
+            }
+            if row + margin >= height {
+                bottom = bottom.max(row + margin + 1 - height);
+            }
+            if first < margin {
+                left = left.max(margin - first);
+            }
+            if last + margin >= width {
+                right = right.max(last + margin + 1 - width);
+            }
+        }
+
+        let grow = |needed: usize, len: usize| {
+            if needed == 0 {
+                0
+            } else {
+                needed.max(len / 2)
+            }
+        };
+        let (mut top, mut bottom) = (grow(top, height), grow(bottom, height));
+        let (left, right) = (grow(left, width), grow(right, width));
+        if self.topology == Topology::Hex {
+            // Shifted rows have to stay odd, and the height even.
+            top += top % 2;
+            bottom += bottom % 2;
+        }
+
+        if top + bottom + left + right > 0 {
+            self.grow(top, bottom, left, right);
+        }
+        self.evolve();
+    }
+
+    fn grow(&mut self, top: usize, bottom: usize, left: usize, right: usize) {
+        let sides = [top, bottom, left, right];
+        self.grid = grown(&self.grid, self.width, self.height, sides);
+        self.changed = None;
+        if let Some(heatmap) = &mut self.heatmap {
+            heatmap.grow(sides);
+        }
+        if let Some(ages) = &mut self.ages {
+            *ages = grown(ages, self.width, self.height, sides);
+        }
+        self.width += (left + right) as u32;
+        self.height += (top + bottom) as u32;
+        self.origin = (self.origin.0 - top as i64, self.origin.1 - left as i64);
+    }
+}
+
+// A copy of the `width` by `height` grid of `values` with as many default
+// values added on each side as `sides` says, in the order top, bottom,
+// left, right.
+pub(super) fn grown<T: Copy + Default>(
+    values: &[T],
+    width: u32,
+    height: u32,
+    [top, bottom, left, right]: [usize; 4],
+) -> Vec<T> {
+    let (width, height) = (width as usize, height as usize);
+    let new_width = left + width + right;
+    let new_height = top + height + bottom;
+
+    let mut grown = vec![T::default(); new_width * new_height];
+    for (row, old) in values.chunks(width).enumerate() {
+        let start = (top + row) * new_width + left;
+        grown[start..start + width].copy_from_slice(old);
+    }
+    grown
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{Overflow, SparseWorld, Topology, World};
+    use crate::patterns;
+
+    #[test]
+    fn test_expanding_keeps_coordinates() {
+        let glider = patterns::glider();
+        let mut world = glider.clone();
+        world.evolve_expanding(1);
+
+        // Every side was too close, so the world grew all round.
+        assert_eq!((world.width(), world.height()), (5, 5));
+        assert_eq!(world.origin(), (-1, -1));
+
+        // The glider evolved as if on an unbounded plane, at its old place.
+        let mut expected = SparseWorld::from(&glider);
+        expected.evolve();
+        let (top, left) = world.origin();
+        let plane = expected
+            .to_world(top, left, world.width(), world.height())
+            .unwrap();
+        assert_eq!(plane.grid, world.grid);
+    }
+
+    #[test]
+    fn test_expanding_leaves_room() {
+        // Nothing is near an edge, so the world stays as it is.
+        let mut world = World::empty(10, 10);
+        let block: World = "OO\nOO".parse().unwrap();
+        world.insert(&block, 4, 4, Overflow::Error).unwrap();
+        let before = world.clone();
+        world.evolve_expanding(2);
+        assert_eq!(world, before);
+
+        // A larger margin makes it grow.
+        world.evolve_expanding(5);
+        assert_eq!((world.width(), world.height()), (20, 20));
+        assert_eq!(world.origin(), (-5, -5));
+    }
+
+    #[test]
+    fn test_expanding_r_pentomino() {
+        // On a small torus the r-pentomino soon runs into itself, but an
+        // expanding world follows the unbounded plane exactly.
+        let r_pentomino: World = ".OO\nOO.\n.O.".parse().unwrap();
+        let mut world = r_pentomino.clone();
+        let mut plane = SparseWorld::from(&r_pentomino);
+        for _ in 0..200 {
+            world.evolve_expanding(1);
+            plane.evolve();
+        }
+
+        let (top, left) = world.origin();
+        let expected = plane
+            .to_world(top, left, world.width(), world.height())
+            .unwrap();
+        assert_eq!(expected.grid, world.grid);
+        assert_eq!(plane.population(), 120);
+    }
+
+    #[test]
+    fn test_expanding_hex() {
+        let mut world: World = "O...\n....".parse().unwrap();
+        world.set_topology(Topology::Hex).unwrap();
+        world.evolve_expanding(1);
+        assert_eq!(world.height() % 2, 0);
+        assert_eq!(world.origin().0 % 2, 0);
+    }
+}
@@ -0,0 +1,10 @@
+// Worlds the tests of more than one module start from.
+
+use super::World;
+
+// A vertical blinker and a block, far enough apart not to touch.
+pub(super) fn blinker_and_block() -> World {
+    "..........\n.O...OO...\n.O...OO...\n.O........\n.........."
+        .parse()
+        .unwrap()
+}
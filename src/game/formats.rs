@@ -0,0 +1,114 @@
+use std::{error::Error, fmt};
+
+use super::{check_dimensions, world_parts::Cell, RuleError, World, WorldError};
+
+pub use life106::Placement;
+pub use svg::SvgOptions;
+
+mod ascii;
+mod life105;
+mod life106;
+mod pbm;
+mod plaintext;
+mod rle;
+mod svg;
+
+/// An error raised while reading a world from text, pointing at the 1-based
+/// line and column where reading failed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseErrorKind {
+    UnexpectedChar(char),
+    Empty,
+    MissingHeader,
+    InvalidHeader(String),
+    InvalidCoordinate(String),
+    OutOfBounds,
+    Rule(RuleError),
+    World(WorldError),
+}
+
+impl ParseError {
+    pub(crate) fn new(line: usize, column: usize, kind: ParseErrorKind) -> Self {
+        Self { line, column, kind }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            ParseErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {:?}", c),
+            ParseErrorKind::Empty => write!(f, "input contains no cells"),
+            ParseErrorKind::MissingHeader => write!(f, "missing header line"),
+            ParseErrorKind::InvalidHeader(header) => write!(f, "invalid header {:?}", header),
+            ParseErrorKind::InvalidCoordinate(coord) => {
+                write!(f, "invalid coordinate {:?}", coord)
+            }
+            ParseErrorKind::OutOfBounds => write!(f, "cells lie outside the world"),
+            ParseErrorKind::Rule(e) => write!(f, "{}", e),
+            ParseErrorKind::World(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+// The dead cells of a world a header or the coordinates in the input ask
+// for. Those can ask for more than fits in memory, which fails as too large
+// rather than aborting.
+fn dead_cells(width: u32, height: u32) -> Result<Vec<Cell>, WorldError> {
+    check_dimensions(width, height)?;
+    let len = width as usize * height as usize;
+    let mut cells = Vec::new();
+    cells
+        .try_reserve_exact(len)
+        .map_err(|_| WorldError::TooLarge { width, height })?;
+    cells.resize(len, Cell::Dead);
+    Ok(cells)
+}
+
+// Reads rows of cell symbols into a world as wide as the longest row, padding
+// shorter rows with dead cells. Every row carries its 1-based line number so
+// errors point into the original input.
+fn parse_grid(rows: &[(usize, &str)], alive: &[char], dead: &[char]) -> Result<World, ParseError> {
+    let width = rows.iter().map(|(_, row)| row.chars().count()).max();
+    let width = match width {
+        Some(width) if width > 0 => width,
+        _ => {
+            let line = rows.first().map_or(1, |(line, _)| *line);
+            return Err(ParseError::new(line, 1, ParseErrorKind::Empty));
+        }
+    };
+
+    check_dimensions(width as u32, rows.len() as u32)
+        .map_err(|e| ParseError::new(1, 1, ParseErrorKind::World(e)))?;
+    let mut world = World::empty(width as u32, rows.len() as u32);
+    let mut cells = vec![Cell::Dead; width];
+    for (row, (line, text)) in rows.iter().enumerate() {
+        cells.fill(Cell::Dead);
+        for (col, c) in text.chars().enumerate() {
+            cells[col] = if alive.contains(&c) {
+                Cell::Alive
+            } else if dead.contains(&c) {
+                Cell::Dead
+            } else {
+                return Err(ParseError::new(
+                    *line,
+                    col + 1,
+                    ParseErrorKind::UnexpectedChar(c),
+                ));
+            };
+        }
+        world
+            .set_row(row as u32, &cells)
+            .expect("rows are as wide as the world");
+    }
+    Ok(world)
+}
@@ -0,0 +1,64 @@
+use std::str::FromStr;
+
+use super::{parse_grid, ParseError};
+use crate::game::World;
+
+impl FromStr for World {
+    type Err = ParseError;
+
+    /// Reads a world drawn as text, one line per row. Both `#`/space (as
+    /// printed by `Row`) and `O`/`.` are accepted. The width is taken from the
+    /// longest line and shorter lines are padded with dead cells.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows: Vec<(usize, &str)> = s.lines().enumerate().map(|(i, row)| (i + 1, row)).collect();
+        parse_grid(&rows, &['#', 'O'], &[' ', '.'])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{
+        formats::{ParseError, ParseErrorKind},
+        world_parts::Cell::*,
+        World,
+    };
+
+    #[test]
+    fn test_parse_ascii() {
+        let world: World = ".O.\n..O\nOOO".parse().unwrap();
+        assert_eq!(world.width(), 3);
+        assert_eq!(world.height(), 3);
+        assert_eq!(
+            world.grid,
+            vec![Dead, Alive, Dead, Dead, Dead, Alive, Alive, Alive, Alive]
+        );
+    }
+
+    #[test]
+    fn test_parse_ascii_pads_short_lines() {
+        let world: World = "#\n ##\n".parse().unwrap();
+        assert_eq!(world.width(), 3);
+        assert_eq!(world.grid, vec![Alive, Dead, Dead, Dead, Alive, Alive]);
+    }
+
+    #[test]
+    fn test_parse_ascii_round_trip() {
+        let world = World::new_with_seed(12, 7, 5);
+        let text: Vec<String> = world.rows().map(|row| row.to_string()).collect();
+
+        let parsed: World = text.join("\n").parse().unwrap();
+        assert_eq!(parsed, world);
+    }
+
+    #[test]
+    fn test_parse_ascii_errors() {
+        let result = "..\n.x".parse::<World>();
+        assert_eq!(
+            result,
+            Err(ParseError::new(2, 2, ParseErrorKind::UnexpectedChar('x')))
+        );
+
+        let result = "".parse::<World>();
+        assert_eq!(result, Err(ParseError::new(1, 1, ParseErrorKind::Empty)));
+    }
+}
@@ -0,0 +1,244 @@
+use super::{dead_cells, ParseError, ParseErrorKind};
+use crate::game::{world_parts::Cell, Rule, World, WorldError};
+
+struct Block<'a> {
+    // The line of its `#P` offset, or of its first row without one.
+    line_no: usize,
+    x: i64,
+    y: i64,
+    rows: Vec<(usize, &'a str)>,
+}
+
+impl World {
+    /// Reads a Life 1.05 file: a `#Life 1.05` header, optional `#D`, `#N` and
+    /// `#R` lines, and blocks of `.`/`*` rows each starting with a `#P x y`
+    /// offset. The world is sized to the bounding box of all blocks and uses
+    /// the rule given by `#R`, or Conway's otherwise.
+    pub fn from_life105(s: &str) -> Result<Self, ParseError> {
+        let mut lines = s
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim_end()));
+        match lines.next() {
+            Some((_, "#Life 1.05")) => {}
+            Some((line_no, header)) => {
+                return Err(ParseError::new(
+                    line_no,
+                    1,
+                    ParseErrorKind::InvalidHeader(header.to_string()),
+                ))
+            }
+            None => return Err(ParseError::new(1, 1, ParseErrorKind::MissingHeader)),
+        }
+
+        let mut rule = Rule::default();
+        let mut blocks: Vec<Block> = Vec::new();
+        for (line_no, line) in lines {
+            if let Some(offset) = line.strip_prefix("#P") {
+                let mut parts = offset.split_whitespace().map(|part| part.parse::<i64>());
+                let (x, y) = match (parts.next(), parts.next(), parts.next()) {
+                    (Some(Ok(x)), Some(Ok(y)), None) => (x, y),
+                    _ => {
+                        return Err(ParseError::new(
+                            line_no,
+                            1,
+                            ParseErrorKind::InvalidCoordinate(line.to_string()),
+                        ))
+                    }
+                };
+                blocks.push(Block {
+                    line_no,
+                    x,
+                    y,
+                    rows: Vec::new(),
+                });
+            } else if let Some(text) = line.strip_prefix("#R") {
+                rule = text
+                    .parse()
+                    .map_err(|e| ParseError::new(line_no, 1, ParseErrorKind::Rule(e)))?;
+            } else if line.starts_with('#') {
+                // `#D` descriptions and `#N` (the default rule) carry nothing
+                // that needs to be kept.
+            } else {
+                if blocks.is_empty() {
+                    blocks.push(Block {
+                        line_no,
+                        x: 0,
+                        y: 0,
+                        rows: Vec::new(),
+                    });
+                }
+                blocks.last_mut().unwrap().rows.push((line_no, line));
+            }
+        }
+
+        // The bounding box of every block so far, as `(left, top, right,
+        // bottom)`, which stays small enough to subtract in `u32`s.
+        let mut extent: Option<(i64, i64, i64, i64)> = None;
+        for block in blocks.iter().filter(|block| !block.rows.is_empty()) {
+            let out_of_bounds = || ParseError::new(block.line_no, 1, ParseErrorKind::OutOfBounds);
+            let width = block
+                .rows
+                .iter()
+                .map(|(_, row)| row.len())
+                .max()
+                .unwrap_or(0);
+            let right = block
+                .x
+                .checked_add(width as i64)
+                .ok_or_else(out_of_bounds)?;
+            let bottom = block
+                .y
+                .checked_add(block.rows.len() as i64)
+                .ok_or_else(out_of_bounds)?;
+            let (left, top, right, bottom) = match extent {
+                Some(a) => (
+                    a.0.min(block.x),
+                    a.1.min(block.y),
+                    a.2.max(right),
+                    a.3.max(bottom),
+                ),
+                None => (block.x, block.y, right, bottom),
+            };
+
+            let too_large = || {
+                let kind = ParseErrorKind::World(WorldError::TooLarge {
+                    width: u32::MAX,
+                    height: u32::MAX,
+                });
+                ParseError::new(block.line_no, 1, kind)
+            };
+            let span = |from: i64, to: i64| {
+                to.checked_sub(from)
+                    .and_then(|span| u32::try_from(span).ok())
+                    .ok_or_else(too_large)
+            };
+            span(left, right)?;
+            span(top, bottom)?;
+            extent = Some((left, top, right, bottom));
+        }
+        let (left, top, right, bottom) =
+            extent.ok_or_else(|| ParseError::new(1, 1, ParseErrorKind::Empty))?;
+
+        let (width, height) = ((right - left) as u32, (bottom - top) as u32);
+        let mut cells = dead_cells(width, height)
+            .map_err(|e| ParseError::new(1, 1, ParseErrorKind::World(e)))?;
+        let (width, height) = (width as usize, height as usize);
+        for block in &blocks {
+            for (i, (line_no, row)) in block.rows.iter().enumerate() {
+                let y = (block.y - top) as usize + i;
+                for (j, c) in row.chars().enumerate() {
+                    let x = (block.x - left) as usize + j;
+                    match c {
+                        '*' => cells[y * width + x] = Cell::Alive,
+                        '.' => {}
+                        c => {
+                            return Err(ParseError::new(
+                                *line_no,
+                                j + 1,
+                                ParseErrorKind::UnexpectedChar(c),
+                            ))
+                        }
+                    }
+                }
+            }
+        }
+
+        World::from_cells(width as u32, height as u32, cells)
+            .map(|world| world.with_rule(rule))
+            .map_err(|e| ParseError::new(1, 1, ParseErrorKind::World(e)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{
+        formats::{ParseError, ParseErrorKind},
+        Rule, RuleError, World, WorldError,
+    };
+    use crate::patterns;
+
+    #[test]
+    fn test_from_life105() {
+        let text = "#Life 1.05\n#D A glider\n#N\n#P -1 -1\n.*.\n..*\n***\n";
+        let world = World::from_life105(text).unwrap();
+        let expected = patterns::glider();
+        assert_eq!(world, expected);
+    }
+
+    #[test]
+    fn test_from_life105_multiple_blocks() {
+        // A block at the origin and a blinker further down and to the left;
+        // the world spans both.
+        let text = "#Life 1.05\n#P 0 0\n**\n**\n#P -3 3\n***\n";
+        let world = World::from_life105(text).unwrap();
+        let expected: World = "...OO\n...OO\n.....\nOOO..".parse().unwrap();
+        assert_eq!(world, expected);
+    }
+
+    #[test]
+    fn test_from_life105_rule() {
+        let world = World::from_life105("#Life 1.05\n#R 125/36\n#P 0 0\n*\n").unwrap();
+        assert_eq!(world.rule(), Rule::new(&[3, 6], &[1, 2, 5]));
+
+        let world = World::from_life105("#Life 1.05\n#N\n#P 0 0\n*\n").unwrap();
+        assert_eq!(world.rule(), Rule::CONWAY);
+    }
+
+    #[test]
+    fn test_from_life105_errors() {
+        let result = World::from_life105("#Life 1.05\n#R 129/36\n#P 0 0\n*\n");
+        assert_eq!(
+            result,
+            Err(ParseError::new(
+                2,
+                1,
+                ParseErrorKind::Rule(RuleError::InvalidCount('9'))
+            ))
+        );
+
+        let result = World::from_life105("#Life 1.05\n#R 23/3\n#P 0 0\n*o\n");
+        assert_eq!(
+            result,
+            Err(ParseError::new(4, 2, ParseErrorKind::UnexpectedChar('o')))
+        );
+
+        let result = World::from_life105("#Life 1.05\n#P 0\n*\n");
+        assert!(matches!(
+            result,
+            Err(ParseError {
+                kind: ParseErrorKind::InvalidCoordinate(_),
+                ..
+            })
+        ));
+
+        // Offsets so far out that the blocks don't fit any world.
+        let result = World::from_life105("#Life 1.05\n#P 9223372036854775807 0\n**\n");
+        assert_eq!(
+            result,
+            Err(ParseError::new(2, 1, ParseErrorKind::OutOfBounds))
+        );
+        let text = "#Life 1.05\n#P -9223372036854775808 0\n*\n#P 9223372036854775806 0\n*\n";
+        assert!(matches!(
+            World::from_life105(text),
+            Err(ParseError {
+                line: 4,
+                kind: ParseErrorKind::World(WorldError::TooLarge { .. }),
+                ..
+            })
+        ));
+
+        // Blocks far enough apart that their world doesn't fit in memory.
+        let text = "#Life 1.05\n#P 0 0\n*\n#P 1073741823 1073741823\n*\n";
+        assert!(matches!(
+            World::from_life105(text),
+            Err(ParseError {
+                kind: ParseErrorKind::World(WorldError::TooLarge { .. }),
+                ..
+            })
+        ));
+
+        let result = World::from_life105("#Life 1.05\n#D Nothing here\n");
+        assert_eq!(result, Err(ParseError::new(1, 1, ParseErrorKind::Empty)));
+    }
+}
@@ -0,0 +1,186 @@
+use super::{ParseError, ParseErrorKind};
+use crate::game::{check_dimensions, world_parts::Cell, World};
+
+const HEADER: &str = "#Life 1.06";
+
+/// Where the cells of a Life 1.06 file end up in the world.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Placement {
+    /// Coordinates are used as `(x, y) = (col, row)` and have to lie inside
+    /// the world.
+    Absolute,
+    /// The pattern is moved so its bounding box sits in the middle of the
+    /// world, which also takes care of negative coordinates.
+    Centered,
+}
+
+impl World {
+    /// Reads a Life 1.06 file, a `#Life 1.06` header followed by one `x y`
+    /// pair per live cell, into a world of the given size.
+    pub fn from_life106(
+        s: &str,
+        width: u32,
+        height: u32,
+        placement: Placement,
+    ) -> Result<Self, ParseError> {
+        check_dimensions(width, height)
+            .map_err(|e| ParseError::new(1, 1, ParseErrorKind::World(e)))?;
+
+        let mut lines = s.lines().enumerate().map(|(i, line)| (i + 1, line.trim()));
+        match lines.next() {
+            Some((_, HEADER)) => {}
+            Some((line_no, header)) => {
+                return Err(ParseError::new(
+                    line_no,
+                    1,
+                    ParseErrorKind::InvalidHeader(header.to_string()),
+                ))
+            }
+            None => return Err(ParseError::new(1, 1, ParseErrorKind::MissingHeader)),
+        }
+
+        let mut coords = Vec::new();
+        for (line_no, line) in lines {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || {
+                ParseError::new(
+                    line_no,
+                    1,
+                    ParseErrorKind::InvalidCoordinate(line.to_string()),
+                )
+            };
+            let mut parts = line.split_whitespace().map(|part| part.parse::<i64>());
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(Ok(x)), Some(Ok(y)), None) => coords.push((line_no, x, y)),
+                _ => return Err(invalid()),
+            }
+        }
+
+        // Both placements come down to a translation applied to every cell.
+        // It's worked out in `i128` so no coordinates can overflow, however
+        // far apart they are.
+        let (dx, dy): (i128, i128) = match placement {
+            Placement::Absolute => (0, 0),
+            Placement::Centered if coords.is_empty() => (0, 0),
+            Placement::Centered => {
+                let min_x = coords.iter().map(|&(_, x, _)| x).min().unwrap() as i128;
+                let max_x = coords.iter().map(|&(_, x, _)| x).max().unwrap() as i128;
+                let min_y = coords.iter().map(|&(_, _, y)| y).min().unwrap() as i128;
+                let max_y = coords.iter().map(|&(_, _, y)| y).max().unwrap() as i128;
+                (
+                    (width as i128 - (max_x - min_x + 1)) / 2 - min_x,
+                    (height as i128 - (max_y - min_y + 1)) / 2 - min_y,
+                )
+            }
+        };
+
+        let mut cells = vec![Cell::Dead; width as usize * height as usize];
+        for (line_no, x, y) in coords {
+            let (col, row) = (x as i128 + dx, y as i128 + dy);
+            if !(0..width as i128).contains(&col) || !(0..height as i128).contains(&row) {
+                return Err(ParseError::new(line_no, 1, ParseErrorKind::OutOfBounds));
+            }
+            cells[row as usize * width as usize + col as usize] = Cell::Alive;
+        }
+
+        World::from_cells(width, height, cells)
+            .map_err(|e| ParseError::new(1, 1, ParseErrorKind::World(e)))
+    }
+
+    /// Writes the world in Life 1.06 format, one `x y` line per live cell.
+    pub fn to_life106(&self) -> String {
+        let mut out = format!("{}\n", HEADER);
+        for (row, col) in self.live_cells() {
+            out.push_str(&format!("{} {}\n", col, row));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Placement;
+    use crate::game::{
+        formats::{ParseError, ParseErrorKind},
+        world_parts::Cell::*,
+        World,
+    };
+
+    const GLIDER: &str = "#Life 1.06\n0 -1\n1 0\n-1 1\n0 1\n1 1\n";
+
+    #[test]
+    fn test_from_life106_absolute() {
+        let world =
+            World::from_life106("#Life 1.06\n1 0\n2 1\n", 3, 2, Placement::Absolute).unwrap();
+        assert_eq!(world.grid, vec![Dead, Alive, Dead, Dead, Dead, Alive]);
+
+        let result = World::from_life106(GLIDER, 5, 5, Placement::Absolute);
+        assert_eq!(
+            result,
+            Err(ParseError::new(2, 1, ParseErrorKind::OutOfBounds))
+        );
+    }
+
+    #[test]
+    fn test_from_life106_centered() {
+        let world = World::from_life106(GLIDER, 5, 5, Placement::Centered).unwrap();
+        let expected: World = ".....\n..O..\n...O.\n.OOO.\n.....".parse().unwrap();
+        assert_eq!(world, expected);
+
+        let result = World::from_life106(GLIDER, 2, 5, Placement::Centered);
+        assert_eq!(
+            result,
+            Err(ParseError::new(3, 1, ParseErrorKind::OutOfBounds))
+        );
+
+        // Coordinates as far apart as they can be don't fit, nor overflow.
+        let far = "#Life 1.06\n-9223372036854775808 0\n9223372036854775807 0\n";
+        for placement in [Placement::Centered, Placement::Absolute] {
+            let result = World::from_life106(far, 5, 5, placement);
+            assert_eq!(
+                result,
+                Err(ParseError::new(2, 1, ParseErrorKind::OutOfBounds))
+            );
+        }
+        let far = "#Life 1.06\n0 9223372036854775807\n0 9223372036854775806\n";
+        let world = World::from_life106(far, 5, 5, Placement::Centered).unwrap();
+        assert_eq!(world.population(), 2);
+    }
+
+    #[test]
+    fn test_from_life106_errors() {
+        let result = World::from_life106("#Life 1.05\n", 3, 3, Placement::Absolute);
+        assert_eq!(
+            result,
+            Err(ParseError::new(
+                1,
+                1,
+                ParseErrorKind::InvalidHeader("#Life 1.05".to_string())
+            ))
+        );
+
+        let result = World::from_life106("#Life 1.06\n1 2 3\n", 3, 3, Placement::Absolute);
+        assert_eq!(
+            result,
+            Err(ParseError::new(
+                2,
+                1,
+                ParseErrorKind::InvalidCoordinate("1 2 3".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_life106_round_trip() {
+        let world = World::new_with_seed(13, 8, 9);
+        let text = world.to_life106();
+        assert!(text.starts_with("#Life 1.06\n"));
+        assert_eq!(
+            World::from_life106(&text, 13, 8, Placement::Absolute).unwrap(),
+            world
+        );
+    }
+}
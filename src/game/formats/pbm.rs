@@ -0,0 +1,75 @@
+use std::io::{self, Write};
+
+use crate::game::World;
+
+impl World {
+    /// Encodes the world as a binary (P4) PBM image, see `write_pbm`.
+    pub fn to_pbm(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        // Writing into a Vec can't fail.
+        self.write_pbm(&mut out).unwrap();
+        out
+    }
+
+    /// Writes the world as a binary (P4) PBM image with one pixel per cell,
+    /// live cells in black. Each row is packed most significant bit first and
+    /// padded to a whole number of bytes.
+    pub fn write_pbm<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        write!(w, "P4\n{} {}\n", self.width, self.height)?;
+
+        let mut packed = vec![0u8; self.width.div_ceil(8) as usize];
+        for row in 0..self.height {
+            packed.fill(0);
+            for (col, cell) in self.get_row(row).iter().enumerate() {
+                if cell.is_alive() {
+                    packed[col / 8] |= 0x80 >> (col % 8);
+                }
+            }
+            w.write_all(&packed)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::World;
+
+    fn pbm_data(world: &World) -> Vec<u8> {
+        let pbm = world.to_pbm();
+        let header = format!("P4\n{} {}\n", world.width(), world.height());
+        assert!(pbm.starts_with(header.as_bytes()));
+        pbm[header.len()..].to_vec()
+    }
+
+    #[test]
+    fn test_to_pbm_width_7() {
+        let world: World = "O.....O\n.O.O.O.".parse().unwrap();
+        assert_eq!(pbm_data(&world), vec![0b1000_0010, 0b0101_0100]);
+    }
+
+    #[test]
+    fn test_to_pbm_width_8() {
+        let world: World = "O......O\nOOOOOOOO".parse().unwrap();
+        assert_eq!(pbm_data(&world), vec![0b1000_0001, 0b1111_1111]);
+    }
+
+    #[test]
+    fn test_to_pbm_width_9() {
+        let world: World = "O.......O\n........O".parse().unwrap();
+        assert_eq!(
+            pbm_data(&world),
+            vec![0b1000_0000, 0b1000_0000, 0b0000_0000, 0b1000_0000]
+        );
+    }
+
+    #[test]
+    fn test_write_pbm() {
+        let world = World::new_with_seed(21, 5, 1);
+        let mut out = Vec::new();
+        world.write_pbm(&mut out).unwrap();
+        assert_eq!(out, world.to_pbm());
+        assert_eq!(out.len(), "P4\n21 5\n".len() + 3 * 5);
+    }
+}
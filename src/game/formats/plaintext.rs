@@ -0,0 +1,102 @@
+use super::{parse_grid, ParseError};
+use crate::game::World;
+
+impl World {
+    /// Reads a pattern in the plaintext `.cells` format: `!` comment lines
+    /// followed by rows of `.` (dead) and `O` (alive). The world is as wide as
+    /// the longest row and shorter rows are padded with dead cells.
+    pub fn from_plaintext(s: &str) -> Result<Self, ParseError> {
+        let rows: Vec<(usize, &str)> = s
+            .lines()
+            .enumerate()
+            .map(|(i, row)| (i + 1, row))
+            .filter(|(_, row)| !row.starts_with('!'))
+            .collect();
+
+        parse_grid(&rows, &['O'], &['.'])
+    }
+
+    /// Writes the world in the plaintext `.cells` format, one row per line.
+    pub fn to_plaintext(&self) -> String {
+        let mut out = String::with_capacity((self.width as usize + 1) * self.height as usize);
+        for row in 0..self.height {
+            for cell in self.get_row(row) {
+                out.push(if cell.is_alive() { 'O' } else { '.' });
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{
+        formats::{ParseError, ParseErrorKind},
+        world_parts::Cell::*,
+        World,
+    };
+
+    const BLINKER: &str = "!Name: Blinker\n!\nOOO\n";
+
+    const PULSAR: &str = "!Name: Pulsar
+!Author: John Conway
+!Despite its size, this is the fourth most common oscillator.
+..OOO...OOO
+
+O....O.O....O
+O....O.O....O
+O....O.O....O
+..OOO...OOO
+
+..OOO...OOO
+O....O.O....O
+O....O.O....O
+O....O.O....O
+
+..OOO...OOO
+";
+
+    #[test]
+    fn test_from_plaintext() {
+        let world = World::from_plaintext(BLINKER).unwrap();
+        assert_eq!((world.width(), world.height()), (3, 1));
+        assert_eq!(world.grid, vec![Alive, Alive, Alive]);
+
+        let world = World::from_plaintext(PULSAR).unwrap();
+        assert_eq!((world.width(), world.height()), (13, 13));
+        assert_eq!(world.grid.iter().filter(|cell| cell.is_alive()).count(), 48);
+        assert_eq!(world.get_row(1), &[Dead; 13]);
+    }
+
+    #[test]
+    fn test_from_plaintext_crlf() {
+        let world = World::from_plaintext("!Name: Beehive\r\n.OO.\r\nO..O\r\n.OO.\r\n").unwrap();
+        assert_eq!((world.width(), world.height()), (4, 3));
+        assert_eq!(world.get_row(1), &[Alive, Dead, Dead, Alive]);
+    }
+
+    #[test]
+    fn test_from_plaintext_errors() {
+        let result = World::from_plaintext("!Name: Nothing\n!\n");
+        assert_eq!(result, Err(ParseError::new(1, 1, ParseErrorKind::Empty)));
+
+        let result = World::from_plaintext("!Name: Blinker\n.O.\n.#.\n");
+        assert_eq!(
+            result,
+            Err(ParseError::new(3, 2, ParseErrorKind::UnexpectedChar('#')))
+        );
+    }
+
+    #[test]
+    fn test_plaintext_round_trip() {
+        let pulsar = World::from_plaintext(PULSAR).unwrap();
+        assert_eq!(
+            World::from_plaintext(&pulsar.to_plaintext()).unwrap(),
+            pulsar
+        );
+
+        let world = World::new_with_seed(17, 9, 4);
+        assert_eq!(World::from_plaintext(&world.to_plaintext()).unwrap(), world);
+    }
+}
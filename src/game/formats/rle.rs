@@ -0,0 +1,403 @@
+use super::{dead_cells, ParseError, ParseErrorKind};
+use crate::game::{world_parts::Cell, Pattern, Rule, World};
+
+// Upper bound for data lines written by `to_rle`, as the format suggests.
+const MAX_LINE_LEN: usize = 70;
+
+impl Pattern {
+    /// Reads a pattern in Run Length Encoded format, the format used by most
+    /// pattern collections. The pattern is sized by the `x`/`y` header, and
+    /// `#N`, `#O` and `#C` lines fill in its name, author and comments.
+    pub fn from_rle(s: &str) -> Result<Self, ParseError> {
+        let (mut name, mut author, mut comments) = (None, None, Vec::new());
+        for line in s
+            .lines()
+            .take_while(|line| line.starts_with('#') || line.trim().is_empty())
+        {
+            let tag = line.get(..2).unwrap_or(line);
+            let text = line.get(2..).unwrap_or("").trim().to_string();
+            match tag {
+                "#N" => name = Some(text),
+                "#O" => author = Some(text),
+                "#C" | "#c" => comments.push(text),
+                _ => {}
+            }
+        }
+
+        let mut lines = s
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'));
+
+        let (header_line, header) = lines
+            .next()
+            .ok_or_else(|| ParseError::new(1, 1, ParseErrorKind::MissingHeader))?;
+        let (width, height, rule) = parse_header(header_line, header)?;
+        let mut cells = dead_cells(width, height)
+            .map_err(|e| ParseError::new(header_line, 1, ParseErrorKind::World(e)))?;
+        let (mut row, mut col) = (0u32, 0u32);
+        let mut count: Option<u32> = None;
+
+        'data: for (line_no, line) in lines {
+            for (i, c) in line.chars().enumerate() {
+                let err = |kind| ParseError::new(line_no, i + 1, kind);
+
+                match c {
+                    '0'..='9' => {
+                        let digit = c.to_digit(10).unwrap();
+                        let n = count
+                            .unwrap_or(0)
+                            .checked_mul(10)
+                            .and_then(|n| n.checked_add(digit))
+                            .ok_or_else(|| err(ParseErrorKind::OutOfBounds))?;
+                        count = Some(n);
+                    }
+                    'b' | 'o' => {
+                        let run = count.take().unwrap_or(1);
+                        if row >= height || col as u64 + run as u64 > width as u64 {
+                            return Err(err(ParseErrorKind::OutOfBounds));
+                        }
+
+                        if c == 'o' {
+                            let start = row as usize * width as usize + col as usize;
+                            cells[start..start + run as usize].fill(Cell::Alive);
+                        }
+                        col += run;
+                    }
+                    '$' => {
+                        row = row
+                            .checked_add(count.take().unwrap_or(1))
+                            .ok_or_else(|| err(ParseErrorKind::OutOfBounds))?;
+                        col = 0;
+                    }
+                    '!' => break 'data,
+                    c if c.is_whitespace() => {}
+                    c => return Err(err(ParseErrorKind::UnexpectedChar(c))),
+                }
+            }
+        }
+
+        let mut pattern = Pattern::new(width, height, cells)
+            .map_err(|e| ParseError::new(header_line, 1, ParseErrorKind::World(e)))?;
+        pattern.name = name;
+        pattern.author = author;
+        pattern.comments = comments;
+        pattern.rule = rule;
+        Ok(pattern)
+    }
+}
+
+impl World {
+    /// Reads a pattern in Run Length Encoded format into a world of the size
+    /// given by its header, see `Pattern::from_rle`.
+    pub fn from_rle(s: &str) -> Result<Self, ParseError> {
+        Pattern::from_rle(s).map(|pattern| pattern.to_world())
+    }
+
+    /// Writes the world in Run Length Encoded format. Dead cells at the end of
+    /// a row are left out and data lines are wrapped at 70 characters.
+    pub fn to_rle(&self) -> String {
+        let mut out = format!(
+            "x = {}, y = {}, rule = {}\n",
+            self.width, self.height, self.rule
+        );
+        let mut line = String::new();
+        // Row ends are only written once the next row with live cells shows
+        // up, so runs of empty rows collapse into a single `$` token.
+        let mut last_row = 0;
+
+        for row in 0..self.height {
+            let cells = self.get_row(row);
+            let Some(end) = cells.iter().rposition(|cell| cell.is_alive()) else {
+                continue;
+            };
+
+            if row > last_row {
+                push_token(&mut out, &mut line, row - last_row, '$');
+            }
+            last_row = row;
+
+            // Dying cells are written as dead, so they join the runs of dead
+            // cells around them.
+            let mut cells = cells[..=end].iter().map(|cell| cell.is_alive()).peekable();
+            while let Some(alive) = cells.next() {
+                let mut run = 1;
+                while cells.next_if_eq(&alive).is_some() {
+                    run += 1;
+                }
+                push_token(&mut out, &mut line, run, if alive { 'o' } else { 'b' });
+            }
+        }
+
+        push_wrapped(&mut out, &mut line, "!");
+        out.push_str(&line);
+        out.push('\n');
+        out
+    }
+}
+
+fn push_token(out: &mut String, line: &mut String, run: u32, tag: char) {
+    let token = if run == 1 {
+        tag.to_string()
+    } else {
+        format!("{}{}", run, tag)
+    };
+    push_wrapped(out, line, &token);
+}
+
+// Adds `token` to the current line, starting a new one first if it would
+// grow too long.
+fn push_wrapped(out: &mut String, line: &mut String, token: &str) {
+    if line.len() + token.len() > MAX_LINE_LEN {
+        out.push_str(line);
+        out.push('\n');
+        line.clear();
+    }
+    line.push_str(token);
+}
+
+fn parse_header(line_no: usize, line: &str) -> Result<(u32, u32, Rule), ParseError> {
+    let invalid = || {
+        ParseError::new(
+            line_no,
+            1,
+            ParseErrorKind::InvalidHeader(line.trim().to_string()),
+        )
+    };
+    let (mut width, mut height, mut rule) = (None, None, Rule::default());
+
+    // Larger-than-Life rules contain commas themselves, so the rule takes up
+    // the rest of the line.
+    let (sizes, rule_part) = match line.find("rule") {
+        Some(i) => (line[..i].trim_end().trim_end_matches(','), Some(&line[i..])),
+        None => (line, None),
+    };
+
+    for part in sizes.split(',').map(str::trim_start).chain(rule_part) {
+        let column = part.as_ptr() as usize - line.as_ptr() as usize + 1;
+        let (key, value) = part.split_once('=').ok_or_else(invalid)?;
+        let value = value.trim();
+
+        match key.trim() {
+            "x" => width = Some(value.parse::<u32>().map_err(|_| invalid())?),
+            "y" => height = Some(value.parse::<u32>().map_err(|_| invalid())?),
+            "rule" => {
+                rule = value
+                    .parse()
+                    .map_err(|e| ParseError::new(line_no, column, ParseErrorKind::Rule(e)))?;
+            }
+            _ => return Err(invalid()),
+        }
+    }
+
+    match (width, height) {
+        (Some(width), Some(height)) => Ok((width, height, rule)),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{
+        formats::{ParseError, ParseErrorKind},
+        world_parts::{Cell, Cell::*},
+        Pattern, Rule, RuleError, World, WorldError,
+    };
+
+    const GLIDER_GUN: &str = "#N Gosper glider gun
+#C This is the first known gun and the first known finite pattern with
+#C unbounded growth.
+x = 36, y = 9, rule = B3/S23
+24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4b
+obo$10bo5bo7bo$11bo3bo$12b2o!
+";
+
+    #[test]
+    fn test_from_rle() {
+        let world = World::from_rle("x = 3, y = 3\nbo$2bo$3o!").unwrap();
+        assert_eq!(
+            world.grid,
+            vec![Dead, Alive, Dead, Dead, Dead, Alive, Alive, Alive, Alive]
+        );
+
+        // Empty rows can be skipped with a count before `$`.
+        let world = World::from_rle("x = 2, y = 3, rule = b3/s23\no2$bo!").unwrap();
+        assert_eq!(world.grid, vec![Alive, Dead, Dead, Dead, Dead, Alive]);
+    }
+
+    #[test]
+    fn test_pattern_from_rle() {
+        let gun = Pattern::from_rle(GLIDER_GUN).unwrap();
+        assert_eq!(gun.name.as_deref(), Some("Gosper glider gun"));
+        assert_eq!(gun.author, None);
+        assert_eq!(gun.comments.len(), 2);
+        assert!(gun.comments[1].starts_with("unbounded growth"));
+
+        let pattern = Pattern::from_rle("#O John Conway\nx = 3, y = 1\n3o!").unwrap();
+        assert_eq!(pattern.author.as_deref(), Some("John Conway"));
+        assert_eq!(pattern.to_string(), "###");
+    }
+
+    #[test]
+    fn test_from_rle_glider_gun() {
+        let gun = World::from_rle(GLIDER_GUN).unwrap();
+        assert_eq!((gun.width(), gun.height()), (36, 9));
+        assert_eq!(gun.grid.iter().filter(|cell| cell.is_alive()).count(), 36);
+
+        // Leave plenty of room below and to the right for the glider.
+        let mut world = World::empty(60, 40);
+        for row in 0..gun.height() {
+            for col in 0..gun.width() {
+                let idx = world.get_index(row + 1, col + 1);
+                world.grid[idx] = gun[gun.get_index(row, col)];
+            }
+        }
+
+        for _ in 0..30 {
+            world.evolve();
+        }
+
+        // The gun returns to its original phase, and the 5 extra cells are
+        // the glider it emitted, heading away below the gun.
+        let population = world.grid.iter().filter(|cell| cell.is_alive()).count();
+        assert_eq!(population, 41);
+        let below_gun = (10..world.height())
+            .flat_map(|row| world.get_row(row))
+            .filter(|cell| cell.is_alive())
+            .count();
+        assert_eq!(below_gun, 5);
+    }
+
+    #[test]
+    fn test_to_rle() {
+        let world = World::from_cells(
+            4,
+            4,
+            vec![
+                Dead, Alive, Dead, Dead, Dead, Dead, Dead, Dead, Dead, Dead, Dead, Dead, Alive,
+                Alive, Alive, Dead,
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(world.to_rle(), "x = 4, y = 4, rule = B3/S23\nbo3$3o!\n");
+        assert_eq!(
+            World::empty(3, 2).to_rle(),
+            "x = 3, y = 2, rule = B3/S23\n!\n"
+        );
+
+        let world = World::from_cells(5, 1, vec![Dead, Dying(1), Dead, Dying(2), Alive])
+            .unwrap()
+            .with_rule(Rule::BRIANS_BRAIN);
+        assert!(world.to_rle().ends_with("\n4bo!\n"));
+    }
+
+    #[test]
+    fn test_rle_rule() {
+        let world = World::from_rle("x = 3, y = 1, rule = B36/S23\n3o!").unwrap();
+        assert_eq!(world.rule(), Rule::new(&[3, 6], &[2, 3]));
+        assert_eq!(world.to_rle(), "x = 3, y = 1, rule = B36/S23\n3o!\n");
+
+        let world = World::from_rle("x = 3, y = 1\n3o!").unwrap();
+        assert_eq!(world.rule(), Rule::CONWAY);
+
+        let rle = "x = 3, y = 1, rule = R5,C0,M0,S33..57,B34..45,NM\n3o!\n";
+        let world = World::from_rle(rle).unwrap();
+        assert_eq!(world.rule(), Rule::BUGS);
+        assert_eq!(world.to_rle(), rle);
+    }
+
+    #[test]
+    fn test_to_rle_wraps_lines() {
+        let world = World::new_with_seed(200, 50, 11);
+        let rle = world.to_rle();
+        assert!(rle.lines().all(|line| line.len() <= 70));
+        assert!(rle.lines().count() > 2);
+
+        // A full last line leaves the `!` for a line of its own.
+        let cells = (0..70).map(|col| Cell::from(col % 2 == 1)).collect();
+        let rle = World::from_cells(70, 1, cells).unwrap().to_rle();
+        assert!(rle.lines().all(|line| line.len() <= 70));
+        assert!(rle.ends_with("bo\n!\n"));
+    }
+
+    #[test]
+    fn test_rle_round_trip() {
+        let world = World::new_with_seed(57, 31, 2);
+        assert_eq!(World::from_rle(&world.to_rle()).unwrap(), world);
+
+        let gun = World::from_rle(GLIDER_GUN).unwrap();
+        assert_eq!(World::from_rle(&gun.to_rle()).unwrap(), gun);
+    }
+
+    #[test]
+    fn test_from_rle_errors() {
+        let result = World::from_rle("#C only comments\n");
+        assert_eq!(
+            result,
+            Err(ParseError::new(1, 1, ParseErrorKind::MissingHeader))
+        );
+
+        let result = World::from_rle("x = 3, y = 3, rule = B36/S2x\n3o!");
+        assert_eq!(
+            result,
+            Err(ParseError::new(
+                1,
+                15,
+                ParseErrorKind::Rule(RuleError::InvalidCount('x'))
+            ))
+        );
+
+        let result = World::from_rle("x = 3, y = 2\n3o$\n2bq!");
+        assert_eq!(
+            result,
+            Err(ParseError::new(3, 3, ParseErrorKind::UnexpectedChar('q')))
+        );
+
+        let result = World::from_rle("x = 3, y = 2\n4o!");
+        assert_eq!(
+            result,
+            Err(ParseError::new(2, 2, ParseErrorKind::OutOfBounds))
+        );
+
+        let result = World::from_rle("x = 1, y = 1\n4294967295$$!");
+        assert_eq!(
+            result,
+            Err(ParseError::new(2, 12, ParseErrorKind::OutOfBounds))
+        );
+
+        let result = World::from_rle("#N huge\nx = 4294967295, y = 4294967295\n!");
+        assert!(matches!(
+            result,
+            Err(ParseError {
+                line: 2,
+                kind: ParseErrorKind::World(WorldError::TooLarge { .. }),
+                ..
+            })
+        ));
+
+        // Fits in a `usize`, but not in any memory there is.
+        let result = World::from_rle("x = 1073741824, y = 1073741824\n!");
+        assert_eq!(
+            result,
+            Err(ParseError::new(
+                1,
+                1,
+                ParseErrorKind::World(WorldError::TooLarge {
+                    width: 1 << 30,
+                    height: 1 << 30
+                })
+            ))
+        );
+
+        let result = World::from_rle("x = three, y = 2\n3o!");
+        assert!(matches!(
+            result,
+            Err(ParseError {
+                kind: ParseErrorKind::InvalidHeader(_),
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,195 @@
+use std::fmt::Write;
+
+use crate::game::World;
+
+/// Settings for `World::to_svg_with`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SvgOptions {
+    pub alive: String,
+    pub background: String,
+    /// Color of the lines drawn between cells, or `None` for no grid.
+    pub grid: Option<String>,
+    /// Only draw the bounding box of the live cells instead of the whole world.
+    pub crop: bool,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            alive: "black".to_string(),
+            background: "white".to_string(),
+            grid: None,
+            crop: false,
+        }
+    }
+}
+
+impl World {
+    /// Draws the world as an SVG document with one `cell_px` sized square per
+    /// live cell, using the default `SvgOptions`.
+    pub fn to_svg(&self, cell_px: u32) -> String {
+        self.to_svg_with(cell_px, &SvgOptions::default())
+    }
+
+    /// Same as `to_svg`, with custom colors, grid lines, or cropping.
+    pub fn to_svg_with(&self, cell_px: u32, options: &SvgOptions) -> String {
+        // (top, left, bottom, right) of the drawn area, bottom and right
+        // exclusive. A crop of an empty world keeps the whole world.
+        let mut area = (0, 0, self.height, self.width);
+        if options.crop {
+            if let Some(rect) = self.bounding_box() {
+                area = (rect.top, rect.left, rect.bottom + 1, rect.right + 1);
+            }
+        }
+
+        let (top, left, bottom, right) = area;
+        // Coordinates are worked out in `u64`, which a `u32` number of cells
+        // of `u32` pixels each can't overflow.
+        let px = cell_px as u64;
+        let width = (right - left) as u64 * px;
+        let height = (bottom - top) as u64 * px;
+
+        let mut svg = String::new();
+        // Writing to a String never fails, so the results are ignored.
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = width,
+            h = height
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect width="{}" height="{}" fill="{}"/>"#,
+            width,
+            height,
+            escape(&options.background)
+        );
+
+        let _ = writeln!(svg, r#"<g fill="{}">"#, escape(&options.alive));
+        for row in top..bottom {
+            let cells = &self.get_row(row)[left as usize..right as usize];
+            for (col, cell) in cells.iter().enumerate() {
+                if cell.is_alive() {
+                    let _ = writeln!(
+                        svg,
+                        r#"<rect x="{}" y="{}" width="{s}" height="{s}"/>"#,
+                        col as u64 * px,
+                        (row - top) as u64 * px,
+                        s = cell_px
+                    );
+                }
+            }
+        }
+        svg.push_str("</g>\n");
+
+        if let Some(color) = &options.grid {
+            let _ = writeln!(svg, r#"<g stroke="{}" stroke-width="1">"#, escape(color));
+            for col in 0..=(right - left) {
+                let x = col as u64 * px;
+                let _ = writeln!(svg, r#"<line x1="{x}" y1="0" x2="{x}" y2="{}"/>"#, height);
+            }
+            for row in 0..=(bottom - top) {
+                let y = row as u64 * px;
+                let _ = writeln!(svg, r#"<line x1="0" y1="{y}" x2="{}" y2="{y}"/>"#, width);
+            }
+            svg.push_str("</g>\n");
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+// Keeps user supplied colors from breaking out of their attribute.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::SvgOptions;
+    use crate::game::World;
+
+    // Checks that every tag is closed in the right order, which is all the
+    // structure the exporter produces.
+    fn assert_well_formed(svg: &str) {
+        let mut open = Vec::new();
+        let mut rest = svg;
+        while let Some(start) = rest.find('<') {
+            let end = rest[start..].find('>').expect("unterminated tag") + start;
+            let tag = &rest[start + 1..end];
+            let name = tag
+                .trim_start_matches('/')
+                .split_whitespace()
+                .next()
+                .unwrap();
+            assert_eq!(
+                tag.matches('"').count() % 2,
+                0,
+                "unbalanced quotes: {}",
+                tag
+            );
+
+            if tag.starts_with('/') {
+                assert_eq!(open.pop(), Some(name), "unexpected closing tag: {}", tag);
+            } else if !tag.ends_with('/') {
+                open.push(name);
+            }
+            rest = &rest[end + 1..];
+        }
+        assert!(open.is_empty(), "unclosed tags: {:?}", open);
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let world: World = "O..\n.OO".parse().unwrap();
+        let svg = world.to_svg(10);
+        assert_well_formed(&svg);
+        assert!(
+            svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="20""#)
+        );
+        assert_eq!(svg.matches("<rect").count(), 4);
+        assert!(svg.contains(r#"<rect x="0" y="0" width="10" height="10"/>"#));
+        assert!(svg.contains(r#"<rect x="20" y="10" width="10" height="10"/>"#));
+        assert!(!svg.contains("<line"));
+
+        // Sizes past `u32::MAX` pixels.
+        let svg = world.to_svg(u32::MAX);
+        assert!(svg.contains(r#"width="12884901885" height="8589934590""#));
+        assert!(svg.contains(r#"<rect x="8589934590" y="4294967295""#));
+    }
+
+    #[test]
+    fn test_to_svg_culls_dead_cells() {
+        let mut world = World::empty(1000, 1000);
+        let idx = world.get_index(500, 500);
+        world.grid[idx] = crate::game::world_parts::Cell::Alive;
+
+        let svg = world.to_svg(1);
+        assert_well_formed(&svg);
+        assert_eq!(svg.matches("<rect").count(), 2);
+    }
+
+    #[test]
+    fn test_to_svg_with_options() {
+        let world: World = ".....\n..O..\n...O.\n.OOO.\n.....".parse().unwrap();
+        let options = SvgOptions {
+            alive: "#ff0000".to_string(),
+            grid: Some("gray".to_string()),
+            crop: true,
+            ..SvgOptions::default()
+        };
+
+        let svg = world.to_svg_with(4, &options);
+        assert_well_formed(&svg);
+        assert!(svg.contains(r#"width="12" height="12""#));
+        assert!(svg.contains(r##"<g fill="#ff0000">"##));
+        assert!(svg.contains(r#"<rect x="4" y="0" width="4" height="4"/>"#));
+        // 4 vertical and 4 horizontal lines around 3x3 cells.
+        assert_eq!(svg.matches("<line").count(), 8);
+    }
+}
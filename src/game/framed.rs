@@ -0,0 +1,244 @@
+use super::{world_parts::DisplayConfig, Topology, World};
+
+/// How `World::render_framed` draws a world.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FrameOptions {
+    /// The characters the cells are drawn with.
+    pub config: DisplayConfig,
+    /// Numbers every nth row and column, starting at 0, if any.
+    pub labels: Option<u32>,
+    /// Adds a line with the generation and population below the frame.
+    pub status: bool,
+}
+
+/// Asserts that two worlds are equal, like `assert_eq!`, but shows both of
+/// them framed and labelled along with their diff when they aren't.
+///
+/// ```should_panic
+/// use game_of_life::{assert_worlds_eq, game::World};
+///
+/// let world: World = "...\nOOO\n...".parse().unwrap();
+/// let mut next = world.clone();
+/// next.evolve();
+/// assert_worlds_eq!(world, next);
+/// ```
+#[macro_export]
+macro_rules! assert_worlds_eq {
+    (@compare $left:expr, $right:expr, $message:expr) => {{
+        let (left, right): (&$crate::game::World, &$crate::game::World) = (&$left, &$right);
+        if left != right {
+            let options = $crate::game::FrameOptions {
+                labels: Some(5),
+                status: true,
+                ..Default::default()
+            };
+            let diff = match left.diff(right) {
+                Ok(diff) if diff.is_empty() => {
+                    "the cells are the same, the rule or edges aren't".to_string()
+                }
+                Ok(diff) => diff.to_string(),
+                Err(e) => e.to_string(),
+            };
+            panic!(
+                "assertion `left == right` failed{}\n left:\n{}\nright:\n{}\ndiff:\n{}",
+                $message,
+                left.render_framed(&options),
+                right.render_framed(&options),
+                diff
+            );
+        }
+    }};
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_worlds_eq!(@compare $left, $right, "")
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        $crate::assert_worlds_eq!(@compare $left, $right, format!(": {}", format_args!($($arg)+)))
+    };
+}
+
+impl World {
+    /// Draws the world within a border, for pasting into bug reports and
+    /// reading assertion failures, see `assert_worlds_eq!`.
+    ///
+    /// Row numbers go on the left, right aligned, and column numbers above,
+    /// starting at the column they number. A column number that would run
+    /// into the one before it is left out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if labels are asked for every 0 cells.
+    ///
+    /// ```
+    /// use game_of_life::game::{FrameOptions, World};
+    ///
+    /// let world: World = "..........\n.OOO......\n..........".parse().unwrap();
+    /// let options = FrameOptions {
+    ///     labels: Some(5),
+    ///     status: true,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     world.render_framed(&options),
+    ///     "  0    5\n \
+    ///      ┌──────────┐\n\
+    ///      0│          │\n \
+    ///      │ ###      │\n \
+    ///      │          │\n \
+    ///      └──────────┘\n\
+    ///      generation 0  population 3"
+    /// );
+    /// ```
+    pub fn render_framed(&self, options: &FrameOptions) -> String {
+        if let Some(every) = options.labels {
+            assert!(every >= 1, "labels must be at least 1 cell apart");
+        }
+        let step = if options.config.separator.is_some() {
+            2
+        } else {
+            1
+        };
+        let hex = self.topology == Topology::Hex;
+        let inner = (self.width as usize - 1) * step + 1 + hex as usize;
+        // Wide enough for the number of the last row that gets one.
+        let margin = options.labels.map_or(0, |every| {
+            ((self.height - 1) / every * every).to_string().len()
+        });
+
+        let mut lines = Vec::with_capacity(self.height as usize + 4);
+        if let Some(every) = options.labels {
+            let mut header = String::new();
+            for col in (0..self.width).step_by(every as usize) {
+                let at = margin + 1 + col as usize * step;
+                if col > 0 && header.len() + 1 > at {
+                    continue;
+                }
+                header.push_str(&" ".repeat(at - header.len()));
+                header.push_str(&col.to_string());
+            }
+            lines.push(header);
+        }
+
+        lines.push(format!("{:margin$}┌{}┐", "", "─".repeat(inner)));
+        for (i, row) in self.rows().enumerate() {
+            let label = match options.labels {
+                Some(every) if (i as u32).is_multiple_of(every) => i.to_string(),
+                _ => String::new(),
+            };
+            let cells = row.display_with(&options.config).to_string();
+            let padding = inner - cells.chars().count();
+            lines.push(format!(
+                "{:>margin$}│{}{}│",
+                label,
+                cells,
+                " ".repeat(padding)
+            ));
+        }
+        lines.push(format!("{:margin$}└{}┘", "", "─".repeat(inner)));
+
+        if options.status {
+            lines.push(format!(
+                "generation {}  population {}",
+                self.generation,
+                self.population()
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FrameOptions;
+    use crate::game::{world_parts::DisplayConfig, StopCondition, Topology, World};
+
+    #[test]
+    fn test_render_framed() {
+        let world: World = "O..\n.O.".parse().unwrap();
+        assert_eq!(
+            world.render_framed(&FrameOptions::default()),
+            "┌───┐\n│#  │\n│ # │\n└───┘"
+        );
+
+        let options = FrameOptions {
+            config: DisplayConfig {
+                separator: Some(' '),
+                ..DisplayConfig::PLAINTEXT
+            },
+            labels: Some(1),
+            status: true,
+        };
+        assert_eq!(
+            world.render_framed(&options),
+            "  0 1 2\n \
+             ┌─────┐\n\
+             0│O . .│\n\
+             1│. O .│\n \
+             └─────┘\n\
+             generation 0  population 2"
+        );
+    }
+
+    #[test]
+    fn test_render_framed_wide_labels() {
+        let options = FrameOptions {
+            labels: Some(3),
+            ..Default::default()
+        };
+        let framed = World::empty(14, 13).render_framed(&options);
+        let lines: Vec<_> = framed.lines().collect();
+        // The row numbers take two columns, for 12.
+        assert_eq!(lines[0], "   0  3  6  9  12");
+        assert_eq!(lines[1], "  ┌──────────────┐");
+        assert_eq!(lines[2], " 0│              │");
+        assert_eq!(lines[3], "  │              │");
+        assert_eq!(lines[11], " 9│              │");
+        assert_eq!(lines[14], "12│              │");
+        assert_eq!(lines.len(), 16);
+        for line in &lines[1..] {
+            assert_eq!(line.chars().count(), 18);
+        }
+
+        // Every other number would run into the one before it.
+        let framed = World::empty(12, 1).render_framed(&FrameOptions {
+            labels: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(framed.lines().next(), Some("  0 2 4 6 8 10"));
+    }
+
+    #[test]
+    fn test_render_framed_hex() {
+        let world: World = "OO\nOO".parse().unwrap();
+        let world = world.with_topology(Topology::Hex).unwrap();
+        assert_eq!(
+            world.render_framed(&FrameOptions::default()),
+            "┌───┐\n│## │\n│ ##│\n└───┘"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "labels must be at least 1 cell apart")]
+    fn test_render_framed_zero_labels() {
+        World::empty(3, 3).render_framed(&FrameOptions {
+            labels: Some(0),
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn test_assert_worlds_eq() {
+        let world: World = ".....\n.....\n.OOO.\n.....\n.....".parse().unwrap();
+        let evolved = |generations| {
+            let mut world = world.clone();
+            world.evolve_n_until(generations, StopCondition::Never);
+            world
+        };
+        assert_worlds_eq!(world, world.clone());
+        assert_worlds_eq!(world, evolved(2), "period {}", 2);
+
+        let result = std::panic::catch_unwind(|| assert_worlds_eq!(world, evolved(1)));
+        let message = result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.starts_with("assertion `left == right` failed\n left:\n  0\n ┌─────┐"));
+        assert!(message.ends_with("diff:\n.....\n..+..\n.-.-.\n..+..\n....."));
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt::Write;
+
+use super::{
+    world_parts::{AnsiStyle, Cell},
+    World,
+};
+
+impl World {
+    /// Draws the world with two rows of cells to a line, as terminal
+    /// characters are about twice as high as they are wide. Each character
+    /// is one of `▀`, `▄`, `█` and a space, after which of its two cells are
+    /// alive. A missing last row of an odd height counts as dead.
+    ///
+    /// Rows are drawn square, without the shift of a hexagonal world.
+    pub fn render_halfblock(&self) -> String {
+        self.halfblock_lines()
+            .map(|pairs| {
+                pairs
+                    .map(
+                        |(upper, lower)| match (upper.is_alive(), lower.is_alive()) {
+                            (true, true) => '█',
+                            (true, false) => '▀',
+                            (false, true) => '▄',
+                            (false, false) => ' ',
+                        },
+                    )
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Same as `render_halfblock`, but with every cell in its color of
+    /// `style`: each character is a `▀` in the color of the upper cell on
+    /// the color of the lower one. Colors are only written where they
+    /// change, and each line ends by resetting them.
+    pub fn render_halfblock_ansi(&self, style: &AnsiStyle) -> String {
+        if style.no_color {
+            return self.render_halfblock();
+        }
+
+        let mut lines = Vec::new();
+        for pairs in self.halfblock_lines() {
+            let mut line = String::new();
+            let (mut fg, mut bg) = (None, None);
+            for (upper, lower) in pairs {
+                let [r, g, b] = style.color(upper);
+                if fg != Some([r, g, b]) {
+                    write!(line, "\x1b[38;2;{};{};{}m", r, g, b).unwrap();
+                    fg = Some([r, g, b]);
+                }
+                let [r, g, b] = style.color(lower);
+                if bg != Some([r, g, b]) {
+                    write!(line, "\x1b[48;2;{};{};{}m", r, g, b).unwrap();
+                    bg = Some([r, g, b]);
+                }
+                line.push('▀');
+            }
+            line.push_str("\x1b[0m");
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+
+    // Every line as the pairs of cells above each other in it.
+    fn halfblock_lines(
+        &self,
+    ) -> impl Iterator<Item = impl Iterator<Item = (Cell, Cell)> + '_> + '_ {
+        (0..self.height.div_ceil(2)).map(move |line| {
+            let upper = self.get_row(line * 2);
+            let lower = (line * 2 + 1 < self.height).then(|| self.get_row(line * 2 + 1));
+            upper
+                .iter()
+                .enumerate()
+                .map(move |(col, &cell)| (cell, lower.map_or(Cell::Dead, |row| row[col])))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{world_parts::AnsiStyle, World};
+
+    #[test]
+    fn test_render_halfblock() {
+        let world: World = "OO..\nO.O.".parse().unwrap();
+        assert_eq!(world.render_halfblock(), "█▀▄ ");
+
+        // The missing row below the last one is dead.
+        let world: World = "O.O\n.OO\nO..".parse().unwrap();
+        assert_eq!(world.render_halfblock(), "▀▄█\n▀  ");
+
+        let world: World = "O".parse().unwrap();
+        assert_eq!(world.render_halfblock(), "▀");
+    }
+
+    #[test]
+    fn test_render_halfblock_ansi() {
+        let style = AnsiStyle {
+            alive: [1, 1, 1],
+            dead: [0, 0, 0],
+            ..AnsiStyle::default()
+        };
+        let world: World = "OOO.\nOO.O".parse().unwrap();
+        assert_eq!(
+            world.render_halfblock_ansi(&style),
+            concat!(
+                "\x1b[38;2;1;1;1m\x1b[48;2;1;1;1m▀▀",
+                "\x1b[48;2;0;0;0m▀",
+                "\x1b[38;2;0;0;0m\x1b[48;2;1;1;1m▀",
+                "\x1b[0m"
+            )
+        );
+
+        let style = AnsiStyle {
+            no_color: true,
+            ..style
+        };
+        assert_eq!(
+            world.render_halfblock_ansi(&style),
+            world.render_halfblock()
+        );
+    }
+}
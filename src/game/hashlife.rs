@@ -0,0 +1,492 @@
+use std::collections::HashMap;
+
+use super::{check_dimensions, world_parts::Cell, Neighborhood, Rule, Topology, World, WorldError};
+
+// Nodes are referred to by their index in `Hashlife::nodes`. The first two
+// are the dead and the live cell.
+type Id = u32;
+
+const DEAD: Id = 0;
+const ALIVE: Id = 1;
+
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    // The north west, north east, south west and south east quarters, all
+    // one level down. Cells have no children.
+    children: [Id; 4],
+    // A node of level `k` covers `2^k` by `2^k` cells.
+    level: u32,
+    population: u64,
+}
+
+/// An unbounded world run by the Hashlife algorithm, which can skip ahead
+/// millions of generations at once for patterns that repeat themselves in
+/// space and time.
+///
+/// The plane is stored as a quadtree in which every distinct square of cells
+/// exists only once, and the future of each square is remembered once it has
+/// been computed. Rules apply as in `SparseWorld`, except that only the 8
+/// nearest cells count as neighbours.
+#[derive(Clone, Debug)]
+pub struct Hashlife {
+    nodes: Vec<Node>,
+    // Finds the node with the given children, so no square is stored twice.
+    lookup: HashMap<[Id; 4], Id>,
+    // The node for the centre of a node after `2^j` generations, by node
+    // and `j`.
+    results: HashMap<(Id, u32), Id>,
+    // The empty node of every level.
+    empty: Vec<Id>,
+    root: Id,
+    rule: Rule,
+    generation: u64,
+}
+
+impl Hashlife {
+    /// Takes over the live cells and the rule of `world`, with its top left
+    /// corner at `(0, 0)`. The world's edges don't carry over, the pattern is
+    /// free to grow in every direction.
+    ///
+    /// Only two state rules looking at the 8 nearest cells of a square grid
+    /// are supported.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rule looks further than the nearest neighbours, has
+    /// dying states or uses the von Neumann neighborhood, or if the world is
+    /// hexagonal.
+    pub fn from_world(world: &World) -> Self {
+        assert!(
+            world.rule.radius() == 1,
+            "hashlife only supports rules of radius 1"
+        );
+        assert!(
+            world.rule.states() == 2,
+            "hashlife only supports rules without dying states"
+        );
+        assert!(
+            world.neighborhood == Neighborhood::Moore && world.topology == Topology::Square,
+            "hashlife only supports the Moore neighborhood on a square grid"
+        );
+
+        let mut life = Self {
+            nodes: vec![
+                Node {
+                    children: [DEAD; 4],
+                    level: 0,
+                    population: 0,
+                },
+                Node {
+                    children: [DEAD; 4],
+                    level: 0,
+                    population: 1,
+                },
+            ],
+            lookup: HashMap::new(),
+            results: HashMap::new(),
+            empty: vec![DEAD],
+            root: DEAD,
+            rule: world.rule,
+            generation: world.generation,
+        };
+        life.root = life.empty(3);
+
+        for row in 0..world.height {
+            for (col, cell) in world.get_row(row).iter().enumerate() {
+                if cell.is_alive() {
+                    life.set(row as i64, col as i64);
+                }
+            }
+        }
+        life
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// The generation the pattern is at, counting on from that of the world
+    /// it was made from. `to_world` hands it back.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn population(&self) -> u64 {
+        self.nodes[self.root as usize].population
+    }
+
+    /// Advances the pattern by `2^pow2` generations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pow2` is 60 or more, which would take the pattern beyond
+    /// the coordinates an `i64` can hold.
+    pub fn step(&mut self, pow2: u32) {
+        assert!(pow2 < 60, "can't step 2^{} generations at once", pow2);
+
+        // The result of a node is its centre, half as wide, so the pattern
+        // has to stay within that for as far as it can travel. Keeping it in
+        // the centre quarter of a node at least 8 times the step is enough.
+        loop {
+            let root = self.nodes[self.root as usize];
+            let centre = self.centre(self.root);
+            let inner = self.centre(centre);
+            if root.level >= pow2 + 3 && self.nodes[inner as usize].population == root.population {
+                break;
+            }
+            self.expand();
+        }
+
+        self.root = self.next(self.root, pow2);
+        self.generation += 1 << pow2;
+    }
+
+    /// Copies the live cells within the `width` by `height` rectangle whose
+    /// top left corner is at `(top, left)` into a world with the same rule.
+    /// Cells outside of it are left out.
+    pub fn to_world(
+        &self,
+        top: i64,
+        left: i64,
+        width: u32,
+        height: u32,
+    ) -> Result<World, WorldError> {
+        check_dimensions(width, height)?;
+        let mut world = World::empty(width, height).with_rule(self.rule);
+
+        let half = self.half_size();
+        let viewport = (top, left, top + height as i64, left + width as i64);
+        self.collect(self.root, -half, -half, viewport, &mut world);
+        world.generation = self.generation;
+        Ok(world)
+    }
+
+    // Half the width of the root, which is centred on `(0, 0)`.
+    fn half_size(&self) -> i64 {
+        1 << (self.nodes[self.root as usize].level - 1)
+    }
+
+    // Makes the cell alive, growing the root until it covers the cell.
+    fn set(&mut self, row: i64, col: i64) {
+        while !(-self.half_size()..self.half_size()).contains(&row)
+            || !(-self.half_size()..self.half_size()).contains(&col)
+        {
+            self.expand();
+        }
+
+        let half = self.half_size();
+        self.root = self.set_in(self.root, row + half, col + half);
+    }
+
+    // Makes the cell at `(row, col)` relative to the node's top left corner
+    // alive, returning the new node.
+    fn set_in(&mut self, id: Id, row: i64, col: i64) -> Id {
+        let node = self.nodes[id as usize];
+        if node.level == 0 {
+            return ALIVE;
+        }
+
+        let half = 1 << (node.level - 1);
+        let quarter = (row >= half) as usize * 2 + (col >= half) as usize;
+        let mut children = node.children;
+        children[quarter] = self.set_in(children[quarter], row % half, col % half);
+        self.join(children)
+    }
+
+    // Adds every live cell within `viewport`, given as top, left, bottom and
+    // right with the last two exclusive, to `world`. The node's top left
+    // corner lies at `(top, left)`.
+    fn collect(
+        &self,
+        id: Id,
+        top: i64,
+        left: i64,
+        viewport: (i64, i64, i64, i64),
+        world: &mut World,
+    ) {
+        let node = self.nodes[id as usize];
+        let size = 1 << node.level;
+        let (v_top, v_left, v_bottom, v_right) = viewport;
+        if node.population == 0
+            || top >= v_bottom
+            || left >= v_right
+            || top + size <= v_top
+            || left + size <= v_left
+        {
+            return;
+        }
+
+        if node.level == 0 {
+            let idx = world.get_index((top - v_top) as u32, (left - v_left) as u32);
+            world.set_cell(idx, Cell::Alive);
+            return;
+        }
+
+        let half = size / 2;
+        let [nw, ne, sw, se] = node.children;
+        self.collect(nw, top, left, viewport, world);
+        self.collect(ne, top, left + half, viewport, world);
+        self.collect(sw, top + half, left, viewport, world);
+        self.collect(se, top + half, left + half, viewport, world);
+    }
+
+    // The one node with the given children.
+    fn join(&mut self, children: [Id; 4]) -> Id {
+        if let Some(&id) = self.lookup.get(&children) {
+            return id;
+        }
+
+        let id = self.nodes.len() as Id;
+        self.nodes.push(Node {
+            children,
+            level: self.nodes[children[0] as usize].level + 1,
+            population: children
+                .iter()
+                .map(|&child| self.nodes[child as usize].population)
+                .sum(),
+        });
+        self.lookup.insert(children, id);
+        id
+    }
+
+    fn empty(&mut self, level: u32) -> Id {
+        while self.empty.len() <= level as usize {
+            let below = *self.empty.last().unwrap();
+            let id = self.join([below; 4]);
+            self.empty.push(id);
+        }
+        self.empty[level as usize]
+    }
+
+    // Doubles the root in size, keeping it centred on `(0, 0)`.
+    fn expand(&mut self) {
+        let root = self.nodes[self.root as usize];
+        let e = self.empty(root.level - 1);
+        let [nw, ne, sw, se] = root.children;
+        let children = [
+            self.join([e, e, e, nw]),
+            self.join([e, e, ne, e]),
+            self.join([e, sw, e, e]),
+            self.join([se, e, e, e]),
+        ];
+        self.root = self.join(children);
+    }
+
+    // The middle of a node, one level down.
+    fn centre(&mut self, id: Id) -> Id {
+        let [nw, ne, sw, se] = self.nodes[id as usize].children;
+        let children = [
+            self.nodes[nw as usize].children[3],
+            self.nodes[ne as usize].children[2],
+            self.nodes[sw as usize].children[1],
+            self.nodes[se as usize].children[0],
+        ];
+        self.join(children)
+    }
+
+    // The centre of a node of level `k` after `2^j` generations, with `j` at
+    // most `k - 2`.
+    fn next(&mut self, id: Id, j: u32) -> Id {
+        let node = self.nodes[id as usize];
+        if node.population == 0 {
+            return self.empty(node.level - 1);
+        }
+        if let Some(&result) = self.results.get(&(id, j)) {
+            return result;
+        }
+
+        let result = if node.level == 2 {
+            self.next_base(id)
+        } else {
+            // Nine overlapping squares half the size of the node, three by
+            // three, whose centres tile the centre of the node.
+            let [nw, ne, sw, se] = node
+                .children
+                .map(|child| self.nodes[child as usize].children);
+            let squares = [
+                self.join(nw),
+                self.join([nw[1], ne[0], nw[3], ne[2]]),
+                self.join(ne),
+                self.join([nw[2], nw[3], sw[0], sw[1]]),
+                self.join([nw[3], ne[2], sw[1], se[0]]),
+                self.join([ne[2], ne[3], se[0], se[1]]),
+                self.join(sw),
+                self.join([sw[1], se[0], sw[3], se[2]]),
+                self.join(se),
+            ];
+
+            // At full speed each half of the time is spent on one of the two
+            // rounds, otherwise all of it on the second.
+            let full = j == node.level - 2;
+            let mut parts = [DEAD; 9];
+            for (part, &square) in parts.iter_mut().zip(&squares) {
+                *part = if full {
+                    self.next(square, j - 1)
+                } else {
+                    self.centre(square)
+                };
+            }
+
+            let rest = if full { j - 1 } else { j };
+            let quarters = [
+                [parts[0], parts[1], parts[3], parts[4]],
+                [parts[1], parts[2], parts[4], parts[5]],
+                [parts[3], parts[4], parts[6], parts[7]],
+                [parts[4], parts[5], parts[7], parts[8]],
+            ];
+            let mut children = [DEAD; 4];
+            for (child, quarter) in children.iter_mut().zip(quarters) {
+                let quarter = self.join(quarter);
+                *child = self.next(quarter, rest);
+            }
+            self.join(children)
+        };
+
+        self.results.insert((id, j), result);
+        result
+    }
+
+    // The centre 2 by 2 cells of a 4 by 4 node after one generation, worked
+    // out cell by cell.
+    fn next_base(&mut self, id: Id) -> Id {
+        let mut cells = [[false; 4]; 4];
+        for (quarter, &child) in self.nodes[id as usize].children.iter().enumerate() {
+            for (i, &cell) in self.nodes[child as usize].children.iter().enumerate() {
+                cells[quarter / 2 * 2 + i / 2][quarter % 2 * 2 + i % 2] = cell == ALIVE;
+            }
+        }
+
+        let mut centre = [DEAD; 4];
+        for (i, cell) in centre.iter_mut().enumerate() {
+            let (row, col) = (1 + i / 2, 1 + i % 2);
+            let mut n = 0;
+            for (r, line) in cells.iter().enumerate().skip(row - 1).take(3) {
+                for (c, &alive) in line.iter().enumerate().skip(col - 1).take(3) {
+                    n += ((r, c) != (row, col) && alive) as u32;
+                }
+            }
+
+            // As everywhere on the plane, nothing is born out of nothing.
+            let alive = if cells[row][col] {
+                self.rule.is_survival(n)
+            } else {
+                n > 0 && self.rule.is_birth(n)
+            };
+            if alive {
+                *cell = ALIVE;
+            }
+        }
+        self.join(centre)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Hashlife;
+    use crate::game::{
+        world_parts::Cell, Boundary, EdgeMode, Neighborhood, Rule, SparseWorld, Topology, World,
+    };
+    use crate::patterns;
+
+    #[test]
+    fn test_hashlife_round_trip() {
+        let world = World::new_with_seed(37, 21, 8).with_rule(Rule::HIGHLIFE);
+        let life = Hashlife::from_world(&world);
+        assert_eq!(life.to_world(0, 0, 37, 21).unwrap(), world);
+        assert_eq!(
+            life.population() as usize,
+            world.grid.iter().filter(|cell| cell.is_alive()).count()
+        );
+
+        // Clipped to a smaller window.
+        let clipped = life.to_world(5, 10, 4, 3).unwrap();
+        for row in 0..3 {
+            assert_eq!(clipped.get_row(row), &world.get_row(row + 5)[10..14]);
+        }
+    }
+
+    #[test]
+    fn test_hashlife_matches_evolve() {
+        // Away from the edges the dense world behaves like the plane.
+        let soup = World::new_with_seed(16, 16, 2);
+        let mut world = World::empty(200, 200);
+        for row in 0..16 {
+            for (col, &cell) in soup.get_row(row).iter().enumerate() {
+                let idx = world.get_index(row + 92, col as u32 + 92);
+                world.grid[idx] = cell;
+            }
+        }
+
+        let mut life = Hashlife::from_world(&world);
+        for _ in 0..50 {
+            world.evolve();
+            life.step(0);
+            assert_eq!(life.to_world(0, 0, 200, 200).unwrap(), world);
+        }
+    }
+
+    #[test]
+    fn test_hashlife_matches_sparse() {
+        for seed in 0..3 {
+            let world = World::new_with_seed(16, 16, seed);
+            let mut sparse = SparseWorld::from(&world);
+            let mut life = Hashlife::from_world(&world);
+
+            // 4096 generations, in steps of varying size.
+            for pow2 in (0..12).chain([0]) {
+                for _ in 0..1 << pow2 {
+                    sparse.evolve();
+                }
+                life.step(pow2);
+
+                assert_eq!(life.population() as usize, sparse.population());
+                if let Some(((top, left), (bottom, right))) = sparse.bounding_box() {
+                    let (width, height) = ((right - left + 1) as u32, (bottom - top + 1) as u32);
+                    assert_eq!(
+                        life.to_world(top, left, width, height),
+                        sparse.to_world(top, left, width, height)
+                    );
+                }
+            }
+            assert_eq!(life.generation(), 4096);
+        }
+    }
+
+    #[test]
+    fn test_hashlife_deep_jump() {
+        // A glider moves one cell diagonally every 4 generations.
+        let glider = patterns::glider();
+        let mut life = Hashlife::from_world(&glider);
+        life.step(40);
+        let offset = 1 << 38;
+        assert_eq!(life.to_world(offset, offset, 3, 3).unwrap(), glider);
+        assert_eq!(life.population(), 5);
+        assert_eq!(life.generation(), 1 << 40);
+    }
+
+    #[test]
+    #[should_panic(expected = "radius 1")]
+    fn test_hashlife_rejects_larger_radius() {
+        Hashlife::from_world(&World::empty(4, 4).with_rule(Rule::BUGS));
+    }
+
+    #[test]
+    fn test_hashlife_rejects_unsupported_worlds() {
+        let world = World::empty(4, 4);
+        let unsupported = [
+            world.clone().with_rule(Rule::BRIANS_BRAIN),
+            world.clone().with_neighborhood(Neighborhood::VonNeumann),
+            world.clone().with_topology(Topology::Hex).unwrap(),
+        ];
+        for world in unsupported {
+            let result = std::panic::catch_unwind(|| Hashlife::from_world(&world));
+            assert!(result.is_err(), "{:?}", world.rule());
+        }
+        assert_eq!(Hashlife::from_world(&world).population(), 0);
+
+        // Edges don't carry over, whatever they are.
+        let mut world = World::empty(4, 4).with_boundary(Boundary::uniform(EdgeMode::Dead));
+        world.set(0, 0, Cell::Alive).unwrap();
+        assert_eq!(Hashlife::from_world(&world).population(), 1);
+    }
+}
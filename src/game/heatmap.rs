@@ -0,0 +1,227 @@
+use super::{
+    expand::grown,
+    shift::{clipped, rotate},
+    world_parts::Cell,
+    World,
+};
+
+/// How often each cell of a world has changed state, see
+/// `World::enable_heatmap`. Cells of still lifes stay cold, while those in
+/// chaotic regions heat up fast.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Heatmap {
+    width: u32,
+    height: u32,
+    // Laid out like the world's grid.
+    counts: Vec<u32>,
+}
+
+impl Heatmap {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            counts: vec![0; width as usize * height as usize],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// How often the cell at `(row, col)` changed, or `None` if it lies
+    /// outside the world.
+    pub fn get(&self, row: u32, col: u32) -> Option<u32> {
+        if row >= self.height || col >= self.width {
+            return None;
+        }
+        Some(self.counts[row as usize * self.width as usize + col as usize])
+    }
+
+    /// The counts of every cell, row by row.
+    pub fn counts(&self) -> &[u32] {
+        &self.counts
+    }
+
+    /// The count of the cell that changed most often.
+    pub fn max(&self) -> u32 {
+        self.counts.iter().copied().max().unwrap_or(0)
+    }
+
+    /// The count at `(row, col)` relative to `max`, within `0.0..=1.0`. All
+    /// cells are 0.0 as long as none has changed.
+    pub fn normalized(&self, row: u32, col: u32) -> Option<f64> {
+        let count = self.get(row, col)?;
+        let max = self.max();
+        Some(if max == 0 {
+            0.0
+        } else {
+            count as f64 / max as f64
+        })
+    }
+
+    /// Every cell's count relative to `max`, row by row, see `normalized`.
+    pub fn normalized_counts(&self) -> Vec<f64> {
+        let max = self.max().max(1) as f64;
+        self.counts
+            .iter()
+            .map(|&count| count as f64 / max)
+            .collect()
+    }
+
+    /// Starts counting over from zero.
+    pub fn reset(&mut self) {
+        self.counts.fill(0);
+    }
+
+    // Counts the cells that differ between the two grids.
+    fn record(&mut self, now: &[Cell], before: &[Cell]) {
+        for ((count, now), before) in self.counts.iter_mut().zip(now).zip(before) {
+            *count = count.saturating_add((now != before) as u32);
+        }
+    }
+
+    // Counts the cells at the given grid indices.
+    fn record_indices(&mut self, changed: &[usize]) {
+        for &idx in changed {
+            self.counts[idx] = self.counts[idx].saturating_add(1);
+        }
+    }
+
+    // Moves the counts into a grid grown by the given number of cells on
+    // each side, see `grown`.
+    pub(super) fn grow(&mut self, sides: [usize; 4]) {
+        let [top, bottom, left, right] = sides;
+        self.counts = grown(&self.counts, self.width, self.height, sides);
+        self.width += (left + right) as u32;
+        self.height += (top + bottom) as u32;
+    }
+
+    // Moves the counts along with the cells, see `World::shift`.
+    pub(super) fn shift(&mut self, d_row: i32, d_col: i32) {
+        rotate(&mut self.counts, self.width, self.height, d_row, d_col);
+    }
+
+    // Same as `shift`, see `World::shift_clipped`.
+    pub(super) fn shift_clipped(&mut self, d_row: i32, d_col: i32) {
+        self.counts = clipped(&self.counts, self.width, self.height, d_row, d_col);
+    }
+}
+
+impl World {
+    /// Starts counting how often each cell changes state as the world
+    /// evolves, through `evolve` and everything built on it as well as
+    /// `evolve_parallel`, `evolve_sparse` and `evolve_region`. Keeps the
+    /// counts if already enabled.
+    ///
+    /// Until this is called evolving doesn't spend any time on it.
+    pub fn enable_heatmap(&mut self) {
+        if self.heatmap.is_none() {
+            self.heatmap = Some(Heatmap::new(self.width, self.height));
+        }
+    }
+
+    /// Stops counting and drops the counts.
+    pub fn disable_heatmap(&mut self) {
+        self.heatmap = None;
+    }
+
+    /// The counts so far, or `None` unless `enable_heatmap` was called.
+    pub fn heatmap(&self) -> Option<&Heatmap> {
+        self.heatmap.as_ref()
+    }
+
+    /// Same as `heatmap`, for resetting the counts.
+    pub fn heatmap_mut(&mut self) -> Option<&mut Heatmap> {
+        self.heatmap.as_mut()
+    }
+
+    // Counts the cells changed by the last generation, which the scratch
+    // buffer still holds.
+    pub(super) fn record_heat(&mut self) {
+        if let Some(heatmap) = &mut self.heatmap {
+            heatmap.record(&self.grid, &self.scratch);
+        }
+    }
+
+    // Same as `record_heat`, for only the cells at `changed`.
+    pub(super) fn record_heat_at(&mut self, changed: &[usize]) {
+        if let Some(heatmap) = &mut self.heatmap {
+            heatmap.record_indices(changed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::fixtures::blinker_and_block;
+    use crate::game::{world_parts::Cell, Rule, World};
+    use crate::patterns;
+
+    #[test]
+    fn test_heatmap() {
+        let mut world = blinker_and_block();
+        assert!(world.heatmap().is_none());
+        world.evolve();
+        world.enable_heatmap();
+        for _ in 0..10 {
+            world.evolve();
+        }
+
+        let heatmap = world.heatmap().unwrap();
+        assert_eq!(heatmap.max(), 10);
+        // The blinker's ends and the cells beside its middle flip every
+        // generation, its middle and the block never change.
+        assert_eq!(heatmap.get(1, 1), Some(10));
+        assert_eq!(heatmap.get(2, 0), Some(10));
+        assert_eq!(heatmap.get(2, 1), Some(0));
+        assert_eq!(heatmap.get(1, 5), Some(0));
+        assert_eq!(heatmap.get(5, 0), None);
+        assert_eq!(heatmap.normalized(2, 2), Some(1.0));
+        assert_eq!(heatmap.normalized(0, 0), Some(0.0));
+        assert_eq!(heatmap.counts().iter().sum::<u32>(), 4 * 10);
+        assert_eq!(heatmap.normalized_counts().iter().sum::<f64>(), 4.0);
+
+        world.heatmap_mut().unwrap().reset();
+        assert_eq!(world.heatmap().unwrap().max(), 0);
+        assert_eq!(world.heatmap().unwrap().normalized(2, 2), Some(0.0));
+
+        world.disable_heatmap();
+        assert!(world.heatmap().is_none());
+    }
+
+    #[test]
+    fn test_heatmap_every_evolve() {
+        let mut world = World::new_with_seed(30, 20, 4).with_rule(Rule::BRIANS_BRAIN);
+        world.enable_heatmap();
+        let mut sparse = world.clone();
+        for _ in 0..15 {
+            world.evolve();
+            sparse.evolve_sparse();
+        }
+        assert_eq!(sparse.heatmap(), world.heatmap());
+        assert!(world.heatmap().unwrap().max() > 0);
+    }
+
+    #[test]
+    fn test_heatmap_expanding() {
+        let mut world = patterns::glider();
+        world.enable_heatmap();
+        for _ in 0..8 {
+            world.evolve_expanding(1);
+        }
+        let heatmap = world.heatmap().unwrap();
+        assert_eq!(
+            (heatmap.width(), heatmap.height()),
+            (world.width(), world.height())
+        );
+        // The glider has moved away from where it started.
+        let (row, col) = (-world.origin().0 as u32, -world.origin().1 as u32);
+        assert!(heatmap.get(row, col + 1).unwrap() > 0);
+        assert_eq!(world.get(row, col + 1), Some(Cell::Dead));
+    }
+}
@@ -0,0 +1,238 @@
+use std::collections::VecDeque;
+
+use super::{world_parts::Cell, World};
+
+/// A world that remembers its last generations, so it can be evolved
+/// backwards as well as forwards.
+///
+/// Only the cells that changed in each generation are stored rather than
+/// the whole grid, so a long history of a big world stays small as long as
+/// little of it changes at a time.
+///
+/// Ages, see `World::enable_ages`, aren't part of the history. Cells that
+/// stepping back or forward changes start over at 0, the others keep the
+/// age they had.
+#[derive(Clone, Debug)]
+pub struct History {
+    world: World,
+    // The generations that can be stepped back through, oldest first.
+    past: VecDeque<Delta>,
+    // The generations stepped back through, most recent last.
+    future: Vec<Delta>,
+    capacity: usize,
+}
+
+// The cells that changed from one generation to the next.
+#[derive(Clone, Debug)]
+struct Delta {
+    // As `(index, before, after)`.
+    cells: Vec<(usize, Cell, Cell)>,
+}
+
+impl History {
+    /// Starts recording `world`, keeping up to `capacity` generations to
+    /// step back through.
+    pub fn new(world: World, capacity: usize) -> Self {
+        Self {
+            world,
+            past: VecDeque::new(),
+            future: Vec::new(),
+            capacity,
+        }
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Stops recording, returning the world as it is now.
+    pub fn into_world(self) -> World {
+        self.world
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes how many generations are kept, forgetting the oldest ones
+    /// if there are more.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.past.len() > capacity {
+            self.past.pop_front();
+        }
+    }
+
+    /// The number of generations that can be stepped back through.
+    pub fn len(&self) -> usize {
+        self.past.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.past.is_empty()
+    }
+
+    /// The number of cell changes stored for all generations, the bulk of
+    /// the memory the history uses.
+    pub fn recorded_changes(&self) -> usize {
+        self.past
+            .iter()
+            .chain(&self.future)
+            .map(|delta| delta.cells.len())
+            .sum()
+    }
+
+    /// Moves on to the next generation, recording the step. After stepping
+    /// back this replays the recorded generation rather than evolving again.
+    pub fn evolve(&mut self) {
+        if self.step_forward() {
+            return;
+        }
+
+        self.world.evolve();
+        // After evolving the scratch buffer holds the previous generation.
+        let cells = self
+            .world
+            .grid
+            .iter()
+            .zip(&self.world.scratch)
+            .enumerate()
+            .filter(|(_, (after, before))| after != before)
+            .map(|(idx, (&after, &before))| (idx, before, after))
+            .collect();
+        self.record(Delta { cells });
+    }
+
+    /// Goes back one generation. Returns `false` if there is none left to
+    /// go back to, because it wasn't recorded or has been forgotten.
+    pub fn step_back(&mut self) -> bool {
+        let Some(delta) = self.past.pop_back() else {
+            return false;
+        };
+        for &(idx, before, _) in &delta.cells {
+            self.world.set_cell(idx, before);
+        }
+        let generation = self.world.generation();
+        self.world.set_generation(generation.saturating_sub(1));
+        self.future.push(delta);
+        true
+    }
+
+    /// Redoes a generation undone by `step_back`. Returns `false` if there
+    /// is none, in which case `evolve` computes a new one.
+    pub fn step_forward(&mut self) -> bool {
+        let Some(delta) = self.future.pop() else {
+            return false;
+        };
+        for &(idx, _, after) in &delta.cells {
+            self.world.set_cell(idx, after);
+        }
+        let generation = self.world.generation();
+        self.world.set_generation(generation + 1);
+        self.record(delta);
+        true
+    }
+
+    // Adds a step to go back through, forgetting the oldest beyond the
+    // capacity.
+    fn record(&mut self, delta: Delta) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.past.len() == self.capacity {
+            self.past.pop_front();
+        }
+        self.past.push_back(delta);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::History;
+    use crate::game::{Rule, World};
+
+    #[test]
+    fn test_history_step_back() {
+        let world = World::new_with_seed(40, 30, 7).with_rule(Rule::BRIANS_BRAIN);
+        let mut history = History::new(world.clone(), 10);
+        let mut snapshots = vec![world];
+        for _ in 0..10 {
+            history.evolve();
+            snapshots.push(history.world().clone());
+        }
+        assert_eq!(history.len(), 10);
+
+        for generation in (5..10).rev() {
+            assert!(history.step_back());
+            assert_eq!(history.world(), &snapshots[generation]);
+            assert_eq!(history.world().generation(), generation as u64);
+            assert_eq!(
+                history.world().population(),
+                snapshots[generation].population()
+            );
+        }
+
+        assert!(history.step_forward());
+        assert_eq!(history.world(), &snapshots[6]);
+        history.evolve();
+        assert_eq!(history.world(), &snapshots[7]);
+        for _ in 0..3 {
+            history.evolve();
+        }
+        assert_eq!(history.world(), &snapshots[10]);
+        assert!(!history.step_forward());
+    }
+
+    #[test]
+    fn test_history_ages() {
+        let mut world: World = ".....\n.....\n.OOO.\n.....\n.....".parse().unwrap();
+        world.enable_ages();
+        let mut history = History::new(world, 10);
+        for _ in 0..3 {
+            history.evolve();
+        }
+        assert_eq!(history.world().age(2, 2), Some(3));
+
+        // The middle cell stays alive and keeps its age, the others start
+        // over.
+        history.step_back();
+        assert_eq!(history.world().age(2, 2), Some(3));
+        assert_eq!(history.world().age(2, 1), Some(0));
+    }
+
+    #[test]
+    fn test_history_capacity() {
+        let world = World::new_with_seed(20, 20, 1);
+        let mut history = History::new(world.clone(), 3);
+        for _ in 0..5 {
+            history.evolve();
+        }
+        assert_eq!(history.len(), 3);
+        for _ in 0..3 {
+            assert!(history.step_back());
+        }
+        assert!(!history.step_back());
+        assert_eq!(history.world().generation(), 2);
+
+        history.set_capacity(1);
+        assert_eq!(history.len(), 0);
+        history.evolve();
+        history.evolve();
+        assert_eq!(history.len(), 1);
+
+        let mut history = History::new(world, 0);
+        history.evolve();
+        assert!(!history.step_back());
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn test_history_stores_changes_only() {
+        let blinker: World = ".....\n..O..\n..O..\n..O..\n.....".parse().unwrap();
+        let mut history = History::new(blinker, 100);
+        for _ in 0..100 {
+            history.evolve();
+        }
+        assert_eq!(history.recorded_changes(), 4 * 100);
+    }
+}
@@ -0,0 +1,225 @@
+use std::fmt;
+
+use super::{world_parts::Cell, Pattern, Topology, World, WorldError};
+
+/// One of the two colours of live cells in the Immigration game.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Team {
+    Red,
+    Blue,
+}
+
+/// A world for the Immigration game, where every live cell belongs to a team.
+/// Cells live and die by the world's rule as usual, and a newborn cell joins
+/// the team most of its live neighbours belong to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColoredWorld {
+    world: World,
+    // The team of every cell, only meaningful where the cell is alive.
+    teams: Vec<Team>,
+}
+
+impl ColoredWorld {
+    pub fn empty(width: u32, height: u32) -> Self {
+        Self::from_world(World::empty(width, height), Team::Red)
+    }
+
+    /// Makes every live cell of `world` a member of `team`. The world's rule,
+    /// neighborhood and topology carry over.
+    pub fn from_world(world: World, team: Team) -> Self {
+        let teams = vec![team; world.grid.len()];
+        Self { world, teams }
+    }
+
+    /// The cells without their teams.
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn width(&self) -> u32 {
+        self.world.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.world.height
+    }
+
+    /// The team of the cell, or `None` if it isn't alive.
+    pub fn get(&self, row: u32, col: u32) -> Option<Team> {
+        if row >= self.world.height || col >= self.world.width {
+            return None;
+        }
+
+        let idx = self.world.get_index(row, col);
+        self.world.grid[idx].is_alive().then_some(self.teams[idx])
+    }
+
+    /// Makes the cell a live member of `team`, or kills it for `None`.
+    pub fn set(&mut self, row: u32, col: u32, team: Option<Team>) -> Result<(), WorldError> {
+        self.world.set(row, col, Cell::from(team.is_some()))?;
+        if let Some(team) = team {
+            let idx = self.world.get_index(row, col);
+            self.teams[idx] = team;
+        }
+        Ok(())
+    }
+
+    /// Stamps a pattern with its top left corner at `(row, col)`, with all of
+    /// its live cells joining `team`. This is how each player seeds a colony.
+    pub fn place(
+        &mut self,
+        pattern: &Pattern,
+        row: u32,
+        col: u32,
+        team: Team,
+    ) -> Result<(), WorldError> {
+        self.world.place(pattern, row, col)?;
+        for r in 0..pattern.height() {
+            for (c, cell) in pattern.get_row(r).iter().enumerate() {
+                if cell.is_alive() {
+                    let idx = self.world.get_index(row + r, col + c as u32);
+                    self.teams[idx] = team;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The number of live cells in `team`.
+    pub fn population(&self, team: Team) -> usize {
+        self.world
+            .grid
+            .iter()
+            .zip(&self.teams)
+            .filter(|(cell, &t)| cell.is_alive() && t == team)
+            .count()
+    }
+
+    pub fn evolve(&mut self) {
+        // Counting only the red cells with the world's own neighbour count
+        // tells how the parents of a newborn cell are split between teams.
+        let mut red = self.world.clone();
+        for (idx, &team) in self.teams.iter().enumerate() {
+            if team != Team::Red {
+                red.set_cell(idx, Cell::Dead);
+            }
+        }
+
+        let previous = self.world.clone();
+        self.world.evolve();
+
+        for row in 0..self.world.height {
+            for col in 0..self.world.width {
+                let idx = self.world.get_index(row, col);
+                if previous.grid[idx].is_alive() || !self.world.grid[idx].is_alive() {
+                    continue;
+                }
+
+                // Under B3 there are always three parents and so always a
+                // majority. Ties under other rules go to blue.
+                let parents = previous.get_num_alive_neighbours(row, col);
+                let red_parents = red.get_num_alive_neighbours(row, col);
+                self.teams[idx] = if 2 * red_parents > parents {
+                    Team::Red
+                } else {
+                    Team::Blue
+                };
+            }
+        }
+    }
+
+    /// A row ready for printing, with `X` for red and `O` for blue cells.
+    pub fn display_row(&self, row: u32) -> ColoredRow<'_> {
+        let start = self.world.get_index(row, 0);
+        ColoredRow {
+            cells: self.world.get_row(row),
+            teams: &self.teams[start..start + self.world.width as usize],
+            indent: self.world.topology == Topology::Hex && row % 2 == 1,
+        }
+    }
+}
+
+/// A row of a `ColoredWorld`, see `ColoredWorld::display_row`.
+pub struct ColoredRow<'a> {
+    cells: &'a [Cell],
+    teams: &'a [Team],
+    indent: bool,
+}
+
+impl<'a> fmt::Display for ColoredRow<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.indent {
+            write!(f, " ")?;
+        }
+        for (cell, team) in self.cells.iter().zip(self.teams) {
+            let symbol = match (cell, team) {
+                (Cell::Alive, Team::Red) => 'X',
+                (Cell::Alive, Team::Blue) => 'O',
+                (Cell::Dying(_), _) => '+',
+                (Cell::Dead, _) => ' ',
+            };
+            write!(f, "{}", symbol)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ColoredWorld, Team};
+    use crate::game::{Pattern, WorldError};
+    use crate::patterns;
+
+    #[test]
+    fn test_colored_world_set() {
+        let mut world = ColoredWorld::empty(4, 3);
+        world.set(1, 2, Some(Team::Blue)).unwrap();
+        assert_eq!(world.get(1, 2), Some(Team::Blue));
+        assert_eq!(world.get(0, 0), None);
+        assert!(world.world().get_row(1)[2].is_alive());
+
+        world.set(1, 2, None).unwrap();
+        assert_eq!(world.get(1, 2), None);
+        assert!(matches!(
+            world.set(3, 0, Some(Team::Red)),
+            Err(WorldError::OutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn test_immigration_majority() {
+        // The cell between the blinker's ends is born with two blue parents
+        // and one red one.
+        let mut world = ColoredWorld::empty(5, 5);
+        world.set(2, 1, Some(Team::Blue)).unwrap();
+        world.set(2, 2, Some(Team::Red)).unwrap();
+        world.set(2, 3, Some(Team::Blue)).unwrap();
+        world.evolve();
+
+        assert_eq!(world.get(1, 2), Some(Team::Blue));
+        assert_eq!(world.get(3, 2), Some(Team::Blue));
+        // The centre survives with its own colour.
+        assert_eq!(world.get(2, 2), Some(Team::Red));
+        assert_eq!(world.get(2, 1), None);
+        assert_eq!(world.display_row(1).to_string(), "  O  ");
+        assert_eq!(world.display_row(2).to_string(), "  X  ");
+    }
+
+    #[test]
+    fn test_immigration_keeps_dynamics() {
+        // Apart from the colours the evolution is plain Conway's.
+        let glider = Pattern::from(patterns::glider());
+        let mut world = ColoredWorld::empty(12, 12);
+        world.place(&glider, 0, 0, Team::Red).unwrap();
+        world.place(&glider, 6, 6, Team::Blue).unwrap();
+        let mut plain = world.world().clone();
+
+        for _ in 0..8 {
+            world.evolve();
+            plain.evolve();
+            assert_eq!(world.world(), &plain);
+        }
+        assert_eq!(world.population(Team::Red), 5);
+        assert_eq!(world.population(Team::Blue), 5);
+    }
+}
@@ -0,0 +1,131 @@
+use super::{world_parts::Cell, World, WorldError};
+
+/// What to do with the parts of an inserted world that reach past the edges
+/// of the target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Overflow {
+    /// Refuse the insertion and leave the target untouched.
+    Error,
+    /// Drop the cells that fall outside the target.
+    Clip,
+    /// Continue on the opposite side, as `evolve` treats the world as a torus.
+    Wrap,
+}
+
+impl World {
+    /// Copies `other` into this world with its top left corner at (`row`,
+    /// `col`), overwriting every cell it covers.
+    pub fn insert(
+        &mut self,
+        other: &World,
+        row: u32,
+        col: u32,
+        overflow: Overflow,
+    ) -> Result<(), WorldError> {
+        self.stamp(other.width, &other.grid, row, col, overflow, false)
+    }
+
+    /// Same as `insert`, but only live cells are copied so whatever is already
+    /// in the world shows through the dead parts of `other`.
+    pub fn insert_or(
+        &mut self,
+        other: &World,
+        row: u32,
+        col: u32,
+        overflow: Overflow,
+    ) -> Result<(), WorldError> {
+        self.stamp(other.width, &other.grid, row, col, overflow, true)
+    }
+
+    // Copies a row-major block of cells `width` wide into the world.
+    pub(crate) fn stamp(
+        &mut self,
+        width: u32,
+        cells: &[Cell],
+        row: u32,
+        col: u32,
+        overflow: Overflow,
+        only_alive: bool,
+    ) -> Result<(), WorldError> {
+        let height = (cells.len() / width as usize) as u32;
+        let bottom = row as u64 + height as u64;
+        let right = col as u64 + width as u64;
+        if overflow == Overflow::Error && (bottom > self.height as u64 || right > self.width as u64)
+        {
+            return Err(WorldError::OutOfBounds {
+                row: (bottom - 1).min(u32::MAX as u64) as u32,
+                col: (right - 1).min(u32::MAX as u64) as u32,
+                width: self.width,
+                height: self.height,
+            });
+        }
+
+        for (i, &cell) in cells.iter().enumerate() {
+            if only_alive && !cell.is_alive() {
+                continue;
+            }
+
+            let r = row as u64 + i as u64 / width as u64;
+            let c = col as u64 + i as u64 % width as u64;
+            let (r, c) = match overflow {
+                Overflow::Wrap => (r % self.height as u64, c % self.width as u64),
+                _ if r >= self.height as u64 || c >= self.width as u64 => continue,
+                _ => (r, c),
+            };
+
+            self.set(r as u32, c as u32, cell)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Overflow;
+    use crate::game::{World, WorldError};
+    use crate::patterns::glider;
+
+    #[test]
+    fn test_insert() {
+        let mut world: World = "OOOOO\nOOOOO\nOOOOO\nOOOOO".parse().unwrap();
+        world.insert(&glider(), 1, 1, Overflow::Error).unwrap();
+
+        let expected: World = "OOOOO\nO.O.O\nO..OO\nOOOOO".parse().unwrap();
+        assert_eq!(world, expected);
+    }
+
+    #[test]
+    fn test_insert_or() {
+        let mut world: World = "O....\n.....\n....O\n.....".parse().unwrap();
+        world.insert_or(&glider(), 1, 1, Overflow::Error).unwrap();
+
+        let expected: World = "O....\n..O..\n...OO\n.OOO.".parse().unwrap();
+        assert_eq!(world, expected);
+    }
+
+    #[test]
+    fn test_insert_overflow() {
+        let mut world = World::empty(4, 4);
+        let result = world.insert(&glider(), 2, 3, Overflow::Error);
+        assert_eq!(
+            result,
+            Err(WorldError::OutOfBounds {
+                row: 4,
+                col: 5,
+                width: 4,
+                height: 4
+            })
+        );
+        assert_eq!(world, World::empty(4, 4));
+
+        world.insert(&glider(), 2, 2, Overflow::Clip).unwrap();
+        let expected: World = "....\n....\n...O\n....".parse().unwrap();
+        assert_eq!(world, expected);
+
+        let mut world = World::empty(4, 4);
+        world.insert(&glider(), 2, 2, Overflow::Wrap).unwrap();
+        let expected: World = "O.OO\n....\n...O\nO...".parse().unwrap();
+        assert_eq!(world, expected);
+    }
+}
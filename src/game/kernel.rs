@@ -0,0 +1,244 @@
+use super::{world_parts::Cell, Boundary, Neighborhood, Topology, World};
+
+// The buffers `evolve_words` packs the cells into, kept on the world for the
+// same reason as its scratch grid.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct WordBuffers {
+    // Bit `col % 64` of word `col / 64` of a row is the cell in column
+    // `col`.
+    pub(crate) alive: Vec<u64>,
+    pub(crate) dead: Vec<u64>,
+    // The live cells of the rows around the one being evolved, moved a
+    // column either way.
+    pub(crate) west: Vec<u64>,
+    pub(crate) east: Vec<u64>,
+}
+
+impl WordBuffers {
+    // Empties the packed cells, making room for `len` words of each.
+    pub(crate) fn clear_packed(&mut self, len: usize) {
+        for packed in [&mut self.alive, &mut self.dead] {
+            packed.clear();
+            packed.resize(len, 0);
+        }
+    }
+}
+
+impl World {
+    // Whether `evolve_words` gives the same result as counting neighbours
+    // cell by cell. Narrow worlds count wrapped neighbours only once, which
+    // the shifts below don't.
+    pub(crate) fn uses_word_kernel(&self) -> bool {
+        self.rule.states() == 2
+            && self.rule.radius() == 1
+            && self.neighborhood == Neighborhood::Moore
+            && self.topology == Topology::Square
+            && self.boundary == Boundary::TORUS
+            && self.width >= 3
+            && self.height >= 3
+    }
+
+    // Evolves 64 cells at a time, with the cells of every row packed into
+    // bits and the neighbour counts added up with bitwise adders. Returns the
+    // new population.
+    pub(crate) fn evolve_words(&self, buffers: &mut WordBuffers, new_grid: &mut [Cell]) -> usize {
+        self.pack_words(buffers);
+        let WordBuffers {
+            alive,
+            dead,
+            west,
+            east,
+        } = buffers;
+        for shifted in [&mut *west, &mut *east] {
+            shifted.resize(3 * self.words_per_row(), 0);
+        }
+        self.evolve_word_rows(alive, dead, 0, new_grid, west, east)
+    }
+
+    // Packs the cells into bits, into the words of the live cells and those
+    // of the dead ones.
+    fn pack_words(&self, buffers: &mut WordBuffers) {
+        let (width, words) = (self.width as usize, self.words_per_row());
+        buffers.clear_packed(words * self.height as usize);
+        for ((cells, alive), dead) in self
+            .grid
+            .chunks(width)
+            .zip(buffers.alive.chunks_mut(words))
+            .zip(buffers.dead.chunks_mut(words))
+        {
+            pack_row(cells, alive, dead);
+        }
+    }
+
+    pub(crate) fn words_per_row(&self) -> usize {
+        (self.width as usize).div_ceil(64)
+    }
+
+    // Evolves the rows from `first_row` on, as many as `new_rows` holds, out
+    // of the packed cells. `west` and `east` take the shifted rows around
+    // each row, three rows' worth of words each. Returns the population of
+    // the rows.
+    pub(crate) fn evolve_word_rows(
+        &self,
+        alive: &[u64],
+        dead: &[u64],
+        first_row: usize,
+        new_rows: &mut [Cell],
+        west: &mut [u64],
+        east: &mut [u64],
+    ) -> usize {
+        let (width, words) = (self.width as usize, self.words_per_row());
+
+        // The neighbour counts that give birth and survival.
+        let births = (0..=8).filter(|&n| self.rule.is_birth(n));
+        let survivals = (0..=8).filter(|&n| self.rule.is_survival(n));
+
+        let height = self.height as usize;
+        let mut population = 0;
+        for (row, new_row) in (first_row..).zip(new_rows.chunks_mut(width)) {
+            let lines = [(row + height - 1) % height, row, (row + 1) % height];
+            for (i, &line) in lines.iter().enumerate() {
+                let cells = &alive[line * words..(line + 1) * words];
+                shift_west(cells, width, &mut west[i * words..(i + 1) * words]);
+                shift_east(cells, width, &mut east[i * words..(i + 1) * words]);
+            }
+
+            for word in 0..words {
+                // The live neighbours of each of the 64 cells, in binary
+                // with one word per bit.
+                let mut count = [0u64; 4];
+                for (i, &line) in lines.iter().enumerate() {
+                    add(&mut count, west[i * words + word]);
+                    add(&mut count, east[i * words + word]);
+                    if line != row {
+                        add(&mut count, alive[line * words + word]);
+                    }
+                }
+
+                let equals = |n: u32| {
+                    count.iter().enumerate().fold(!0, |acc, (bit, &plane)| {
+                        acc & if n >> bit & 1 == 1 { plane } else { !plane }
+                    })
+                };
+                let born = births.clone().fold(0, |acc, n| acc | equals(n));
+                let survives = survivals.clone().fold(0, |acc, n| acc | equals(n));
+                let next = alive[row * words + word] & survives | dead[row * words + word] & born;
+
+                let start = word * 64;
+                let end = (start + 64).min(width);
+                population += (next & (!0 >> (64 - (end - start)))).count_ones() as usize;
+                for (bit, cell) in new_row[start..end].iter_mut().enumerate() {
+                    *cell = Cell::from(next >> bit & 1 == 1);
+                }
+            }
+        }
+        population
+    }
+}
+
+// Packs a row of cells into the words of its live and its dead cells.
+pub(crate) fn pack_row(cells: &[Cell], alive: &mut [u64], dead: &mut [u64]) {
+    for (col, cell) in cells.iter().enumerate() {
+        let bit = 1 << (col % 64);
+        match cell {
+            Cell::Alive => alive[col / 64] |= bit,
+            Cell::Dead => dead[col / 64] |= bit,
+            // Neither counts as a neighbour nor can be born.
+            Cell::Dying(_) => {}
+        }
+    }
+}
+
+// Adds one bit per cell to the counters, rippling the carries up.
+fn add(count: &mut [u64; 4], mut bits: u64) {
+    for plane in count.iter_mut() {
+        let carry = *plane & bits;
+        *plane ^= bits;
+        bits = carry;
+    }
+}
+
+// Every cell's western neighbour, so bit `col` holds column `col - 1`, with
+// the first column wrapping around to the last.
+fn shift_west(cells: &[u64], width: usize, out: &mut [u64]) {
+    let last = cells[(width - 1) / 64] >> ((width - 1) % 64) & 1;
+    let mut carry = last;
+    for (out, &word) in out.iter_mut().zip(cells) {
+        *out = word << 1 | carry;
+        carry = word >> 63;
+    }
+}
+
+// Every cell's eastern neighbour, so bit `col` holds column `col + 1`, with
+// the last column wrapping around to the first.
+fn shift_east(cells: &[u64], width: usize, out: &mut [u64]) {
+    let mut carry = 0;
+    for (out, &word) in out.iter_mut().zip(cells).rev() {
+        *out = word >> 1 | carry << 63;
+        carry = word & 1;
+    }
+    // The padding past the end of the row is empty, so the last column got
+    // nothing shifted in and can take the first one.
+    out[(width - 1) / 64] |= (cells[0] & 1) << ((width - 1) % 64);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{world_parts::Cell, Rule, World};
+
+    // The next generation with the neighbours counted one cell at a time.
+    fn evolve_scalar(world: &World) -> Vec<Cell> {
+        (0..world.height)
+            .flat_map(|row| (0..world.width).map(move |col| (row, col)))
+            .map(|(row, col)| {
+                let n = world.get_num_alive_neighbours(row, col);
+                world.grid[world.get_index(row, col)].set_state(n, &world.rule)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_word_kernel_matches_scalar() {
+        for width in [3, 5, 63, 64, 65, 127, 128, 130] {
+            for seed in 0..3 {
+                for rule in [
+                    Rule::CONWAY,
+                    Rule::HIGHLIFE,
+                    Rule::SEEDS,
+                    Rule::DAY_AND_NIGHT,
+                ] {
+                    let mut world = World::new_with_seed(width, 7, seed).with_rule(rule);
+                    assert!(world.uses_word_kernel());
+                    for _ in 0..10 {
+                        let expected = evolve_scalar(&world);
+                        world.evolve();
+                        assert_eq!(world.grid, expected, "{}x7, {}, seed {}", width, rule, seed);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_word_kernel_leaves_dying_cells() {
+        // Left over from a rule with more states, dying cells still die
+        // and aren't born in the same step.
+        let mut world = World::new_with_seed(70, 10, 1).with_rule(Rule::BRIANS_BRAIN);
+        world.evolve();
+        world.set_rule(Rule::CONWAY);
+        assert!(world.grid.iter().any(|cell| matches!(cell, Cell::Dying(_))));
+
+        let expected = evolve_scalar(&world);
+        world.evolve();
+        assert_eq!(world.grid, expected);
+    }
+
+    #[test]
+    fn test_word_kernel_narrow_worlds() {
+        assert!(!World::empty(2, 10).uses_word_kernel());
+        assert!(!World::empty(10, 1).uses_word_kernel());
+        assert!(!World::empty(10, 10)
+            .with_rule(Rule::BUGS)
+            .uses_word_kernel());
+    }
+}
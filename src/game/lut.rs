@@ -0,0 +1,165 @@
+use super::{world_parts::Cell, Neighborhood, Rule, Topology, World};
+
+/// Whether a cell is alive in the next generation, for every 3 by 3 block
+/// of cells around it. Bit `i` of the block is row `i % 3` and column
+/// `2 - i / 3`, so the cell itself is bit 4.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Transitions([u64; 8]);
+
+impl Transitions {
+    pub(crate) fn new(rule: &Rule) -> Self {
+        let mut table = [0; 8];
+        for block in 0..512u32 {
+            let n = (block & !(1 << 4)).count_ones();
+            let alive = if block >> 4 & 1 == 1 {
+                rule.is_survival(n)
+            } else {
+                rule.is_birth(n)
+            };
+            table[block as usize / 64] |= (alive as u64) << (block % 64);
+        }
+        Self(table)
+    }
+
+    fn is_alive(&self, block: usize) -> bool {
+        self.0[block / 64] >> (block % 64) & 1 == 1
+    }
+}
+
+impl World {
+    // Whether `evolve_lut` gives the same result as counting neighbours cell
+    // by cell. Narrow worlds count wrapped neighbours only once, which the
+    // blocks don't.
+    pub(crate) fn uses_lut(&self) -> bool {
+        self.rule.radius() == 1
+            && self.neighborhood == Neighborhood::Moore
+            && self.topology == Topology::Square
+            && self.width >= 3
+            && self.height >= 3
+    }
+
+    // Evolves the cells row by row, looking the next state of every cell up
+    // by the block around it. Sliding along a row only brings in one new
+    // column of the block at a time. Returns the new population.
+    pub(crate) fn evolve_lut(&self, new_grid: &mut [Cell]) -> usize {
+        self.evolve_lut_rows(0, new_grid)
+    }
+
+    // Same as `evolve_lut` for the rows from `first_row` on, as many as
+    // `new_rows` holds. Returns the population of those rows.
+    pub(crate) fn evolve_lut_rows(&self, first_row: u32, new_rows: &mut [Cell]) -> usize {
+        let width = self.width as usize;
+        let mut population = 0;
+
+        // The rows above, at and below the one being evolved, with the
+        // columns beyond either edge at the ends.
+        let mut lines = [
+            vec![false; width + 2],
+            vec![false; width + 2],
+            vec![false; width + 2],
+        ];
+        for (row, new_row) in (first_row..).zip(new_rows.chunks_mut(width)) {
+            for (line, d_row) in lines.iter_mut().zip(-1..=1) {
+                self.load_line(row as i64 + d_row, line);
+            }
+
+            let column = |col: usize| {
+                (lines[0][col] as usize)
+                    | (lines[1][col] as usize) << 1
+                    | (lines[2][col] as usize) << 2
+            };
+            let mut block = column(0) << 3 | column(1);
+            let old_row = self.get_row(row);
+            for (col, (new, old)) in new_row.iter_mut().zip(old_row).enumerate() {
+                block = (block << 3 | column(col + 2)) & 0o777;
+
+                let alive = self.transitions.is_alive(block);
+                *new = match *old {
+                    Cell::Alive | Cell::Dead if alive => Cell::Alive,
+                    cell => cell.decay(&self.rule),
+                };
+                population += new.is_alive() as usize;
+            }
+        }
+        population
+    }
+
+    // Fills `line` with the row, that may lie outside the world, from one
+    // column left of the world to one column right of it.
+    fn load_line(&self, row: i64, line: &mut [bool]) {
+        let width = self.width as i64;
+        if (0..self.height as i64).contains(&row) {
+            let cells = self.get_row(row as u32);
+            for (alive, cell) in line[1..].iter_mut().zip(cells) {
+                *alive = cell.is_alive();
+            }
+            line[0] = self.is_alive_at(row, -1);
+            line[width as usize + 1] = self.is_alive_at(row, width);
+        } else {
+            for (alive, col) in line.iter_mut().zip(-1..=width) {
+                *alive = self.is_alive_at(row, col);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{world_parts::Cell, Boundary, EdgeMode, Rule, World};
+
+    // The next generation with the neighbours counted one cell at a time.
+    fn evolve_scalar(world: &World) -> Vec<Cell> {
+        (0..world.height)
+            .flat_map(|row| (0..world.width).map(move |col| (row, col)))
+            .map(|(row, col)| {
+                let n = world.get_num_alive_in_window(row, col);
+                world.grid[world.get_index(row, col)].set_state(n, &world.rule)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lut_matches_scalar() {
+        let boundaries = [
+            Boundary::TORUS,
+            Boundary::CYLINDER,
+            Boundary::uniform(EdgeMode::Dead),
+            Boundary::uniform(EdgeMode::Mirror),
+            Boundary::KLEIN_BOTTLE,
+            Boundary::CROSS_SURFACE,
+        ];
+        let rules = [
+            Rule::CONWAY,
+            Rule::BRIANS_BRAIN,
+            Rule::DAY_AND_NIGHT,
+            Rule::SEEDS,
+        ];
+        for (i, boundary) in boundaries.into_iter().enumerate() {
+            for rule in rules {
+                let mut world = World::new_with_seed(23, 17, i as u64)
+                    .with_rule(rule)
+                    .with_boundary(boundary);
+                for _ in 0..10 {
+                    let mut next = vec![Cell::Dead; world.grid.len()];
+                    world.evolve_lut(&mut next);
+                    assert_eq!(next, evolve_scalar(&world), "{:?}, {}", boundary, rule);
+                    world.grid = next;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lut_follows_rule_changes() {
+        let mut world: World = ".....\n..O..\n..O..\n..O..\n.....".parse().unwrap();
+        world.set_boundary(Boundary::uniform(EdgeMode::Dead));
+        world.evolve();
+
+        // Nothing dies under Life without Death, so the blinker's middle row
+        // stays as the cells above and below are born.
+        world.set_rule(Rule::LIFE_WITHOUT_DEATH);
+        world.evolve();
+        let expected: World = ".....\n..O..\n.OOO.\n..O..\n.....".parse().unwrap();
+        assert_eq!(world.grid, expected.grid);
+    }
+}
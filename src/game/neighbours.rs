@@ -0,0 +1,599 @@
+use super::{World, WorldError};
+
+/// Which of the surrounding cells count as a cell's neighbours.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Neighborhood {
+    #[default]
+    /// The 8 cells around it, or the whole square within the rule's radius.
+    Moore,
+    /// The 4 orthogonally adjacent cells, or every cell within the rule's
+    /// radius in steps along the rows and columns.
+    VonNeumann,
+}
+
+/// How the cells of a world are laid out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Topology {
+    #[default]
+    /// A square grid, where the neighborhood decides which cells count.
+    Square,
+    /// A hexagonal grid with every odd row shifted half a cell to the right,
+    /// so each cell has 6 neighbours and rules use counts up to 6. Only the
+    /// adjacent cells count, whatever the neighborhood and radius.
+    Hex,
+}
+
+/// What lies beyond one pair of opposite edges of a world.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum EdgeMode {
+    #[default]
+    /// The edges wrap around to the other side.
+    Wrap,
+    /// Everything beyond the edges is dead.
+    Dead,
+    /// The outside is a mirror image of the inside, as if the edges were
+    /// mirrors.
+    Mirror,
+    /// The edges wrap around to the other side, but crossing them flips the
+    /// other coordinate, like the seam of a Klein bottle.
+    Twisted,
+}
+
+impl EdgeMode {
+    // Maps a position along an axis of `len` cells into the world, or to
+    // `None` when it lies outside and counts as dead. A twist of the other
+    // coordinate is left to `Boundary::resolve`.
+    fn resolve(self, i: i64, len: u32) -> Option<usize> {
+        let len = len as i64;
+        if (0..len).contains(&i) {
+            return Some(i as usize);
+        }
+
+        match self {
+            EdgeMode::Wrap | EdgeMode::Twisted => Some(i.rem_euclid(len) as usize),
+            EdgeMode::Dead => None,
+            EdgeMode::Mirror => {
+                let i = i.rem_euclid(2 * len);
+                Some(if i < len { i } else { 2 * len - 1 - i } as usize)
+            }
+        }
+    }
+
+    // How far a window of `radius` extends before and after a position on an
+    // axis of `len` cells. Wrapping axes shorter than the window would bring
+    // the same cells around more than once, so there it is cut down to one
+    // full turn.
+    fn reach(self, radius: i64, len: u32) -> (i64, i64) {
+        match self {
+            EdgeMode::Wrap | EdgeMode::Twisted => {
+                let len = len as i64;
+                (radius.min((len - 1) / 2), radius.min(len / 2))
+            }
+            EdgeMode::Dead | EdgeMode::Mirror => (radius, radius),
+        }
+    }
+}
+
+/// What lies beyond the edges of a world, set separately for the left and
+/// right edges and for the top and bottom ones. Wrapping both makes the
+/// world a torus, and wrapping one of them a cylinder.
+///
+/// With twisted edges on both axes, the cross-surface, each corner cell is
+/// its own diagonal neighbour across the corner.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Boundary {
+    /// The left and right edges.
+    pub horizontal: EdgeMode,
+    /// The top and bottom edges.
+    pub vertical: EdgeMode,
+}
+
+impl Boundary {
+    pub const fn new(horizontal: EdgeMode, vertical: EdgeMode) -> Self {
+        Self {
+            horizontal,
+            vertical,
+        }
+    }
+
+    /// The same mode on all four edges.
+    pub const fn uniform(mode: EdgeMode) -> Self {
+        Self::new(mode, mode)
+    }
+
+    /// Wraps around on every edge, the default.
+    pub const TORUS: Boundary = Boundary::uniform(EdgeMode::Wrap);
+
+    /// Wraps around from left to right, but the top and bottom are dead.
+    pub const CYLINDER: Boundary = Boundary::new(EdgeMode::Wrap, EdgeMode::Dead);
+
+    /// Wraps around from top to bottom as usual, and from left to right
+    /// upside down.
+    pub const KLEIN_BOTTLE: Boundary = Boundary::new(EdgeMode::Twisted, EdgeMode::Wrap);
+
+    /// Both pairs of edges wrap around with a twist, so crossing the top or
+    /// bottom also mirrors left and right.
+    pub const CROSS_SURFACE: Boundary = Boundary::uniform(EdgeMode::Twisted);
+
+    pub(crate) fn is_twisted(&self) -> bool {
+        self.horizontal == EdgeMode::Twisted || self.vertical == EdgeMode::Twisted
+    }
+
+    // Maps a position that may lie outside a world of the given size into
+    // it, or to `None` when it counts as dead. The left and right edges are
+    // crossed first.
+    pub(crate) fn resolve(
+        &self,
+        row: i64,
+        col: i64,
+        width: u32,
+        height: u32,
+    ) -> Option<(usize, usize)> {
+        let (width, height) = (width as i64, height as i64);
+        let mut row = row;
+        if self.horizontal == EdgeMode::Twisted && col.div_euclid(width) % 2 != 0 {
+            row = height - 1 - row;
+        }
+        let col = self.horizontal.resolve(col, width as u32)? as i64;
+
+        let flip_col = self.vertical == EdgeMode::Twisted && row.div_euclid(height) % 2 != 0;
+        let row = self.vertical.resolve(row, height as u32)?;
+        let col = if flip_col { width - 1 - col } else { col };
+        Some((row, col as usize))
+    }
+}
+
+impl World {
+    /// Changes how the cells are laid out. A hexagonal world needs an even
+    /// height, or the shifted rows wouldn't line up where it wraps around.
+    pub fn set_topology(&mut self, topology: Topology) -> Result<(), WorldError> {
+        if topology == Topology::Hex && !self.height.is_multiple_of(2) {
+            return Err(WorldError::OddHexHeight(self.height));
+        }
+        self.topology = topology;
+        self.changed = None;
+        Ok(())
+    }
+
+    /// Same as `set_topology`, for chaining.
+    pub fn with_topology(mut self, topology: Topology) -> Result<Self, WorldError> {
+        self.set_topology(topology)?;
+        Ok(self)
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Replaces what lies beyond the edges, which wrap around unless changed.
+    pub fn with_boundary(mut self, boundary: Boundary) -> Self {
+        self.set_boundary(boundary);
+        self
+    }
+
+    pub fn set_boundary(&mut self, boundary: Boundary) {
+        self.boundary = boundary;
+        self.changed = None;
+    }
+
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    // Whether the cell at a position that may lie outside the world is alive.
+    pub(crate) fn is_alive_at(&self, row: i64, col: i64) -> bool {
+        match self.boundary.resolve(row, col, self.width, self.height) {
+            Some((row, col)) => self.grid[row * self.width as usize + col].is_alive(),
+            None => false,
+        }
+    }
+
+    /// Counts the live cells among the 6 neighbours of a cell in a
+    /// hexagonal world.
+    pub(crate) fn get_num_alive_hex(&self, row: u32, col: u32) -> u32 {
+        let (row, col) = (row as i64, col as i64);
+        // Odd rows are shifted right, so the rows above and below touch a
+        // cell's own column and the one on the shifted side.
+        let side = if row % 2 == 0 { col - 1 } else { col + 1 };
+
+        let neighbours = [
+            (row, col - 1),
+            (row, col + 1),
+            (row - 1, col),
+            (row - 1, side),
+            (row + 1, col),
+            (row + 1, side),
+        ]
+        .map(|(row, col)| self.boundary.resolve(row, col, self.width, self.height));
+
+        // Wrapping around a very narrow world brings some of them back to the
+        // same cell, or to the cell itself, which only counts once or not at
+        // all. A mirrored edge reflects cells on purpose, so there they count
+        // as they are.
+        let mirrored = self.boundary.horizontal == EdgeMode::Mirror
+            || self.boundary.vertical == EdgeMode::Mirror;
+        let own = Some((row as usize, col as usize));
+
+        let mut count = 0;
+        for (i, &neighbour) in neighbours.iter().enumerate() {
+            let Some((row, col)) = neighbour else {
+                continue;
+            };
+            if !mirrored && (neighbour == own || neighbours[..i].contains(&neighbour)) {
+                continue;
+            }
+            count += self.grid[row * self.width as usize + col].is_alive() as u32;
+        }
+        count
+    }
+
+    /// Counts the live cells within the rule's radius around a cell.
+    pub(crate) fn get_num_alive_in_window(&self, row: u32, col: u32) -> u32 {
+        let radius = self.rule.radius() as i64;
+        let (up, down) = self.boundary.vertical.reach(radius, self.height);
+        let (row, col) = (row as i64, col as i64);
+        let mut count = 0;
+
+        for d_row in -up..=down {
+            // The von Neumann neighborhood is a diamond, so its rows get
+            // narrower away from the centre.
+            let reach = match self.neighborhood {
+                Neighborhood::Moore => radius,
+                Neighborhood::VonNeumann => radius - d_row.abs(),
+            };
+            let (left, right) = self.boundary.horizontal.reach(reach, self.width);
+            for d_col in -left..=right {
+                if (d_row, d_col) != (0, 0) {
+                    count += self.is_alive_at(row + d_row, col + d_col) as u32;
+                }
+            }
+        }
+        count
+    }
+
+    /// The live neighbour count of every cell within the rule's radius in the
+    /// Moore neighborhood, laid out like the grid. Twisted edges aren't
+    /// supported, as the window can't be split up along them.
+    pub(crate) fn window_counts(&self) -> Vec<u32> {
+        debug_assert!(!self.boundary.is_twisted());
+
+        // The square window is split into a sum along each row followed by a
+        // sum of those along each column. Both are running sums that add the
+        // cell entering the window and drop the one leaving it, so the cost
+        // doesn't depend on the radius.
+        let radius = self.rule.radius() as i64;
+        let (width, height) = (self.width as usize, self.height as usize);
+        let horizontal = self.boundary.horizontal.reach(radius, self.width);
+        let vertical = self.boundary.vertical.reach(radius, self.height);
+
+        let mut row_sums = vec![0; self.grid.len()];
+        for row in 0..height {
+            let cells = &self.grid[row * width..(row + 1) * width];
+            let sums = &mut row_sums[row * width..(row + 1) * width];
+            running_sums(
+                |i| {
+                    self.boundary
+                        .horizontal
+                        .resolve(i, self.width)
+                        .map_or(0, |i| cells[i].is_alive() as u32)
+                },
+                |i, sum| sums[i] = sum,
+                width,
+                horizontal,
+            );
+        }
+
+        let mut counts = vec![0; self.grid.len()];
+        for col in 0..width {
+            running_sums(
+                |i| {
+                    self.boundary
+                        .vertical
+                        .resolve(i, self.height)
+                        .map_or(0, |i| row_sums[i * width + col])
+                },
+                |i, sum| counts[i * width + col] = sum,
+                height,
+                vertical,
+            );
+        }
+
+        for (count, cell) in counts.iter_mut().zip(&self.grid) {
+            *count -= cell.is_alive() as u32;
+        }
+        counts
+    }
+}
+
+// Sums the values from `before` positions before to `after` positions after
+// every position in `0..len`, where `value` takes care of positions beyond
+// either end.
+fn running_sums(
+    value: impl Fn(i64) -> u32,
+    mut set: impl FnMut(usize, u32),
+    len: usize,
+    (before, after): (i64, i64),
+) {
+    let mut sum = (-before..=after).map(&value).sum::<u32>();
+    for i in 0..len {
+        set(i, sum);
+        let i = i as i64;
+        sum = sum + value(i + after + 1) - value(i - before);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Boundary, EdgeMode, Topology};
+    use crate::{
+        game::{Neighborhood, Overflow, Pattern, Rule, Transform, World, WorldError},
+        patterns,
+    };
+
+    #[test]
+    fn test_window_counts() {
+        let rule = Rule::larger_than_life(2, 0..=0, 0..=0);
+        let world = World::new_with_seed(9, 7, 3).with_rule(rule);
+        let counts = world.window_counts();
+        for row in 0..world.height() {
+            for col in 0..world.width() {
+                let idx = world.get_index(row, col);
+                assert_eq!(counts[idx], world.get_num_alive_in_window(row, col));
+            }
+        }
+    }
+
+    #[test]
+    fn test_window_wraps() {
+        // A single cell in a corner is a neighbour of every cell within two
+        // steps of it across the edges, and of nothing else.
+        let mut world: World = "O.....\n......\n......\n......\n......\n......"
+            .parse()
+            .unwrap();
+        world.set_rule(Rule::larger_than_life(2, 0..=0, 0..=0));
+        let counts = world.window_counts();
+        for row in 0..6 {
+            for col in 0..6 {
+                let near = |i: u32| i <= 2 || i >= 4;
+                let expected = (near(row) && near(col) && (row, col) != (0, 0)) as u32;
+                assert_eq!(counts[world.get_index(row, col)], expected);
+            }
+        }
+
+        // A window wider than the world still counts every cell only once.
+        let mut world: World = "O.\n..".parse().unwrap();
+        world.set_rule(Rule::larger_than_life(2, 0..=0, 0..=0));
+        assert_eq!(world.window_counts(), vec![0, 1, 1, 1]);
+        assert_eq!(world.get_num_alive_in_window(1, 1), 1);
+
+        let world = World::new_random(3, 4, 1.0)
+            .unwrap()
+            .with_rule(Rule::larger_than_life(2, 0..=0, 0..=0));
+        assert_eq!(world.window_counts(), vec![11; 12]);
+    }
+
+    #[test]
+    fn test_von_neumann_window() {
+        // Within a radius of 2 the diamond holds 12 neighbours.
+        let rule = Rule::larger_than_life(2, 0..=0, 0..=0);
+        let world = World::new_random(7, 7, 1.0)
+            .unwrap()
+            .with_rule(rule)
+            .with_neighborhood(Neighborhood::VonNeumann);
+        assert_eq!(world.get_num_alive_neighbours(0, 0), 12);
+
+        let world: World = "O......\n.......\n.......\n.......\n.......\n.......\n......."
+            .parse()
+            .unwrap();
+        let world = world
+            .with_rule(rule)
+            .with_neighborhood(Neighborhood::VonNeumann);
+        assert_eq!(world.get_num_alive_neighbours(5, 0), 1);
+        assert_eq!(world.get_num_alive_neighbours(6, 6), 1);
+        assert_eq!(world.get_num_alive_neighbours(5, 6), 0);
+    }
+
+    #[test]
+    fn test_hex_neighbours() {
+        let world: World = "....\n.O..\n....\n....".parse().unwrap();
+        let world = world.with_topology(Topology::Hex).unwrap();
+
+        // (1, 1) sits in a shifted row, so it touches columns 1 and 2 of the
+        // rows above and below.
+        let neighbours: Vec<(u32, u32)> = (0..4)
+            .flat_map(|row| (0..4).map(move |col| (row, col)))
+            .filter(|&(row, col)| world.get_num_alive_neighbours(row, col) == 1)
+            .collect();
+        assert_eq!(
+            neighbours,
+            vec![(0, 1), (0, 2), (1, 0), (1, 2), (2, 1), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn test_hex_wraps() {
+        // A cell in the bottom right corner is a shifted row, so below it
+        // wraps to the top row and its right side wraps to the first column.
+        let world: World = "....\n....\n....\n...O".parse().unwrap();
+        let world = world.with_topology(Topology::Hex).unwrap();
+        let neighbours: Vec<(u32, u32)> = (0..4)
+            .flat_map(|row| (0..4).map(move |col| (row, col)))
+            .filter(|&(row, col)| world.get_num_alive_neighbours(row, col) == 1)
+            .collect();
+        assert_eq!(
+            neighbours,
+            vec![(0, 0), (0, 3), (2, 0), (2, 3), (3, 0), (3, 2)]
+        );
+
+        // Every neighbour relation goes both ways, across the seams as well.
+        let world = World::new_with_seed(6, 4, 3)
+            .with_topology(Topology::Hex)
+            .unwrap();
+        let total: u32 = (0..4)
+            .flat_map(|row| (0..6).map(move |col| (row, col)))
+            .filter(|&(row, col)| world[world.get_index(row, col)].is_alive())
+            .map(|(row, col)| world.get_num_alive_neighbours(row, col))
+            .sum();
+        assert_eq!(total % 2, 0);
+
+        assert_eq!(
+            World::empty(4, 3).with_topology(Topology::Hex),
+            Err(WorldError::OddHexHeight(3))
+        );
+    }
+
+    #[test]
+    fn test_hex_narrow() {
+        // On 2x2 the six neighbours fold onto the three other cells.
+        let world: World = "OO\nOO".parse().unwrap();
+        let world = world.with_topology(Topology::Hex).unwrap();
+        for (row, col) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            assert_eq!(world.get_num_alive_neighbours(row, col), 3);
+        }
+    }
+
+    #[test]
+    fn test_evolve_hex() {
+        // Under B2/S34 a lone pair dies, and the two cells touching both of
+        // its members are born, one in the row above and one below.
+        let world: World = "....\n.OO.\n....\n....".parse().unwrap();
+        let mut world = world
+            .with_rule(Rule::hex(&[2], &[3, 4]))
+            .with_topology(Topology::Hex)
+            .unwrap();
+        world.evolve();
+        let expected: World = "..O.\n....\n..O.\n....".parse().unwrap();
+        assert_eq!(world.grid, expected.grid);
+
+        assert_eq!(world.row(0).to_string(), "  # ");
+        assert_eq!(world.row(1).to_string(), "     ");
+    }
+
+    #[test]
+    fn test_edge_mode_resolve() {
+        assert_eq!(EdgeMode::Wrap.resolve(-1, 5), Some(4));
+        assert_eq!(EdgeMode::Wrap.resolve(7, 5), Some(2));
+        assert_eq!(EdgeMode::Dead.resolve(-1, 5), None);
+        assert_eq!(EdgeMode::Dead.resolve(5, 5), None);
+        assert_eq!(EdgeMode::Mirror.resolve(-1, 5), Some(0));
+        assert_eq!(EdgeMode::Mirror.resolve(-2, 5), Some(1));
+        assert_eq!(EdgeMode::Mirror.resolve(6, 5), Some(3));
+        for mode in [EdgeMode::Wrap, EdgeMode::Dead, EdgeMode::Mirror] {
+            assert_eq!(mode.resolve(3, 5), Some(3));
+        }
+    }
+
+    #[test]
+    fn test_glider_at_boundary() {
+        let glider = patterns::glider();
+        let block: World = "OO\nOO".parse().unwrap();
+        let place = |world: &World, row, col| {
+            let mut placed = World::empty(10, 10);
+            placed.insert(world, row, col, Overflow::Error).unwrap();
+            placed
+        };
+
+        let run = |mode| {
+            let mut world = place(&glider, 2, 2).with_boundary(Boundary::uniform(mode));
+            for _ in 0..60 {
+                world.evolve();
+            }
+            world.grid
+        };
+
+        // Wrapping around keeps the glider going, 15 cells down and right.
+        assert_eq!(run(EdgeMode::Wrap), place(&glider, 7, 7).grid);
+        // Against dead edges it crashes into the corner and leaves a block.
+        assert_eq!(run(EdgeMode::Dead), place(&block, 8, 8).grid);
+        // Meeting its own mirror image leaves a block further from the edges.
+        assert_eq!(run(EdgeMode::Mirror), place(&block, 7, 6).grid);
+    }
+
+    #[test]
+    fn test_cylinder() {
+        let lwss = Pattern::from(patterns::lwss());
+        let travel = |pattern: &Pattern| {
+            let mut world = World::empty(20, 20).with_boundary(Boundary::CYLINDER);
+            world.place(pattern, 8, 8).unwrap();
+            let start = world.clone();
+            // Two cells every 4 generations takes it once around the world.
+            for _ in 0..40 {
+                world.evolve();
+            }
+            (start, world)
+        };
+
+        // Travelling left it loops around forever.
+        let (start, world) = travel(&lwss);
+        assert_eq!(world, start);
+
+        // Turned to travel down it crashes into the dead bottom edge instead,
+        // and all that is left is a block.
+        let (_, mut world) = travel(&lwss.rotated(1));
+        for _ in 0..80 {
+            world.evolve();
+        }
+        assert_eq!(world.grid.iter().filter(|cell| cell.is_alive()).count(), 4);
+        let still = world.clone();
+        world.evolve();
+        assert_eq!(world, still);
+    }
+
+    #[test]
+    fn test_klein_bottle_resolve() {
+        let klein = Boundary::KLEIN_BOTTLE;
+        // Across the left and right edges the row is flipped.
+        assert_eq!(klein.resolve(1, -1, 5, 4), Some((2, 4)));
+        assert_eq!(klein.resolve(0, 5, 5, 4), Some((3, 0)));
+        // Across the top and bottom it wraps as on a torus.
+        assert_eq!(klein.resolve(-1, 2, 5, 4), Some((3, 2)));
+        assert_eq!(klein.resolve(4, 2, 5, 4), Some((0, 2)));
+        // Both at once, in the corners.
+        assert_eq!(klein.resolve(-1, -1, 5, 4), Some((0, 4)));
+        assert_eq!(klein.resolve(4, 5, 5, 4), Some((3, 0)));
+        assert_eq!(klein.resolve(2, 3, 5, 4), Some((2, 3)));
+    }
+
+    #[test]
+    fn test_cross_surface_resolve() {
+        let cross = Boundary::CROSS_SURFACE;
+        assert_eq!(cross.resolve(1, -1, 5, 4), Some((2, 4)));
+        assert_eq!(cross.resolve(-1, 1, 5, 4), Some((3, 3)));
+        assert_eq!(cross.resolve(4, 0, 5, 4), Some((0, 4)));
+        // Diagonally across a corner is the corner cell itself.
+        assert_eq!(cross.resolve(-1, -1, 5, 4), Some((0, 0)));
+        assert_eq!(cross.resolve(4, 5, 5, 4), Some((3, 4)));
+
+        // So a lone corner cell is its own neighbour, and nothing else is
+        // counted twice.
+        let world: World = "O....\n.....\n.....\n.....".parse().unwrap();
+        let world = world.with_boundary(cross);
+        assert_eq!(world.get_num_alive_neighbours(0, 0), 1);
+        let total: u32 = (0..4)
+            .flat_map(|row| (0..5).map(move |col| (row, col)))
+            .map(|(row, col)| world.get_num_alive_neighbours(row, col))
+            .sum();
+        assert_eq!(total, 8);
+    }
+
+    #[test]
+    fn test_glider_across_twisted_seam() {
+        // Once the glider is across the twisted seam it is the one on a
+        // torus turned upside down, now heading up and to the right.
+        let glider = patterns::glider();
+        let mut torus = World::empty(10, 10);
+        torus.insert(&glider, 4, 5, Overflow::Error).unwrap();
+        let mut klein = torus.clone().with_boundary(Boundary::KLEIN_BOTTLE);
+        for _ in 0..20 {
+            torus.evolve();
+            klein.evolve();
+        }
+
+        let flipped = Pattern::from(torus).transformed(Transform::FlipVertical);
+        assert_eq!(klein.grid, flipped.cells());
+        assert_ne!(
+            klein.grid,
+            flipped.transformed(Transform::FlipVertical).cells()
+        );
+    }
+}
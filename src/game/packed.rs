@@ -0,0 +1,249 @@
+use std::mem;
+
+use super::{check_dimensions, distinct_deltas, world_parts::Cell, Rule, World};
+
+/// A world that stores one bit per cell, taking an eighth of the memory of
+/// `World`.
+///
+/// It always uses the Moore neighborhood with wrapping edges. Rules apply as
+/// in `World`, except that dying states of Generations rules aren't kept
+/// (cells die at once) and only the 8 nearest cells count as neighbours.
+#[derive(Clone, Debug)]
+pub struct PackedWorld {
+    // Every row starts on a fresh word, with bit `col % 64` of word
+    // `col / 64` holding the cell in column `col`. Bits past the end of a
+    // row are always zero.
+    words: Vec<u64>,
+    // The words the next generation is written into, as in `World`.
+    scratch: Vec<u64>,
+    width: u32,
+    height: u32,
+    rule: Rule,
+}
+
+impl PackedWorld {
+    /// # Panics
+    ///
+    /// Panics for the same dimensions as `World::new`.
+    pub fn empty(width: u32, height: u32) -> Self {
+        if let Err(e) = check_dimensions(width, height) {
+            panic!("invalid world dimensions: {}", e);
+        }
+
+        Self {
+            words: vec![0; words_per_row(width) * height as usize],
+            scratch: Vec::new(),
+            width,
+            height,
+            rule: Rule::default(),
+        }
+    }
+
+    /// Replaces the rule used by `evolve`, which is Conway's unless changed.
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Whether the cell is alive. Cells outside the world are dead.
+    pub fn get(&self, row: u32, col: u32) -> bool {
+        if row >= self.height || col >= self.width {
+            return false;
+        }
+
+        let (word, bit) = self.position(row, col);
+        self.words[word] >> bit & 1 == 1
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the cell lies outside the world.
+    pub fn set(&mut self, row: u32, col: u32, alive: bool) {
+        assert!(
+            row < self.height && col < self.width,
+            "cell ({}, {}) lies outside the {}x{} world",
+            row,
+            col,
+            self.width,
+            self.height
+        );
+
+        let (word, bit) = self.position(row, col);
+        if alive {
+            self.words[word] |= 1 << bit;
+        } else {
+            self.words[word] &= !(1 << bit);
+        }
+    }
+
+    pub fn population(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn evolve(&mut self) {
+        let mut words = mem::take(&mut self.scratch);
+        words.clear();
+        words.resize(self.words.len(), 0);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let n = self.get_num_alive_neighbours(row, col);
+                let alive = if self.get(row, col) {
+                    self.rule.is_survival(n)
+                } else {
+                    self.rule.is_birth(n)
+                };
+
+                if alive {
+                    let (word, bit) = self.position(row, col);
+                    words[word] |= 1 << bit;
+                }
+            }
+        }
+        self.scratch = mem::replace(&mut self.words, words);
+    }
+
+    /// Counts the live cells among the 8 around a cell, wrapping around the
+    /// edges like `World` does.
+    pub fn get_num_alive_neighbours(&self, row: u32, col: u32) -> u32 {
+        let mut count = 0;
+
+        for &delta_row in distinct_deltas(&[self.height - 1, 0, 1], self.height) {
+            for &delta_col in distinct_deltas(&[self.width - 1, 0, 1], self.width) {
+                if delta_row == 0 && delta_col == 0 {
+                    continue;
+                }
+
+                let n_row = (delta_row as u64 + row as u64) % self.height as u64;
+                let n_col = (delta_col as u64 + col as u64) % self.width as u64;
+                count += self.get(n_row as u32, n_col as u32) as u32;
+            }
+        }
+
+        count
+    }
+
+    fn position(&self, row: u32, col: u32) -> (usize, u32) {
+        let word = row as usize * words_per_row(self.width) + col as usize / 64;
+        (word, col % 64)
+    }
+}
+
+impl PartialEq for PackedWorld {
+    fn eq(&self, other: &Self) -> bool {
+        self.words == other.words
+            && self.width == other.width
+            && self.height == other.height
+            && self.rule == other.rule
+    }
+}
+
+impl Eq for PackedWorld {}
+
+fn words_per_row(width: u32) -> usize {
+    (width as usize).div_ceil(64)
+}
+
+impl From<&World> for PackedWorld {
+    /// Takes over the live cells and the rule. Dying cells become dead.
+    fn from(world: &World) -> Self {
+        let mut packed = Self::empty(world.width, world.height).with_rule(world.rule);
+        for row in 0..world.height {
+            for (col, cell) in world.get_row(row).iter().enumerate() {
+                if cell.is_alive() {
+                    packed.set(row, col as u32, true);
+                }
+            }
+        }
+        packed
+    }
+}
+
+impl From<&PackedWorld> for World {
+    fn from(packed: &PackedWorld) -> Self {
+        let cells = (0..packed.height)
+            .flat_map(|row| (0..packed.width).map(move |col| (row, col)))
+            .map(|(row, col)| Cell::from(packed.get(row, col)))
+            .collect();
+        World::from_cells(packed.width, packed.height, cells)
+            .unwrap()
+            .with_rule(packed.rule)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PackedWorld;
+    use crate::game::{Rule, World};
+
+    #[test]
+    fn test_packed_get_set() {
+        let mut world = PackedWorld::empty(70, 3);
+        world.set(1, 0, true);
+        world.set(1, 63, true);
+        world.set(1, 64, true);
+        world.set(2, 69, true);
+        assert!(world.get(1, 63) && world.get(1, 64) && world.get(2, 69));
+        assert!(!world.get(0, 0) && !world.get(1, 1) && !world.get(3, 0));
+        assert_eq!(world.population(), 4);
+
+        world.set(1, 64, false);
+        assert!(!world.get(1, 64));
+        assert_eq!(world.population(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "lies outside")]
+    fn test_packed_set_out_of_bounds() {
+        PackedWorld::empty(4, 4).set(0, 4, true);
+    }
+
+    #[test]
+    fn test_packed_round_trip() {
+        let world = World::new_with_seed(100, 7, 2).with_rule(Rule::HIGHLIFE);
+        assert_eq!(World::from(&PackedWorld::from(&world)), world);
+    }
+
+    #[test]
+    fn test_packed_matches_world() {
+        // Widths around the 64 bit words, and narrow worlds that wrap onto
+        // themselves.
+        let sizes = [(64, 10), (65, 9), (130, 5), (1, 6), (2, 2), (3, 1)];
+        for (i, (width, height)) in sizes.into_iter().enumerate() {
+            for rule in [Rule::CONWAY, Rule::HIGHLIFE, Rule::DAY_AND_NIGHT] {
+                let mut world = World::new_with_seed(width, height, i as u64).with_rule(rule);
+                let mut packed = PackedWorld::from(&world);
+                for _ in 0..100 {
+                    world.evolve();
+                    packed.evolve();
+                    assert_eq!(World::from(&packed), world);
+                }
+            }
+        }
+
+        // The generation before doesn't count towards equality.
+        let blinker = PackedWorld::from(
+            &".....\n.....\n.OOO.\n.....\n....."
+                .parse::<World>()
+                .unwrap(),
+        );
+        let mut evolved = blinker.clone();
+        evolved.evolve();
+        evolved.evolve();
+        assert_eq!(evolved, blinker);
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt;
+
+use super::{
+    world_parts::{Cell, Row},
+    Overflow, Rule, World, WorldError,
+};
+
+/// A reusable shape together with the metadata pattern files carry, as
+/// opposed to a `World`, which is the arena a simulation runs in. Use
+/// `World::place` to stamp a pattern into a world.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    pub name: Option<String>,
+    pub author: Option<String>,
+    pub comments: Vec<String>,
+    /// The rule the pattern is meant to run under.
+    pub rule: Rule,
+    width: u32,
+    height: u32,
+    cells: Vec<Cell>,
+}
+
+impl Pattern {
+    /// Creates a pattern without metadata from cells laid out row by row.
+    pub fn new(width: u32, height: u32, cells: Vec<Cell>) -> Result<Self, WorldError> {
+        Ok(World::from_cells(width, height, cells)?.into())
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    pub fn get_row(&self, row: u32) -> &[Cell] {
+        let start = row as usize * self.width as usize;
+        &self.cells[start..start + self.width as usize]
+    }
+
+    /// A world exactly the size of the pattern, using the pattern's rule.
+    pub fn to_world(&self) -> World {
+        World::from_cells(self.width, self.height, self.cells.clone())
+            .expect("patterns always have valid dimensions")
+            .with_rule(self.rule)
+    }
+}
+
+impl From<World> for Pattern {
+    fn from(world: World) -> Self {
+        Self {
+            name: None,
+            author: None,
+            comments: Vec::new(),
+            rule: world.rule,
+            width: world.width,
+            height: world.height,
+            cells: world.grid,
+        }
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.height {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", Row::new(self.get_row(row)))?;
+        }
+        Ok(())
+    }
+}
+
+impl World {
+    /// Copies the pattern into the world with its top left corner at
+    /// (`row`, `col`). The whole pattern has to fit inside the world.
+    pub fn place(&mut self, pattern: &Pattern, row: u32, col: u32) -> Result<(), WorldError> {
+        self.stamp(
+            pattern.width,
+            &pattern.cells,
+            row,
+            col,
+            Overflow::Error,
+            false,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Pattern;
+    use crate::game::{world_parts::Cell::*, World, WorldError};
+    use crate::patterns;
+
+    #[test]
+    fn test_pattern_new() {
+        let pattern = Pattern::new(2, 1, vec![Alive, Dead]).unwrap();
+        assert_eq!((pattern.width(), pattern.height()), (2, 1));
+        assert_eq!(pattern.name, None);
+
+        assert!(Pattern::new(2, 2, vec![Alive]).is_err());
+    }
+
+    #[test]
+    fn test_pattern_display() {
+        let pattern = Pattern::from(patterns::glider());
+        assert_eq!(pattern.to_string(), " # \n  #\n###");
+    }
+
+    #[test]
+    fn test_place() {
+        let glider = Pattern::from(patterns::glider());
+        let mut world = World::empty(5, 4);
+        world.place(&glider, 1, 2).unwrap();
+
+        let expected: World = ".....\n...O.\n....O\n..OOO".parse().unwrap();
+        assert_eq!(world, expected);
+
+        let result = world.place(&glider, 2, 2);
+        assert_eq!(
+            result,
+            Err(WorldError::OutOfBounds {
+                row: 4,
+                col: 4,
+                width: 5,
+                height: 4
+            })
+        );
+        assert_eq!(world, expected);
+    }
+}
@@ -0,0 +1,423 @@
+use std::collections::{HashMap, VecDeque};
+
+use super::{world_parts::Cell, StopCondition, World};
+
+/// How a world ends up repeating itself, see `World::detect_period`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Period {
+    /// The number of generations after which the world repeats, 1 for a
+    /// world that stopped changing.
+    pub length: u64,
+    /// The number of generations before the repetition starts.
+    pub transient: u64,
+}
+
+/// What `World::detect_cycle` found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CycleResult {
+    /// After `transient` generations the world repeats every `period`
+    /// generations, 1 for a world that stopped changing.
+    Cycle { transient: u64, period: u64 },
+    /// The world didn't start repeating within the given number of
+    /// generations.
+    NoCycleWithin(u64),
+}
+
+/// What `World::run_until_stable` found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StabilizationResult {
+    /// The world entered a cycle of `period` generations, 1 for a world
+    /// that stopped changing, after `generations` generations.
+    Stabilized { generations: u64, period: u64 },
+    /// The world didn't settle within the given number of generations.
+    NotStabilizedWithin(u64),
+}
+
+impl World {
+    /// Whether the next generation is the same as this one.
+    pub fn is_stable(&self) -> bool {
+        self.evolved(1).grid == self.grid
+    }
+
+    /// Evolves the world until it repeats itself every `max_period`
+    /// generations or less, or `max_generations` are up. A soup has settled
+    /// into ash then.
+    ///
+    /// The generations reported are those before the world entered the
+    /// cycle, a world that dies out is stable with period 1 from the
+    /// generation it became empty. The world itself is left where the cycle
+    /// was noticed, up to `period` generations later.
+    ///
+    /// Only the hashes of the last `max_period` generations are kept, and a
+    /// match is confirmed by evolving a copy one more period.
+    pub fn run_until_stable(
+        &mut self,
+        max_generations: u64,
+        max_period: u64,
+    ) -> StabilizationResult {
+        let mut recent = VecDeque::new();
+        for generation in 0..=max_generations {
+            if self.is_dead_for_good() {
+                return StabilizationResult::Stabilized {
+                    generations: generation,
+                    period: 1,
+                };
+            }
+
+            let hash = self.state_hash();
+            let period = recent
+                .iter()
+                .rev()
+                .zip(1..)
+                .filter(|&(&earlier, _)| earlier == hash)
+                .map(|(_, period)| period)
+                .find(|&period| self.evolved(period).grid == self.grid);
+            if let Some(period) = period {
+                return StabilizationResult::Stabilized {
+                    generations: generation - period,
+                    period,
+                };
+            }
+
+            if generation == max_generations {
+                break;
+            }
+            if max_period > 0 {
+                if recent.len() as u64 == max_period {
+                    recent.pop_front();
+                }
+                recent.push_back(hash);
+            }
+            self.evolve();
+        }
+        StabilizationResult::NotStabilizedWithin(max_generations)
+    }
+
+    // Whether every cell is dead and stays that way.
+    fn is_dead_for_good(&self) -> bool {
+        !self.rule.is_birth(0) && self.grid.iter().all(|&cell| cell == Cell::Dead)
+    }
+
+    /// Evolves a copy of the world for up to `max_generations` generations,
+    /// looking for one it has been in before. Returns `None` if none repeats
+    /// within that many.
+    ///
+    /// Only a hash of each earlier generation is kept rather than the world
+    /// itself, so memory still grows with the number of generations, if
+    /// slowly. `detect_cycle` needs none. A match is confirmed by evolving
+    /// another copy up to the earlier generation, so a hash collision can't
+    /// cause a wrong answer.
+    pub fn detect_period(&self, max_generations: u64) -> Option<Period> {
+        let mut world = self.clone();
+        let mut seen = HashMap::new();
+        seen.insert(world.state_hash(), 0);
+
+        for generation in 1..=max_generations {
+            world.evolve();
+            let hash = world.state_hash();
+            match seen.get(&hash) {
+                Some(&earlier) if self.evolved(earlier).grid == world.grid => {
+                    return Some(Period {
+                        length: generation - earlier,
+                        transient: earlier,
+                    });
+                }
+                // A collision, the first generation with the hash is kept.
+                Some(_) => {}
+                None => {
+                    seen.insert(hash, generation);
+                }
+            }
+        }
+        None
+    }
+
+    /// Evolves copies of the world looking for a cycle that is fully seen
+    /// within `max_generations` generations, that is whose transient and
+    /// period add up to at most that many.
+    ///
+    /// Uses Brent's algorithm, so only three copies of the world are kept
+    /// however long it runs, at the cost of evolving up to three times as
+    /// many generations as `detect_period`.
+    pub fn detect_cycle(&self, max_generations: u64) -> CycleResult {
+        let not_found = CycleResult::NoCycleWithin(max_generations);
+        let limit = max_generations.saturating_mul(3);
+
+        // Find the period, with the tortoise waiting at each power of two
+        // for the hare to come round.
+        let mut tortoise = self.clone();
+        let mut hare = self.clone();
+        hare.evolve();
+        let mut steps = 1;
+        let (mut power, mut period) = (1, 1);
+        while tortoise.grid != hare.grid {
+            if steps >= limit {
+                return not_found;
+            }
+            if power == period {
+                tortoise.clone_from(&hare);
+                power *= 2;
+                period = 0;
+            }
+            hare.evolve();
+            steps += 1;
+            period += 1;
+        }
+        if period > max_generations {
+            return not_found;
+        }
+
+        // With the hare a period ahead, they meet where the cycle starts.
+        tortoise.clone_from(self);
+        hare.clone_from(self);
+        hare.evolve_n_until(period, StopCondition::Never);
+        let mut transient = 0;
+        while tortoise.grid != hare.grid {
+            if transient + period >= max_generations {
+                return not_found;
+            }
+            tortoise.evolve();
+            hare.evolve();
+            transient += 1;
+        }
+        CycleResult::Cycle { transient, period }
+    }
+
+    // A copy of the world after `generations` more generations.
+    fn evolved(&self, generations: u64) -> World {
+        let mut world = self.clone();
+        world.evolve_n_until(generations, StopCondition::Never);
+        world
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CycleResult, Period, StabilizationResult};
+    use crate::game::{world_parts::Cell::*, Boundary, EdgeMode, Rule, StopCondition, World};
+
+    #[test]
+    fn test_detect_period_oscillators() {
+        let blinker: World = ".....\n..O..\n..O..\n..O..\n.....".parse().unwrap();
+        assert_eq!(
+            blinker.detect_period(10),
+            Some(Period {
+                length: 2,
+                transient: 0
+            })
+        );
+
+        let block: World = "....\n.OO.\n.OO.\n....".parse().unwrap();
+        assert_eq!(
+            block.detect_period(10),
+            Some(Period {
+                length: 1,
+                transient: 0
+            })
+        );
+
+        // A loose cell dies first.
+        let world: World = ".......\n..O....\n..O....\n..O....\n.......\n.....O."
+            .parse()
+            .unwrap();
+        assert_eq!(
+            world.detect_period(10),
+            Some(Period {
+                length: 2,
+                transient: 1
+            })
+        );
+
+        // A glider only comes back once it has gone all the way round.
+        let glider: World = ".O...\n..O..\nOOO..\n.....\n.....".parse().unwrap();
+        assert_eq!(glider.detect_period(10), None);
+        assert_eq!(
+            glider.detect_period(20),
+            Some(Period {
+                length: 20,
+                transient: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_detect_period_r_pentomino() {
+        let mut world = World::empty(64, 64)
+            .with_rule(Rule::CONWAY)
+            .with_boundary(Boundary::uniform(EdgeMode::Dead));
+        let r_pentomino: World = ".OO\nOO.\n.O.".parse().unwrap();
+        for (row, col, cell) in r_pentomino.iter() {
+            world.set(row + 30, col + 30, cell).unwrap();
+        }
+
+        // Boxed in by dead edges it settles far sooner than on an open
+        // plane, into still lifes and blinkers.
+        let period = world.detect_period(5000).unwrap();
+        assert_eq!(
+            period,
+            Period {
+                length: 2,
+                transient: 319
+            }
+        );
+
+        let mut settled = world.clone();
+        settled.evolve_n_until(319, StopCondition::Never);
+        assert_eq!(
+            settled.detect_period(2).map(|period| period.transient),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_detect_cycle() {
+        let cycle = |transient, period| CycleResult::Cycle { transient, period };
+
+        let blinker: World = ".....\n..O..\n..O..\n..O..\n.....".parse().unwrap();
+        assert_eq!(blinker.detect_cycle(10), cycle(0, 2));
+        assert_eq!(blinker.detect_cycle(1), CycleResult::NoCycleWithin(1));
+
+        let block: World = "....\n.OO.\n.OO.\n....".parse().unwrap();
+        assert_eq!(block.detect_cycle(10), cycle(0, 1));
+        assert_eq!(World::empty(8, 8).detect_cycle(1), cycle(0, 1));
+
+        let pulsar: World = [
+            ".................",
+            ".................",
+            "....OOO...OOO....",
+            ".................",
+            "..O....O.O....O..",
+            "..O....O.O....O..",
+            "..O....O.O....O..",
+            "....OOO...OOO....",
+            ".................",
+            "....OOO...OOO....",
+            "..O....O.O....O..",
+            "..O....O.O....O..",
+            "..O....O.O....O..",
+            ".................",
+            "....OOO...OOO....",
+            ".................",
+            ".................",
+        ]
+        .join("\n")
+        .parse()
+        .unwrap();
+        assert_eq!(pulsar.detect_cycle(10), cycle(0, 3));
+
+        let mut glider = World::empty(10, 10);
+        for (row, col) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] {
+            glider.set(row, col, Alive).unwrap();
+        }
+        assert_eq!(glider.detect_cycle(40), cycle(0, 40));
+        assert_eq!(glider.detect_cycle(39), CycleResult::NoCycleWithin(39));
+    }
+
+    #[test]
+    fn test_detect_cycle_matches_detect_period() {
+        let world: World = ".......\n..O....\n..O....\n..O....\n.......\n.....O."
+            .parse()
+            .unwrap();
+        assert_eq!(world.detect_cycle(10), cycle_of(world.detect_period(10)));
+        assert_eq!(world.detect_cycle(2), CycleResult::NoCycleWithin(2));
+        assert_eq!(world.detect_cycle(3), cycle_of(world.detect_period(3)));
+
+        let soup = World::new_with_seed(12, 12, 5);
+        assert_eq!(soup.detect_cycle(500), cycle_of(soup.detect_period(500)));
+    }
+
+    fn cycle_of(period: Option<Period>) -> CycleResult {
+        let period = period.unwrap();
+        CycleResult::Cycle {
+            transient: period.transient,
+            period: period.length,
+        }
+    }
+
+    #[test]
+    fn test_is_stable() {
+        let block: World = "....\n.OO.\n.OO.\n....".parse().unwrap();
+        assert!(block.is_stable());
+        assert!(World::empty(4, 4).is_stable());
+
+        let blinker: World = ".....\n..O..\n..O..\n..O..\n.....".parse().unwrap();
+        assert!(!blinker.is_stable());
+    }
+
+    #[test]
+    fn test_run_until_stable() {
+        let stabilized = |generations, period| StabilizationResult::Stabilized {
+            generations,
+            period,
+        };
+
+        // A loose cell dies first.
+        let mut world: World = ".......\n..O....\n..O....\n..O....\n.......\n.....O."
+            .parse()
+            .unwrap();
+        assert_eq!(world.clone().run_until_stable(10, 2), stabilized(1, 2));
+        assert_eq!(
+            world.clone().run_until_stable(10, 1),
+            StabilizationResult::NotStabilizedWithin(10)
+        );
+        assert_eq!(
+            world.run_until_stable(2, 2),
+            StabilizationResult::NotStabilizedWithin(2)
+        );
+        assert_eq!(world.generation(), 2);
+
+        let mut world: World = "....\n.OO.\n.OO.\n....".parse().unwrap();
+        assert_eq!(world.run_until_stable(10, 2), stabilized(0, 1));
+        assert_eq!(world.generation(), 1);
+
+        // A glider on a torus only comes back once it has gone all the way
+        // round.
+        let mut glider: World = ".O...\n..O..\nOOO..\n.....\n.....".parse().unwrap();
+        assert_eq!(
+            glider.clone().run_until_stable(100, 19),
+            StabilizationResult::NotStabilizedWithin(100)
+        );
+        assert_eq!(glider.run_until_stable(100, 20), stabilized(0, 20));
+    }
+
+    #[test]
+    fn test_run_until_stable_dies_out() {
+        // The two cells die in the first generation.
+        let mut world: World = ".....\n.O...\n...O.\n.....".parse().unwrap();
+        assert_eq!(
+            world.run_until_stable(10, 4),
+            StabilizationResult::Stabilized {
+                generations: 1,
+                period: 1
+            }
+        );
+        assert!(world.is_empty());
+        assert_eq!(world.generation(), 1);
+
+        // Brian's Brain leaves dying cells behind for a generation.
+        let mut world: World = "......\n.O....\n......".parse().unwrap();
+        world.set_rule(Rule::BRIANS_BRAIN);
+        assert_eq!(
+            world.run_until_stable(10, 4),
+            StabilizationResult::Stabilized {
+                generations: 2,
+                period: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_run_until_stable_matches_detect_period() {
+        for seed in 0..5 {
+            let world = World::new_with_seed(16, 16, seed);
+            let period = world.detect_period(2000).unwrap();
+            assert_eq!(
+                world.clone().run_until_stable(2000, period.length),
+                StabilizationResult::Stabilized {
+                    generations: period.transient,
+                    period: period.length
+                }
+            );
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use std::{
+    error::Error,
+    fmt,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    error::{LimitError, LimitErrorKind},
+    Delay, DynamicImage, Frame, GrayImage, Rgba, RgbaImage,
+};
+
+use super::{world_parts::Cell, World, WorldError};
+
+#[derive(Debug)]
+pub enum ImageError {
+    Image(image::ImageError),
+    ZeroScale,
+    World(WorldError),
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImageError::Image(e) => write!(f, "{}", e),
+            ImageError::ZeroScale => write!(f, "scale must be at least 1"),
+            ImageError::World(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for ImageError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ImageError::Image(e) => Some(e),
+            ImageError::ZeroScale => None,
+            ImageError::World(e) => Some(e),
+        }
+    }
+}
+
+impl From<image::ImageError> for ImageError {
+    fn from(e: image::ImageError) -> Self {
+        ImageError::Image(e)
+    }
+}
+
+/// Colors used when drawing a world as an image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RenderStyle {
+    pub alive: [u8; 4],
+    pub dead: [u8; 4],
+}
+
+impl Default for RenderStyle {
+    /// Black cells on a white background.
+    fn default() -> Self {
+        Self {
+            alive: [0, 0, 0, 255],
+            dead: [255, 255, 255, 255],
+        }
+    }
+}
+
+impl World {
+    /// Seeds a world from an image file. Every `scale × scale` block of pixels
+    /// becomes one cell, which is alive when the block's average luminance is
+    /// below `threshold`.
+    pub fn from_image(
+        path: impl AsRef<Path>,
+        threshold: u8,
+        scale: u32,
+    ) -> Result<Self, ImageError> {
+        Self::from_dynamic_image(&image::open(path)?, threshold, scale)
+    }
+
+    /// Same as `from_image`, for an encoded image that's already in memory.
+    pub fn from_image_bytes(bytes: &[u8], threshold: u8, scale: u32) -> Result<Self, ImageError> {
+        Self::from_dynamic_image(&image::load_from_memory(bytes)?, threshold, scale)
+    }
+
+    fn from_dynamic_image(
+        image: &DynamicImage,
+        threshold: u8,
+        scale: u32,
+    ) -> Result<Self, ImageError> {
+        if scale == 0 {
+            return Err(ImageError::ZeroScale);
+        }
+
+        let luma = image.to_luma8();
+        let width = luma.width().div_ceil(scale);
+        let height = luma.height().div_ceil(scale);
+
+        let mut cells = Vec::with_capacity(width as usize * height as usize);
+        for row in 0..height {
+            for col in 0..width {
+                let alive =
+                    block_luminance(&luma, row * scale, col * scale, scale) < threshold as u32;
+                cells.push(Cell::from(alive));
+            }
+        }
+
+        World::from_cells(width, height, cells).map_err(ImageError::World)
+    }
+}
+
+impl World {
+    /// Draws every cell as a `cell_size × cell_size` block using the default
+    /// `RenderStyle`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cell_size` is zero, or if the image would be more than
+    /// `u32::MAX` pixels wide or high.
+    pub fn to_image(&self, cell_size: u32) -> RgbaImage {
+        self.to_image_with(cell_size, &RenderStyle::default())
+    }
+
+    /// Same as `to_image`, with custom colors.
+    pub fn to_image_with(&self, cell_size: u32, style: &RenderStyle) -> RgbaImage {
+        assert!(cell_size > 0, "cell_size must be at least 1");
+        let (width, height) = self
+            .image_size(cell_size)
+            .expect("the image is too large, see `World::to_image`");
+
+        RgbaImage::from_fn(width, height, |x, y| {
+            let idx = self.get_index(y / cell_size, x / cell_size);
+            Rgba(if self.grid[idx].is_alive() {
+                style.alive
+            } else {
+                style.dead
+            })
+        })
+    }
+
+    /// Writes the world to a PNG file, see `to_image`. An image too large to
+    /// draw is an error rather than a panic.
+    pub fn save_png(&self, path: impl AsRef<Path>, cell_size: u32) -> Result<(), ImageError> {
+        self.check_image_size(cell_size)?;
+        self.to_image(cell_size)
+            .save_with_format(path, image::ImageFormat::Png)?;
+        Ok(())
+    }
+}
+
+impl World {
+    /// Records an evolution run as an animated GIF, see `write_gif`.
+    pub fn record_gif(
+        &self,
+        path: impl AsRef<Path>,
+        generations: u32,
+        frame_delay_ms: u32,
+        cell_size: u32,
+    ) -> Result<u32, ImageError> {
+        let file = File::create(path).map_err(image::ImageError::IoError)?;
+        self.write_gif(BufWriter::new(file), generations, frame_delay_ms, cell_size)
+    }
+
+    /// Writes the current state followed by up to `generations` evolved states
+    /// as frames of a looping GIF, and returns the number of frames written.
+    /// The world itself is left untouched. Recording stops early once a
+    /// generation no longer changes the grid.
+    pub fn write_gif<W: Write>(
+        &self,
+        writer: W,
+        generations: u32,
+        frame_delay_ms: u32,
+        cell_size: u32,
+    ) -> Result<u32, ImageError> {
+        self.check_image_size(cell_size)?;
+        let mut encoder = GifEncoder::new(writer);
+        encoder.set_repeat(Repeat::Infinite)?;
+        let delay = Delay::from_numer_denom_ms(frame_delay_ms, 1);
+
+        let mut world = self.clone();
+        encoder.encode_frame(Frame::from_parts(world.to_image(cell_size), 0, 0, delay))?;
+        let mut frames = 1;
+
+        for _ in 0..generations {
+            let previous = world.grid.clone();
+            world.evolve();
+            if world.grid == previous {
+                break;
+            }
+
+            encoder.encode_frame(Frame::from_parts(world.to_image(cell_size), 0, 0, delay))?;
+            frames += 1;
+        }
+
+        Ok(frames)
+    }
+}
+
+impl World {
+    // The width and height of the world drawn at `cell_size`, if they fit in
+    // a `u32`.
+    fn image_size(&self, cell_size: u32) -> Option<(u32, u32)> {
+        Some((
+            self.width.checked_mul(cell_size)?,
+            self.height.checked_mul(cell_size)?,
+        ))
+    }
+
+    fn check_image_size(&self, cell_size: u32) -> Result<(), ImageError> {
+        self.image_size(cell_size).map(|_| ()).ok_or_else(|| {
+            let kind = LimitErrorKind::DimensionError;
+            ImageError::Image(image::ImageError::Limits(LimitError::from_kind(kind)))
+        })
+    }
+}
+
+// Blocks at the right and bottom edges can be smaller than `scale`, so the
+// average only covers the pixels that actually exist.
+fn block_luminance(luma: &GrayImage, top: u32, left: u32, scale: u32) -> u32 {
+    let bottom = (top + scale).min(luma.height());
+    let right = (left + scale).min(luma.width());
+
+    let mut sum = 0u64;
+    for y in top..bottom {
+        for x in left..right {
+            sum += luma.get_pixel(x, y).0[0] as u64;
+        }
+    }
+
+    let count = (bottom - top) as u64 * (right - left) as u64;
+    (sum / count) as u32
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use image::{codecs::gif::GifDecoder, AnimationDecoder, GrayImage, ImageFormat, Luma, Rgba};
+
+    use super::{ImageError, RenderStyle};
+    use crate::game::{world_parts::Cell::*, World};
+
+    fn encode(image: &GrayImage) -> Vec<u8> {
+        let mut bytes = Cursor::new(Vec::new());
+        image.write_to(&mut bytes, ImageFormat::Png).unwrap();
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn test_from_image_bytes() {
+        let mut image = GrayImage::from_pixel(3, 2, Luma([255]));
+        image.put_pixel(0, 0, Luma([0]));
+        image.put_pixel(2, 1, Luma([100]));
+
+        let world = World::from_image_bytes(&encode(&image), 128, 1).unwrap();
+        assert_eq!((world.width(), world.height()), (3, 2));
+        assert_eq!(world.grid, vec![Alive, Dead, Dead, Dead, Dead, Alive]);
+
+        let world = World::from_image_bytes(&encode(&image), 50, 1).unwrap();
+        assert_eq!(world.grid, vec![Alive, Dead, Dead, Dead, Dead, Dead]);
+    }
+
+    #[test]
+    fn test_from_image_bytes_scaled() {
+        // A 5x5 image in 2x2 blocks gives a 3x3 world with partial edge blocks.
+        let mut image = GrayImage::from_pixel(5, 5, Luma([255]));
+        for (x, y) in [(0, 0), (1, 0), (0, 1), (1, 1), (4, 4)] {
+            image.put_pixel(x, y, Luma([0]));
+        }
+        image.put_pixel(2, 2, Luma([0]));
+
+        let world = World::from_image_bytes(&encode(&image), 128, 2).unwrap();
+        assert_eq!((world.width(), world.height()), (3, 3));
+        assert_eq!(
+            world.grid,
+            vec![Alive, Dead, Dead, Dead, Dead, Dead, Dead, Dead, Alive]
+        );
+    }
+
+    #[test]
+    fn test_from_image_errors() {
+        let result = World::from_image_bytes(b"not an image", 128, 1);
+        assert!(matches!(result, Err(ImageError::Image(_))));
+
+        let image = GrayImage::from_pixel(2, 2, Luma([0]));
+        let result = World::from_image_bytes(&encode(&image), 128, 0);
+        assert!(matches!(result, Err(ImageError::ZeroScale)));
+    }
+
+    #[test]
+    fn test_to_image() {
+        let world: World = "O..\n.OO".parse().unwrap();
+        let image = world.to_image(1);
+        assert_eq!(image.dimensions(), (3, 2));
+        assert_eq!(image.get_pixel(0, 0), &Rgba([0, 0, 0, 255]));
+        assert_eq!(image.get_pixel(1, 0), &Rgba([255, 255, 255, 255]));
+        assert_eq!(image.get_pixel(2, 1), &Rgba([0, 0, 0, 255]));
+
+        let style = RenderStyle {
+            alive: [255, 0, 0, 255],
+            dead: [0, 0, 0, 0],
+        };
+        let image = world.to_image_with(4, &style);
+        assert_eq!(image.dimensions(), (12, 8));
+        assert_eq!(image.get_pixel(3, 3), &Rgba([255, 0, 0, 255]));
+        assert_eq!(image.get_pixel(4, 3), &Rgba([0, 0, 0, 0]));
+        assert_eq!(image.get_pixel(11, 7), &Rgba([255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_image_too_large() {
+        let world = World::empty(3, 2);
+        let result = world.write_gif(Vec::new(), 1, 100, u32::MAX / 2);
+        assert!(matches!(
+            result,
+            Err(ImageError::Image(image::ImageError::Limits(_)))
+        ));
+        assert!(std::panic::catch_unwind(|| world.to_image(u32::MAX / 2)).is_err());
+    }
+
+    #[test]
+    fn test_image_round_trip() {
+        let world = World::new_with_seed(30, 20, 8);
+        let mut bytes = Cursor::new(Vec::new());
+        world
+            .to_image(3)
+            .write_to(&mut bytes, ImageFormat::Png)
+            .unwrap();
+
+        let loaded = World::from_image_bytes(bytes.get_ref(), 128, 3).unwrap();
+        assert_eq!(loaded, world);
+    }
+
+    #[test]
+    fn test_write_gif() {
+        let world: World = ".....\n..O..\n..O..\n..O..\n.....".parse().unwrap();
+        let mut bytes = Vec::new();
+        let frames = world.write_gif(&mut bytes, 6, 100, 2).unwrap();
+        assert_eq!(frames, 7);
+
+        let decoded = GifDecoder::new(Cursor::new(bytes)).unwrap();
+        let decoded = decoded.into_frames().collect_frames().unwrap();
+        assert_eq!(decoded.len(), 7);
+        assert_eq!(decoded[0].buffer().dimensions(), (10, 10));
+        assert_eq!(decoded[1].delay().numer_denom_ms(), (100, 1));
+    }
+
+    #[test]
+    fn test_write_gif_stops_when_stable() {
+        // A block never changes, so only the initial frame is written.
+        let world: World = "....\n.OO.\n.OO.\n....".parse().unwrap();
+        let mut bytes = Vec::new();
+        let frames = world.write_gif(&mut bytes, 50, 100, 1).unwrap();
+        assert_eq!(frames, 1);
+
+        let decoded = GifDecoder::new(Cursor::new(bytes)).unwrap();
+        assert_eq!(decoded.into_frames().count(), 1);
+    }
+}
@@ -0,0 +1,139 @@
+use super::{World, WorldError};
+
+impl World {
+    /// Evolves only the cells within the `width` by `height` rectangle whose
+    /// top left corner is at `(top, left)`. Cells outside of it stay as they
+    /// are, but still count as neighbours of the cells inside.
+    ///
+    /// As only part of the world moves on, this doesn't count as a
+    /// generation.
+    ///
+    /// Returns an error if the rectangle doesn't fit in the world.
+    pub fn evolve_region(
+        &mut self,
+        top: u32,
+        left: u32,
+        height: u32,
+        width: u32,
+    ) -> Result<(), WorldError> {
+        let bottom = top as u64 + height as u64;
+        let right = left as u64 + width as u64;
+        if bottom > self.height as u64 || right > self.width as u64 {
+            return Err(WorldError::OutOfBounds {
+                row: (bottom.max(1) - 1).min(u32::MAX as u64) as u32,
+                col: (right.max(1) - 1).min(u32::MAX as u64) as u32,
+                width: self.width,
+                height: self.height,
+            });
+        }
+
+        // Work out all new states before writing any of them, as they depend
+        // on the old ones.
+        let mut cells = Vec::with_capacity(width as usize * height as usize);
+        for row in top..top + height {
+            for col in left..left + width {
+                let n = self.get_num_alive_neighbours(row, col);
+                cells.push(self.grid[self.get_index(row, col)].set_state(n, &self.rule));
+            }
+        }
+
+        // Cells within the region age and heat up the way `evolve` has them.
+        let mut cells = cells.into_iter();
+        let mut changed = Vec::new();
+        for row in top..top + height {
+            for col in left..left + width {
+                let idx = self.get_index(row, col);
+                let (old, new) = (self.grid[idx], cells.next().unwrap());
+                if old != new {
+                    self.replace_in_population(old, new);
+                    self.grid[idx] = new;
+                    changed.push(idx);
+                }
+                if let Some(ages) = &mut self.ages {
+                    ages[idx] = if old.is_alive() && new.is_alive() {
+                        ages[idx].saturating_add(1)
+                    } else {
+                        0
+                    };
+                }
+            }
+        }
+        self.changed = None;
+        self.record_heat_at(&changed);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{Rule, World, WorldError};
+
+    #[test]
+    fn test_evolve_region_whole_world() {
+        for rule in [Rule::CONWAY, Rule::BRIANS_BRAIN, Rule::BUGS] {
+            let mut world = World::new_with_seed(40, 30, 2).with_rule(rule);
+            let mut expected = world.clone();
+            for _ in 0..5 {
+                world.evolve_region(0, 0, 30, 40).unwrap();
+                expected.evolve();
+                assert_eq!(world, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_evolve_region_ages_and_heat() {
+        let mut world = World::new_with_seed(40, 30, 2);
+        world.enable_ages();
+        world.enable_heatmap();
+        let mut expected = world.clone();
+        for _ in 0..5 {
+            world.evolve_region(0, 0, 30, 40).unwrap();
+            expected.evolve();
+        }
+        assert_eq!(world.heatmap(), expected.heatmap());
+        for (row, col, _) in world.iter() {
+            assert_eq!(world.age(row, col), expected.age(row, col));
+        }
+        assert!(world.max_age().unwrap() > 0);
+
+        // Only the cells within the region age.
+        let mut world: World = "OO......\nOO......\n........\n........\n.....OO.\n.....OO."
+            .parse()
+            .unwrap();
+        world.enable_ages();
+        world.evolve_region(0, 0, 2, 3).unwrap();
+        assert_eq!(world.age(0, 0), Some(1));
+        assert_eq!(world.age(4, 5), Some(0));
+    }
+
+    #[test]
+    fn test_evolve_region_freezes_outside() {
+        // Only the middle of the blinker is evolved, its ends stay put but
+        // still count.
+        let mut world: World = ".....\n..O..\n..O..\n..O..\n.....".parse().unwrap();
+        world.evolve_region(2, 0, 1, 5).unwrap();
+        let expected: World = ".....\n..O..\n.OOO.\n..O..\n.....".parse().unwrap();
+        assert_eq!(world, expected);
+
+        world.evolve_region(0, 0, 0, 0).unwrap();
+        assert_eq!(world, expected);
+    }
+
+    #[test]
+    fn test_evolve_region_out_of_bounds() {
+        let mut world = World::new_with_seed(10, 8, 1);
+        let before = world.clone();
+        assert_eq!(
+            world.evolve_region(5, 2, 4, 3),
+            Err(WorldError::OutOfBounds {
+                row: 8,
+                col: 4,
+                width: 10,
+                height: 8
+            })
+        );
+        assert!(world.evolve_region(0, u32::MAX, 1, 2).is_err());
+        assert_eq!(world, before);
+    }
+}
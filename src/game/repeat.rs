@@ -0,0 +1,155 @@
+use std::ops::ControlFlow;
+
+use super::World;
+
+/// Whether a generation changed anything, see `World::evolve_checked`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepStatus {
+    Changed,
+    Unchanged,
+}
+
+/// When `World::evolve_n_until` stops before it has run every generation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StopCondition {
+    /// Runs every generation.
+    Never,
+    /// Stops once a generation changes nothing, as none after it would.
+    #[default]
+    WhenStable,
+}
+
+impl World {
+    /// Same as `evolve`, but also tells whether any cell changed. Once a
+    /// world is unchanged it stays that way, so an animation can stop there.
+    pub fn evolve_checked(&mut self) -> StepStatus {
+        self.evolve();
+        // After evolving the scratch buffer holds the previous generation.
+        if self.grid == self.scratch {
+            StepStatus::Unchanged
+        } else {
+            StepStatus::Changed
+        }
+    }
+
+    /// Advances the world by up to `n` generations, stopping early once a
+    /// generation changes nothing, as none after it would either. The cells
+    /// end up as after calling `evolve` `n` times, but `generation` and the
+    /// ages of the cells only count the generations actually computed. Use
+    /// `evolve_n_until` to run all of them.
+    ///
+    /// Returns the number of generations actually computed.
+    pub fn evolve_n(&mut self, n: u64) -> u64 {
+        self.evolve_n_until(n, StopCondition::WhenStable)
+    }
+
+    /// Same as `evolve_n`, with the choice of whether to stop early.
+    pub fn evolve_n_until(&mut self, n: u64, stop: StopCondition) -> u64 {
+        if stop == StopCondition::Never {
+            for _ in 0..n {
+                self.evolve();
+            }
+            return n;
+        }
+        self.evolve_n_with(n, |_, _| ControlFlow::Continue(()))
+    }
+
+    /// Same as `evolve_n`, calling `f` with the number of generations so far
+    /// and the world after every one of them. Returning `ControlFlow::Break`
+    /// stops the run right there.
+    pub fn evolve_n_with<F>(&mut self, n: u64, mut f: F) -> u64
+    where
+        F: FnMut(u64, &World) -> ControlFlow<()>,
+    {
+        for generation in 1..=n {
+            let settled = self.evolve_checked() == StepStatus::Unchanged;
+            if f(generation, self).is_break() || settled {
+                return generation;
+            }
+        }
+        n
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::ops::ControlFlow;
+
+    use super::{StepStatus, StopCondition};
+    use crate::game::{Rule, World};
+
+    #[test]
+    fn test_evolve_n_matches_evolve() {
+        let mut world = World::new_with_seed(30, 20, 4).with_rule(Rule::BRIANS_BRAIN);
+        let mut expected = world.clone();
+        for _ in 0..40 {
+            expected.evolve();
+        }
+        assert_eq!(world.evolve_n(40), 40);
+        assert_eq!(world, expected);
+
+        assert_eq!(world.evolve_n(0), 0);
+        assert_eq!(world, expected);
+    }
+
+    #[test]
+    fn test_evolve_n_stops_at_fixed_point() {
+        // A block never changes, so the first generation already settles.
+        let mut world: World = "....\n.OO.\n.OO.\n....".parse().unwrap();
+        let block = world.clone();
+        assert_eq!(world.evolve_n(1_000_000), 1);
+        assert_eq!(world, block);
+
+        // A blinker keeps changing.
+        let mut world: World = ".....\n..O..\n..O..\n..O..\n.....".parse().unwrap();
+        assert_eq!(world.evolve_n(10), 10);
+    }
+
+    #[test]
+    fn test_evolve_checked() {
+        let mut world: World = "......\n.OO...\n.OO...\n......\n......".parse().unwrap();
+        assert_eq!(world.evolve_checked(), StepStatus::Unchanged);
+
+        // A loose cell dies first, after which only the block is left.
+        let mut world: World = "......\n.OO...\n.OO...\n......\n....O.".parse().unwrap();
+        assert_eq!(world.evolve_checked(), StepStatus::Changed);
+        assert_eq!(world.evolve_checked(), StepStatus::Unchanged);
+        assert_eq!(world.evolve_checked(), StepStatus::Unchanged);
+    }
+
+    #[test]
+    fn test_evolve_n_until() {
+        // A soup settles long before a million generations are up.
+        let mut world = World::new_with_seed(20, 20, 1);
+        let ran = world.evolve_n_until(1_000_000, StopCondition::WhenStable);
+        assert_eq!(ran, 104);
+        let settled = world.clone();
+        assert_eq!(world.evolve_checked(), StepStatus::Unchanged);
+        assert_eq!(world, settled);
+
+        let mut world: World = "....\n.OO.\n.OO.\n....".parse().unwrap();
+        assert_eq!(world.evolve_n_until(25, StopCondition::Never), 25);
+    }
+
+    #[test]
+    fn test_evolve_n_with() {
+        let mut world = World::new_with_seed(30, 20, 4);
+        let mut seen = Vec::new();
+        let ran = world.evolve_n_with(100, |generation, world| {
+            seen.push((generation, world.grid.clone()));
+            if generation == 5 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(ran, 5);
+
+        let mut expected = World::new_with_seed(30, 20, 4);
+        for (generation, grid) in seen {
+            expected.evolve();
+            assert_eq!(grid, expected.grid, "generation {}", generation);
+        }
+        assert_eq!(world, expected);
+    }
+}
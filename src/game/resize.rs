@@ -0,0 +1,203 @@
+use super::{check_dimensions, world_parts::Cell, Topology, World, WorldError};
+
+/// Which part of a world stays in place when it is resized, see
+/// `World::resize`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Anchor {
+    #[default]
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    // How far the old cells move down and to the right, with -1 for the top
+    // or left, 0 for the middle and 1 for the bottom or right.
+    fn sides(self) -> (i64, i64) {
+        match self {
+            Anchor::TopLeft => (-1, -1),
+            Anchor::Top => (-1, 0),
+            Anchor::TopRight => (-1, 1),
+            Anchor::Left => (0, -1),
+            Anchor::Center => (0, 0),
+            Anchor::Right => (0, 1),
+            Anchor::BottomLeft => (1, -1),
+            Anchor::Bottom => (1, 0),
+            Anchor::BottomRight => (1, 1),
+        }
+    }
+}
+
+// How far the cells of an axis move when its length changes from `old` to
+// `new`. Centering the other way round moves them straight back.
+fn offset(side: i64, old: u32, new: u32) -> i64 {
+    let change = new as i64 - old as i64;
+    match side {
+        -1 => 0,
+        0 => change / 2,
+        _ => change,
+    }
+}
+
+impl World {
+    /// Changes the size of the world, keeping the cells in place at the
+    /// part `anchor` names. Cells that no longer fit are dropped, and new
+    /// ones start out dead. Heatmap counts and ages, if enabled, start over
+    /// at 0.
+    ///
+    /// Returns an error for the same dimensions as `World::new_random`, or an
+    /// odd height for a hexagonal world.
+    pub fn resize(&mut self, width: u32, height: u32, anchor: Anchor) -> Result<(), WorldError> {
+        check_dimensions(width, height)?;
+        if self.topology == Topology::Hex && !height.is_multiple_of(2) {
+            return Err(WorldError::OddHexHeight(height));
+        }
+
+        let (rows, cols) = anchor.sides();
+        let mut d_row = offset(rows, self.height, height);
+        let d_col = offset(cols, self.width, width);
+        if self.topology == Topology::Hex {
+            // Shifted rows have to stay shifted.
+            d_row -= d_row.rem_euclid(2);
+        }
+
+        // The columns that survive, in the old and the new grid.
+        let first = (-d_col).max(0);
+        let last = (self.width as i64).min(width as i64 - d_col);
+        let mut grid = vec![Cell::Dead; width as usize * height as usize];
+        let mut population = 0;
+        if first < last {
+            let len = (last - first) as usize;
+            for row in 0..self.height as i64 {
+                let new_row = row + d_row;
+                if !(0..height as i64).contains(&new_row) {
+                    continue;
+                }
+
+                let from = self.get_index(row as u32, first as u32);
+                let to = new_row as usize * width as usize + (first + d_col) as usize;
+                let cells = &self.grid[from..from + len];
+                population += cells.iter().filter(|cell| cell.is_alive()).count();
+                grid[to..to + len].copy_from_slice(cells);
+            }
+        }
+
+        self.grid = grid;
+        self.scratch = Vec::new();
+        self.changed = None;
+        self.population = Some(population);
+        self.width = width;
+        self.height = height;
+        self.origin = (self.origin.0 - d_row, self.origin.1 - d_col);
+        if self.heatmap.take().is_some() {
+            self.enable_heatmap();
+        }
+        self.reset_ages();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Anchor;
+    use crate::game::{Topology, World, WorldError};
+    use crate::patterns;
+
+    // A pattern with no symmetry, so every cell has to end up in its place.
+    fn pattern() -> World {
+        "OO.\n..O\nO..\n.OO".parse().unwrap()
+    }
+
+    #[test]
+    fn test_resize_grow() {
+        let mut world = pattern();
+        world.resize(5, 6, Anchor::TopLeft).unwrap();
+        let expected: World = "OO...\n..O..\nO....\n.OO..\n.....\n.....".parse().unwrap();
+        assert_eq!(world.grid, expected.grid);
+        assert_eq!(world.population(), 6);
+        assert_eq!(world.origin(), (0, 0));
+
+        let mut world = pattern();
+        world.resize(7, 6, Anchor::Center).unwrap();
+        let expected: World = ".......\n..OO...\n....O..\n..O....\n...OO..\n......."
+            .parse()
+            .unwrap();
+        assert_eq!(world.grid, expected.grid);
+        assert_eq!(world.origin(), (-1, -2));
+
+        let mut world = pattern();
+        world.resize(4, 5, Anchor::BottomRight).unwrap();
+        let expected: World = "....\n.OO.\n...O\n.O..\n..OO".parse().unwrap();
+        assert_eq!(world.grid, expected.grid);
+    }
+
+    #[test]
+    fn test_resize_shrink() {
+        let mut world = pattern();
+        world.resize(2, 3, Anchor::TopLeft).unwrap();
+        let expected: World = "OO\n..\nO.".parse().unwrap();
+        assert_eq!(world.grid, expected.grid);
+        assert_eq!(world.population(), 3);
+
+        let mut world = pattern();
+        world.resize(2, 2, Anchor::BottomRight).unwrap();
+        let expected: World = "..\nOO".parse().unwrap();
+        assert_eq!(world.grid, expected.grid);
+        assert_eq!(world.origin(), (2, 1));
+
+        // Growing and shrinking round the center gives the same world back.
+        let mut world = pattern();
+        world.resize(8, 9, Anchor::Center).unwrap();
+        world.resize(3, 4, Anchor::Center).unwrap();
+        assert_eq!(world, pattern());
+    }
+
+    #[test]
+    fn test_resize_evolves() {
+        // A resized world evolves the same as one built at the new size.
+        let glider = patterns::glider();
+        let mut world = glider.clone();
+        world.resize(9, 9, Anchor::Center).unwrap();
+        let mut expected: World = ".........\n.........\n.........\n....O....\n.....O...\n...OOO...\n.........\n.........\n.........".parse().unwrap();
+        assert_eq!(world.grid, expected.grid);
+        for _ in 0..8 {
+            world.evolve();
+            expected.evolve();
+        }
+        assert_eq!(world.grid, expected.grid);
+        assert_eq!(world.population(), 5);
+    }
+
+    #[test]
+    fn test_resize_errors() {
+        let mut world = pattern();
+        assert_eq!(
+            world.resize(0, 3, Anchor::TopLeft),
+            Err(WorldError::ZeroDimension {
+                width: 0,
+                height: 3
+            })
+        );
+
+        world.set_topology(Topology::Hex).unwrap();
+        assert_eq!(
+            world.resize(3, 5, Anchor::TopLeft),
+            Err(WorldError::OddHexHeight(5))
+        );
+        assert_eq!(world, {
+            let mut world = pattern();
+            world.set_topology(Topology::Hex).unwrap();
+            world
+        });
+
+        // Shifted rows stay shifted.
+        world.resize(3, 6, Anchor::Center).unwrap();
+        assert_eq!(world.origin().0 % 2, 0);
+    }
+}
@@ -0,0 +1,759 @@
+use std::{error::Error, fmt, ops::RangeInclusive, str::FromStr};
+
+// Enough bits for every neighbour count up to a radius of `MAX_RADIUS`.
+type Counts = [u64; 8];
+
+/// The birth and survival conditions of a Life-like automaton: a dead cell
+/// comes alive when its number of live neighbours is one of the birth counts,
+/// and a live cell stays alive when the count is one of the survival counts.
+///
+/// Generations rules have more than 2 states: a live cell that doesn't
+/// survive decays through `states - 2` dying states before it is dead.
+///
+/// Larger-than-Life rules count the neighbours in a square of `radius` cells
+/// around each cell instead of only the 8 adjacent ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rule {
+    // Bit n is set when a count of n neighbours applies.
+    birth: Counts,
+    survival: Counts,
+    states: u8,
+    radius: u8,
+}
+
+impl Rule {
+    /// The largest radius a Larger-than-Life rule can have.
+    pub const MAX_RADIUS: u8 = 10;
+
+    /// Creates a rule from neighbour counts in `0..=8`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a count is above 8.
+    pub const fn new(birth: &[u8], survival: &[u8]) -> Self {
+        Self {
+            birth: mask(birth),
+            survival: mask(survival),
+            states: 2,
+            radius: 1,
+        }
+    }
+
+    /// Creates a Generations rule with `states` cell states in total, where
+    /// 2 states is an ordinary Life-like rule.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a count is above 8 or `states` is below 2.
+    pub const fn generations(birth: &[u8], survival: &[u8], states: u8) -> Self {
+        assert!(states >= 2, "a rule needs at least 2 states");
+        Self {
+            birth: mask(birth),
+            survival: mask(survival),
+            states,
+            radius: 1,
+        }
+    }
+
+    /// Creates a rule for a hexagonal world, where cells have 6 neighbours,
+    /// like `B2/S34`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a count is above 6.
+    pub const fn hex(birth: &[u8], survival: &[u8]) -> Self {
+        let mut i = 0;
+        while i < birth.len() || i < survival.len() {
+            assert!(
+                (i >= birth.len() || birth[i] <= 6) && (i >= survival.len() || survival[i] <= 6),
+                "hexagonal neighbour counts range from 0 to 6"
+            );
+            i += 1;
+        }
+        Self::new(birth, survival)
+    }
+
+    /// Creates a Larger-than-Life rule, where the neighbours of a cell are
+    /// all other cells in the `(2 * radius + 1)²` square centred on it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radius` is 0 or above `MAX_RADIUS`, or if a range goes past
+    /// the number of neighbours.
+    pub const fn larger_than_life(
+        radius: u8,
+        birth: RangeInclusive<u32>,
+        survival: RangeInclusive<u32>,
+    ) -> Self {
+        assert!(
+            radius >= 1 && radius <= Self::MAX_RADIUS,
+            "radius has to be within 1 to 10"
+        );
+        Self {
+            birth: range_mask(radius, &birth),
+            survival: range_mask(radius, &survival),
+            states: 2,
+            radius,
+        }
+    }
+
+    /// Conway's Game of Life, `B3/S23`.
+    pub const CONWAY: Rule = Rule::new(&[3], &[2, 3]);
+
+    /// HighLife, `B36/S23`. Like Conway's, but also has a small replicator.
+    pub const HIGHLIFE: Rule = Rule::new(&[3, 6], &[2, 3]);
+
+    /// Seeds, `B2/S`. No cell survives, yet patterns tend to explode.
+    pub const SEEDS: Rule = Rule::new(&[2], &[]);
+
+    /// Day & Night, `B3678/S34678`. Live and dead cells behave the same way,
+    /// so inverting a pattern inverts its evolution.
+    pub const DAY_AND_NIGHT: Rule = Rule::new(&[3, 6, 7, 8], &[3, 4, 6, 7, 8]);
+
+    /// Life without Death, `B3/S012345678`. Live cells never die.
+    pub const LIFE_WITHOUT_DEATH: Rule = Rule::new(&[3], &[0, 1, 2, 3, 4, 5, 6, 7, 8]);
+
+    /// Maze, `B3/S12345`. Grows maze-like corridors.
+    pub const MAZE: Rule = Rule::new(&[3], &[1, 2, 3, 4, 5]);
+
+    /// Replicator, `B1357/S1357`. Every pattern is eventually replaced by
+    /// copies of itself.
+    pub const REPLICATOR: Rule = Rule::new(&[1, 3, 5, 7], &[1, 3, 5, 7]);
+
+    /// Brian's Brain, `B2/S/C3`. Firing cells always turn refractory and
+    /// then ready again, and a ready cell fires with exactly 2 firing
+    /// neighbours. Firing cells are `Cell::Alive`, refractory ones
+    /// `Cell::Dying(1)`.
+    pub const BRIANS_BRAIN: Rule = Rule::generations(&[2], &[], 3);
+
+    /// Bugs, `R5,C0,M1,S34..58,B34..45,NM`, a Larger-than-Life rule with
+    /// gliding blobs. The `M1` notation counts a live cell as its own
+    /// neighbour, so the survival range here is one lower.
+    pub const BUGS: Rule = Rule::larger_than_life(5, 34..=45, 33..=57);
+
+    pub fn is_birth(&self, neighbours: u32) -> bool {
+        contains(&self.birth, neighbours)
+    }
+
+    pub fn is_survival(&self, neighbours: u32) -> bool {
+        contains(&self.survival, neighbours)
+    }
+
+    /// The number of cell states, alive and dead included.
+    pub fn states(&self) -> u8 {
+        self.states
+    }
+
+    /// How far away a cell's neighbours can be, 1 for Life-like rules.
+    pub fn radius(&self) -> u8 {
+        self.radius
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Self::CONWAY
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RuleError {
+    Malformed(String),
+    InvalidCount(char),
+    InvalidRadius(u32),
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::Malformed(rule) => write!(f, "malformed rule {:?}", rule),
+            RuleError::InvalidCount(c) => {
+                write!(f, "invalid neighbour count {:?}, expected 0 to 8", c)
+            }
+            RuleError::InvalidRadius(radius) => write!(
+                f,
+                "invalid radius {}, expected 1 to {}",
+                radius,
+                Rule::MAX_RADIUS
+            ),
+        }
+    }
+}
+
+impl Error for RuleError {}
+
+impl FromStr for Rule {
+    type Err = RuleError;
+
+    /// Parses B/S notation like `B36/S23`, or the older S/B notation like
+    /// `23/36`, ignoring case. A third part gives the number of states of a
+    /// Generations rule, as in `B2/S/C3` or `345/2/4`.
+    ///
+    /// Larger-than-Life rules use the `R5,C0,M1,S34..58,B34..45,NM` notation
+    /// instead.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with(['R', 'r']) {
+            return parse_larger_than_life(s);
+        }
+
+        let malformed = || RuleError::Malformed(s.to_string());
+        let mut parts = s.split('/');
+        let (first, second) = match (parts.next(), parts.next()) {
+            (Some(first), Some(second)) => (first, second),
+            _ => return Err(malformed()),
+        };
+        let states = match parts.next() {
+            None => 2,
+            Some(states) => strip_letter(states, 'C')
+                .unwrap_or(states)
+                .parse::<u8>()
+                .ok()
+                .filter(|&n| n >= 2)
+                .ok_or_else(malformed)?,
+        };
+        if parts.next().is_some() {
+            return Err(malformed());
+        }
+
+        let (birth, survival) = match (strip_letter(first, 'B'), strip_letter(second, 'S')) {
+            (Some(birth), Some(survival)) => (birth, survival),
+            _ => match (strip_letter(first, 'S'), strip_letter(second, 'B')) {
+                (Some(survival), Some(birth)) => (birth, survival),
+                // Without letters the survival counts come first.
+                _ => (second, first),
+            },
+        };
+
+        Ok(Self {
+            birth: parse_counts(birth)?,
+            survival: parse_counts(survival)?,
+            states,
+            radius: 1,
+        })
+    }
+}
+
+impl fmt::Display for Rule {
+    /// Writes the rule in B/S notation, e.g. `B3/S23`, followed by the number
+    /// of states for Generations rules, e.g. `B2/S/C3`. Larger-than-Life
+    /// rules are written like `R5,C0,M0,S33..57,B34..45,NM`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.radius > 1 {
+            let states = if self.states > 2 { self.states } else { 0 };
+            write!(f, "R{},C{},M0,S", self.radius, states)?;
+            write_ranges(f, &self.survival, max_count(self.radius))?;
+            write!(f, ",B")?;
+            write_ranges(f, &self.birth, max_count(self.radius))?;
+            return write!(f, ",NM");
+        }
+
+        write!(f, "B")?;
+        for n in (0..=8).filter(|&n| self.is_birth(n)) {
+            write!(f, "{}", n)?;
+        }
+        write!(f, "/S")?;
+        for n in (0..=8).filter(|&n| self.is_survival(n)) {
+            write!(f, "{}", n)?;
+        }
+        if self.states > 2 {
+            write!(f, "/C{}", self.states)?;
+        }
+        Ok(())
+    }
+}
+
+// Writes the counts as comma separated `start..end` ranges.
+fn write_ranges(f: &mut fmt::Formatter<'_>, counts: &Counts, max: u32) -> fmt::Result {
+    let mut n = 0;
+    let mut first = true;
+    while n <= max {
+        if !contains(counts, n) {
+            n += 1;
+            continue;
+        }
+
+        let start = n;
+        while n < max && contains(counts, n + 1) {
+            n += 1;
+        }
+        if !first {
+            write!(f, ",")?;
+        }
+        write!(f, "{}..{}", start, n)?;
+        first = false;
+        n += 1;
+    }
+    Ok(())
+}
+
+fn parse_larger_than_life(s: &str) -> Result<Rule, RuleError> {
+    let malformed = || RuleError::Malformed(s.to_string());
+    let number = |value: &str| value.parse::<u32>().map_err(|_| malformed());
+
+    let (mut radius, mut states, mut middle) = (None, 2, false);
+    let (mut birth, mut survival) = (Vec::new(), Vec::new());
+    // Extra ranges follow their `S` or `B` item as items of their own.
+    let mut current: Option<&mut Vec<(u32, u32)>> = None;
+
+    for item in s.split(',').map(str::trim) {
+        let mut chars = item.chars();
+        let key = chars.next().map(|c| c.to_ascii_uppercase());
+        let value = chars.as_str();
+        match key {
+            Some('R') => radius = Some(number(value)?),
+            Some('C') => states = number(value)?.max(2),
+            Some('M') => middle = number(value)? == 1,
+            Some('N') if value.eq_ignore_ascii_case("M") => {}
+            Some('S') => current = Some(&mut survival),
+            Some('B') => current = Some(&mut birth),
+            Some('0'..='9') => {}
+            _ => return Err(malformed()),
+        }
+
+        let range = match key {
+            Some('S' | 'B') if !value.is_empty() => value,
+            Some('0'..='9') => item,
+            _ => continue,
+        };
+        let (start, end) = range.split_once("..").ok_or_else(malformed)?;
+        current
+            .as_mut()
+            .ok_or_else(malformed)?
+            .push((number(start)?, number(end)?));
+    }
+
+    let radius = radius.ok_or_else(malformed)?;
+    if radius == 0 || radius > Rule::MAX_RADIUS as u32 {
+        return Err(RuleError::InvalidRadius(radius));
+    }
+    let states = u8::try_from(states).map_err(|_| malformed())?;
+
+    let mut rule = Rule {
+        birth: [0; 8],
+        survival: [0; 8],
+        states,
+        radius: radius as u8,
+    };
+    for (counts, ranges, shift) in [
+        (&mut rule.birth, birth, 0),
+        (&mut rule.survival, survival, middle as u32),
+    ] {
+        for (start, end) in ranges {
+            // With `M1` a live cell counts itself, which our counts never do.
+            if start < shift || end > max_count(rule.radius) + shift {
+                return Err(malformed());
+            }
+            for n in start - shift..=end - shift {
+                counts[n as usize / 64] |= 1 << (n % 64);
+            }
+        }
+    }
+    Ok(rule)
+}
+
+fn strip_letter(s: &str, letter: char) -> Option<&str> {
+    s.strip_prefix(letter)
+        .or_else(|| s.strip_prefix(letter.to_ascii_lowercase()))
+}
+
+fn parse_counts(counts: &str) -> Result<Counts, RuleError> {
+    let mut mask = [0; 8];
+    for c in counts.chars() {
+        match c.to_digit(10) {
+            Some(n) if n <= 8 => mask[0] |= 1 << n,
+            _ => return Err(RuleError::InvalidCount(c)),
+        }
+    }
+    Ok(mask)
+}
+
+fn contains(counts: &Counts, n: u32) -> bool {
+    (n as usize) < counts.len() * 64 && counts[n as usize / 64] & 1 << (n % 64) != 0
+}
+
+// The number of neighbours a cell has within the radius.
+const fn max_count(radius: u8) -> u32 {
+    let side = 2 * radius as u32 + 1;
+    side * side - 1
+}
+
+const fn mask(counts: &[u8]) -> Counts {
+    let mut mask = [0; 8];
+    let mut i = 0;
+    while i < counts.len() {
+        assert!(counts[i] <= 8, "neighbour counts range from 0 to 8");
+        mask[0] |= 1 << counts[i];
+        i += 1;
+    }
+    mask
+}
+
+const fn range_mask(radius: u8, range: &RangeInclusive<u32>) -> Counts {
+    let mut mask = [0; 8];
+    let mut n = *range.start();
+    while n <= *range.end() {
+        assert!(
+            n <= max_count(radius),
+            "neighbour count is larger than the neighbourhood"
+        );
+        mask[n as usize / 64] |= 1 << (n % 64);
+        n += 1;
+    }
+    mask
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Rule, RuleError};
+    use crate::game::{world_parts::Cell, World};
+
+    fn population(world: &World) -> usize {
+        (0..world.height())
+            .flat_map(|row| world.get_row(row))
+            .filter(|cell| cell.is_alive())
+            .count()
+    }
+
+    #[test]
+    fn test_rule() {
+        let rule = Rule::CONWAY;
+        let births: Vec<u32> = (0..=8).filter(|&n| rule.is_birth(n)).collect();
+        let survivals: Vec<u32> = (0..=8).filter(|&n| rule.is_survival(n)).collect();
+        assert_eq!(births, vec![3]);
+        assert_eq!(survivals, vec![2, 3]);
+        assert!(!rule.is_birth(9));
+
+        let seeds = Rule::new(&[2], &[]);
+        assert!(seeds.is_birth(2));
+        assert!((0..=8).all(|n| !seeds.is_survival(n)));
+
+        assert_eq!(Rule::default(), Rule::CONWAY);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rule_rejects_large_counts() {
+        Rule::new(&[9], &[]);
+    }
+
+    #[test]
+    fn test_parse_rule() {
+        let conway = Rule::CONWAY;
+        assert_eq!("B3/S23".parse(), Ok(conway));
+        assert_eq!("b3/s23".parse(), Ok(conway));
+        assert_eq!("S23/B3".parse(), Ok(conway));
+        assert_eq!("23/3".parse(), Ok(conway));
+
+        assert_eq!("B36/S23".parse(), Ok(Rule::new(&[3, 6], &[2, 3])));
+        assert_eq!("B2/S".parse(), Ok(Rule::new(&[2], &[])));
+        assert_eq!("/2".parse(), Ok(Rule::new(&[2], &[])));
+        assert_eq!(
+            "B3678/S34678".parse(),
+            Ok(Rule::new(&[3, 6, 7, 8], &[3, 4, 6, 7, 8]))
+        );
+        assert_eq!("12345/3".parse(), Ok(Rule::new(&[3], &[1, 2, 3, 4, 5])));
+    }
+
+    #[test]
+    fn test_parse_rule_errors() {
+        assert_eq!("B39/S23".parse::<Rule>(), Err(RuleError::InvalidCount('9')));
+        assert_eq!("B3/Sx".parse::<Rule>(), Err(RuleError::InvalidCount('x')));
+        assert_eq!(
+            "B3S23".parse::<Rule>(),
+            Err(RuleError::Malformed("B3S23".to_string()))
+        );
+        assert!("B3/B23".parse::<Rule>().is_err());
+        assert!("B2/S/C1".parse::<Rule>().is_err());
+        assert!("B2/S/Cx".parse::<Rule>().is_err());
+        assert!("B2/S/C3/4".parse::<Rule>().is_err());
+    }
+
+    #[test]
+    fn test_rule_display_round_trip() {
+        assert_eq!(Rule::CONWAY.to_string(), "B3/S23");
+        assert_eq!(Rule::new(&[6, 3], &[]).to_string(), "B36/S");
+
+        // Every combination of birth and survival counts survives a round trip.
+        let counts = |bits: u16| (0..=8).filter(|&n| bits & 1 << n != 0).collect::<Vec<u8>>();
+        for birth in (0..512).step_by(7) {
+            for survival in (0..512).step_by(11) {
+                let rule = Rule::new(&counts(birth), &counts(survival));
+                assert_eq!(rule.to_string().parse(), Ok(rule));
+            }
+        }
+    }
+
+    #[test]
+    fn test_hex_rule() {
+        assert_eq!(Rule::hex(&[2], &[3, 4]), "B2/S34".parse().unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hex_rule_rejects_large_counts() {
+        Rule::hex(&[2], &[7]);
+    }
+
+    #[test]
+    fn test_larger_than_life() {
+        let rule = Rule::larger_than_life(2, 5..=7, 4..=9);
+        assert_eq!(rule.radius(), 2);
+        assert!(rule.is_birth(5) && rule.is_birth(7) && !rule.is_birth(8));
+        assert!(!rule.is_survival(24) && rule.is_survival(9));
+
+        // A radius of 1 is an ordinary Life-like rule.
+        assert_eq!(Rule::larger_than_life(1, 3..=3, 2..=3), Rule::CONWAY);
+        assert_eq!(Rule::CONWAY.radius(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_larger_than_life_rejects_large_counts() {
+        Rule::larger_than_life(2, 0..=25, 0..=0);
+    }
+
+    #[test]
+    fn test_parse_larger_than_life() {
+        assert_eq!("R5,C0,M1,S34..58,B34..45,NM".parse(), Ok(Rule::BUGS));
+        assert_eq!("r5,c0,m0,s33..57,b34..45,nm".parse(), Ok(Rule::BUGS));
+        assert_eq!(Rule::BUGS.to_string(), "R5,C0,M0,S33..57,B34..45,NM");
+
+        // Further ranges follow as items of their own.
+        let rule: Rule = "R2,C3,M0,S2..3,6..8,B4..4,NM".parse().unwrap();
+        assert_eq!(rule.states(), 3);
+        let survivals: Vec<u32> = (0..=24).filter(|&n| rule.is_survival(n)).collect();
+        assert_eq!(survivals, vec![2, 3, 6, 7, 8]);
+        assert_eq!(rule.to_string(), "R2,C3,M0,S2..3,6..8,B4..4,NM");
+
+        assert_eq!(
+            "R11,C0,M0,S1..2,B3..3,NM".parse::<Rule>(),
+            Err(RuleError::InvalidRadius(11))
+        );
+        assert!("R2,C0,M0,S1..30,B3..3,NM".parse::<Rule>().is_err());
+        assert!("R2,C0,M1,S0..3,B3..3,NM".parse::<Rule>().is_err());
+        assert!("R2,C0,M0,S1-3,B3..3,NM".parse::<Rule>().is_err());
+        assert!("R2,C0,M0,S1..3,B3..3,NX".parse::<Rule>().is_err());
+    }
+
+    #[test]
+    fn test_preset_strings() {
+        let presets = [
+            (Rule::CONWAY, "B3/S23"),
+            (Rule::HIGHLIFE, "B36/S23"),
+            (Rule::SEEDS, "B2/S"),
+            (Rule::DAY_AND_NIGHT, "B3678/S34678"),
+            (Rule::LIFE_WITHOUT_DEATH, "B3/S012345678"),
+            (Rule::MAZE, "B3/S12345"),
+            (Rule::REPLICATOR, "B1357/S1357"),
+            (Rule::BRIANS_BRAIN, "B2/S/C3"),
+            (Rule::BUGS, "R5,C0,M0,S33..57,B34..45,NM"),
+        ];
+        for (rule, text) in presets {
+            assert_eq!(rule.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn test_conway() {
+        let blinker: World = ".....\n.....\n.OOO.\n.....\n.....".parse().unwrap();
+        let mut world = blinker.clone().with_rule(Rule::CONWAY);
+        world.evolve();
+        assert_ne!(world, blinker);
+        world.evolve();
+        assert_eq!(world, blinker);
+    }
+
+    #[test]
+    fn test_highlife() {
+        // The centre cell has 6 live neighbours, which only HighLife counts
+        // as a birth.
+        let world: World = ".....\n.OOO.\n.....\n.OOO.\n.....".parse().unwrap();
+        assert_eq!(world.get_num_alive_neighbours(2, 2), 6);
+
+        let mut highlife = world.clone().with_rule(Rule::HIGHLIFE);
+        let mut conway = world.with_rule(Rule::CONWAY);
+        highlife.evolve();
+        conway.evolve();
+        assert!(highlife[highlife.get_index(2, 2)].is_alive());
+        assert!(!conway[conway.get_index(2, 2)].is_alive());
+    }
+
+    #[test]
+    fn test_seeds() {
+        let mut world = World::new_with_seed(30, 30, 1).with_rule(Rule::SEEDS);
+        for _ in 0..10 {
+            let previous = world.clone();
+            world.evolve();
+            for i in 0..previous.grid.len() {
+                if previous[i].is_alive() {
+                    assert_eq!(world[i], Cell::Dead);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_day_and_night() {
+        let invert = |world: &World| {
+            let cells = world.grid.iter().map(|cell| !cell.is_alive()).collect();
+            World::from_bools(world.width(), world.height(), cells)
+                .unwrap()
+                .with_rule(world.rule())
+        };
+
+        let mut world = World::new_with_seed(20, 20, 4).with_rule(Rule::DAY_AND_NIGHT);
+        let mut inverted = invert(&world);
+        for _ in 0..10 {
+            world.evolve();
+            inverted.evolve();
+            assert_eq!(inverted, invert(&world));
+        }
+    }
+
+    #[test]
+    fn test_life_without_death() {
+        let mut world = World::new_random(30, 30, 0.1)
+            .unwrap()
+            .with_rule(Rule::LIFE_WITHOUT_DEATH);
+        for _ in 0..20 {
+            let previous = population(&world);
+            world.evolve();
+            assert!(population(&world) >= previous);
+        }
+    }
+
+    #[test]
+    fn test_maze() {
+        // A lone domino dies under Conway's rule, but under Maze each of its
+        // cells survives with a single neighbour.
+        let domino: World = "....\n.OO.\n....".parse().unwrap();
+        let mut world = domino.clone().with_rule(Rule::MAZE);
+        world.evolve();
+        assert!(world[world.get_index(1, 1)].is_alive());
+        assert!(world[world.get_index(1, 2)].is_alive());
+
+        let mut world = domino.with_rule(Rule::CONWAY);
+        world.evolve();
+        assert_eq!(population(&world), 0);
+    }
+
+    #[test]
+    fn test_generations() {
+        // Under B2/S/C4 a domino dies into two dying cells, which fade out
+        // over two generations while the cells they touched are born.
+        let mut world: World = "....\n.OO.\n....".parse().unwrap();
+        world.set_rule(Rule::generations(&[2], &[], 4));
+        world.evolve();
+        assert_eq!(
+            world.get_row(1),
+            &[Cell::Dead, Cell::Dying(1), Cell::Dying(1), Cell::Dead]
+        );
+        world.evolve();
+        assert_eq!(world.get_row(1)[1..3], [Cell::Dying(2), Cell::Dying(2)]);
+        world.evolve();
+        assert!(!world.get_row(1)[1..3]
+            .iter()
+            .any(|cell| matches!(cell, Cell::Dying(_))));
+    }
+
+    #[test]
+    fn test_brians_brain_oscillator() {
+        // Four firing and four refractory cells around a square chase each
+        // other round it with period 3.
+        let (f, r) = (Cell::Alive, Cell::Dying(1));
+        let mut world = World::empty(8, 8).with_rule(Rule::BRIANS_BRAIN);
+        for (row, col, cell) in [
+            (2, 3, f),
+            (2, 4, r),
+            (3, 2, r),
+            (3, 5, f),
+            (4, 2, f),
+            (4, 5, r),
+            (5, 3, r),
+            (5, 4, f),
+        ] {
+            let idx = world.get_index(row, col);
+            world.grid[idx] = cell;
+        }
+
+        let start = world.clone();
+        for generation in 1..=6 {
+            world.evolve();
+            assert_eq!(world == start, generation % 3 == 0);
+        }
+    }
+
+    #[test]
+    fn test_brians_brain_cycle() {
+        let mut world = World::new_with_seed(20, 20, 8).with_rule(Rule::BRIANS_BRAIN);
+        for _ in 0..10 {
+            let previous = world.clone();
+            world.evolve();
+            for row in 0..20 {
+                for col in 0..20 {
+                    let idx = world.get_index(row, col);
+                    let expected = match previous[idx] {
+                        Cell::Alive => Cell::Dying(1),
+                        Cell::Dying(_) => Cell::Dead,
+                        Cell::Dead if previous.get_num_alive_neighbours(row, col) == 2 => {
+                            Cell::Alive
+                        }
+                        Cell::Dead => Cell::Dead,
+                    };
+                    assert_eq!(world[idx], expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_bugs() {
+        // A solid block much smaller than the neighbourhood has too few live
+        // neighbours everywhere and dies out at once.
+        let mut world = World::empty(30, 30).with_rule(Rule::BUGS);
+        for row in 10..14 {
+            for col in 10..14 {
+                let idx = world.get_index(row, col);
+                world.grid[idx] = Cell::Alive;
+            }
+        }
+        world.evolve();
+        assert_eq!(population(&world), 0);
+
+        // A region with most of its neighbourhood alive keeps going.
+        let mut world = World::new_random(40, 40, 0.5)
+            .unwrap()
+            .with_rule(Rule::BUGS);
+        world.evolve();
+        assert!(population(&world) > 0);
+    }
+
+    #[test]
+    fn test_replicator() {
+        // After 4 generations a single cell has been replaced by 8 copies of
+        // itself, 4 cells away in every direction.
+        let mut world = World::empty(16, 16).with_rule(Rule::REPLICATOR);
+        let idx = world.get_index(8, 8);
+        world.grid[idx] = Cell::Alive;
+        for _ in 0..4 {
+            world.evolve();
+        }
+
+        let mut expected = World::empty(16, 16).with_rule(Rule::REPLICATOR);
+        for (row, col) in [
+            (4, 4),
+            (4, 8),
+            (4, 12),
+            (8, 4),
+            (8, 12),
+            (12, 4),
+            (12, 8),
+            (12, 12),
+        ] {
+            let idx = expected.get_index(row, col);
+            expected.grid[idx] = Cell::Alive;
+        }
+        assert_eq!(world, expected);
+    }
+}
@@ -0,0 +1,103 @@
+use serde::{
+    de::Error as _, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use super::{world_parts::Cell, World};
+
+// Cells are stored as plain booleans to keep large grids small.
+impl Serialize for Cell {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bool(self.is_alive())
+    }
+}
+
+impl<'de> Deserialize<'de> for Cell {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        bool::deserialize(deserializer).map(Cell::from)
+    }
+}
+
+impl Serialize for World {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("World", 4)?;
+        state.serialize_field("width", &self.width)?;
+        state.serialize_field("height", &self.height)?;
+        state.serialize_field("generation", &self.generation)?;
+        state.serialize_field("grid", &self.grid)?;
+        state.end()
+    }
+}
+
+// The fields as they appear in serialized data, before they are checked to
+// describe a valid world.
+#[derive(Deserialize)]
+#[serde(rename = "World")]
+struct RawWorld {
+    width: u32,
+    height: u32,
+    // Left out by data written before it was saved.
+    #[serde(default)]
+    generation: u64,
+    grid: Vec<Cell>,
+}
+
+impl<'de> Deserialize<'de> for World {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawWorld::deserialize(deserializer)?;
+        let mut world =
+            World::from_cells(raw.width, raw.height, raw.grid).map_err(D::Error::custom)?;
+        world.generation = raw.generation;
+        Ok(world)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{
+        world_parts::Cell::{self, *},
+        World,
+    };
+
+    #[test]
+    fn test_serialize_cell() {
+        assert_eq!(serde_json::to_string(&Alive).unwrap(), "true");
+        assert_eq!(serde_json::to_string(&Dead).unwrap(), "false");
+        assert_eq!(
+            serde_json::from_str::<Vec<Cell>>("[true,false]").unwrap(),
+            vec![Alive, Dead]
+        );
+    }
+
+    #[test]
+    fn test_serialize_world() {
+        let world = World::from_cells(2, 1, vec![Alive, Dead]).unwrap();
+        let json = serde_json::to_string(&world).unwrap();
+        assert_eq!(
+            json,
+            r#"{"width":2,"height":1,"generation":0,"grid":[true,false]}"#
+        );
+
+        let mut world = World::new_with_seed(16, 9, 3);
+        world.evolve_n(2);
+        let json = serde_json::to_string(&world).unwrap();
+        let loaded = serde_json::from_str::<World>(&json).unwrap();
+        assert_eq!(loaded, world);
+        assert_eq!(loaded.generation(), 2);
+
+        let loaded: World =
+            serde_json::from_str(r#"{"width":1,"height":1,"grid":[true]}"#).unwrap();
+        assert_eq!(loaded.generation(), 0);
+    }
+
+    #[test]
+    fn test_deserialize_rejects_invalid_world() {
+        let result = serde_json::from_str::<World>(r#"{"width":2,"height":2,"grid":[true]}"#);
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("expected 4 cells, got 1"));
+
+        let result = serde_json::from_str::<World>(r#"{"width":0,"height":2,"grid":[]}"#);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,276 @@
+use std::{error::Error, fmt};
+
+use super::{
+    check_dimensions,
+    snapshot::{pack_cells, unpack_cells},
+    World, WorldError,
+};
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// Set in the leading flag byte when runs of empty bytes have been compressed.
+const COMPRESSED: u8 = 1;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ShareError {
+    InvalidBase64,
+    InvalidFlags(u8),
+    Malformed,
+    Truncated,
+    TrailingData,
+    World(WorldError),
+}
+
+impl fmt::Display for ShareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShareError::InvalidBase64 => write!(f, "share string is not valid base64url"),
+            ShareError::InvalidFlags(flags) => write!(f, "unknown share string flags {}", flags),
+            ShareError::Malformed => write!(f, "share string is malformed"),
+            ShareError::Truncated => write!(f, "share string is truncated"),
+            ShareError::TrailingData => write!(f, "share string has data past the grid"),
+            ShareError::World(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for ShareError {}
+
+impl World {
+    /// Encodes the world as a short, URL-safe string that `from_share_string`
+    /// turns back into an identical world.
+    ///
+    /// The string is base64url (without padding) of a flag byte, width and
+    /// height as LEB128 varints, and the bit-packed cells. Runs of empty bytes
+    /// are compressed whenever that makes the result shorter, which keeps
+    /// sparse worlds in particular very small.
+    pub fn to_share_string(&self) -> String {
+        let packed = pack_cells(&self.grid);
+        let compressed = compress(&packed);
+
+        let mut bytes = Vec::new();
+        let payload = if compressed.len() < packed.len() {
+            bytes.push(COMPRESSED);
+            compressed
+        } else {
+            bytes.push(0);
+            packed
+        };
+        write_varint(&mut bytes, self.width as u64);
+        write_varint(&mut bytes, self.height as u64);
+        bytes.extend(payload);
+
+        encode_base64(&bytes)
+    }
+
+    pub fn from_share_string(s: &str) -> Result<Self, ShareError> {
+        let bytes = decode_base64(s)?;
+        let (&flags, mut rest) = bytes.split_first().ok_or(ShareError::Truncated)?;
+        if flags & !COMPRESSED != 0 {
+            return Err(ShareError::InvalidFlags(flags));
+        }
+
+        let width = read_varint(&mut rest)?;
+        let height = read_varint(&mut rest)?;
+        let (width, height) = match (u32::try_from(width), u32::try_from(height)) {
+            (Ok(width), Ok(height)) => (width, height),
+            _ => return Err(ShareError::Malformed),
+        };
+        check_dimensions(width, height).map_err(ShareError::World)?;
+
+        let len = width as usize * height as usize;
+        let packed_len = len.div_ceil(8);
+        let packed = if flags & COMPRESSED != 0 {
+            decompress(rest, packed_len)?
+        } else {
+            rest.to_vec()
+        };
+
+        if packed.len() < packed_len {
+            return Err(ShareError::Truncated);
+        }
+        if packed.len() > packed_len {
+            return Err(ShareError::TrailingData);
+        }
+
+        World::from_cells(width, height, unpack_cells(&packed, len)).map_err(ShareError::World)
+    }
+}
+
+// A zero byte is followed by a varint holding the length of the run of zeros
+// it starts. All other bytes are copied as they are.
+fn compress(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == 0 {
+            let run = bytes[i..].iter().take_while(|&&b| b == 0).count();
+            out.push(0);
+            write_varint(&mut out, run as u64);
+            i += run;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    out
+}
+
+// Stops as soon as the output would grow past `limit`, so a tampered run
+// length can't cause a huge allocation.
+fn decompress(mut bytes: &[u8], limit: usize) -> Result<Vec<u8>, ShareError> {
+    let mut out = Vec::new();
+    while let Some((&b, rest)) = bytes.split_first() {
+        bytes = rest;
+        if b != 0 {
+            out.push(b);
+        } else {
+            let run = read_varint(&mut bytes)?;
+            if out.len() as u64 + run > limit as u64 {
+                return Err(ShareError::TrailingData);
+            }
+            out.resize(out.len() + run as usize, 0);
+        }
+
+        if out.len() > limit {
+            return Err(ShareError::TrailingData);
+        }
+    }
+    Ok(out)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, ShareError> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&b, rest) = bytes.split_first().ok_or(ShareError::Truncated)?;
+        *bytes = rest;
+        value |= ((b & 0x7f) as u64) << shift;
+        if b & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(ShareError::Malformed)
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        // 3 bytes make 4 characters, a shorter final chunk one fewer per
+        // missing byte.
+        for i in 0..=chunk.len() {
+            out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    out
+}
+
+fn decode_base64(s: &str) -> Result<Vec<u8>, ShareError> {
+    let mut out = Vec::with_capacity(s.len() / 4 * 3);
+    for chunk in s.as_bytes().chunks(4) {
+        if chunk.len() == 1 {
+            return Err(ShareError::InvalidBase64);
+        }
+
+        let mut n = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            let value = ALPHABET
+                .iter()
+                .position(|&a| a == c)
+                .ok_or(ShareError::InvalidBase64)?;
+            n |= (value as u32) << (18 - 6 * i);
+        }
+        for i in 0..chunk.len() - 1 {
+            out.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode_base64, encode_base64, ShareError};
+    use crate::game::World;
+    use crate::patterns;
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(encode_base64(b""), "");
+        assert_eq!(encode_base64(b"f"), "Zg");
+        assert_eq!(encode_base64(b"fo"), "Zm8");
+        assert_eq!(encode_base64(b"foo"), "Zm9v");
+        assert_eq!(encode_base64(&[0xfb, 0xff]), "-_8");
+
+        for bytes in [&b"f"[..], b"fo", b"foo", b"foob", &[0xfb, 0xff, 0x00]] {
+            assert_eq!(decode_base64(&encode_base64(bytes)).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_share_string_round_trip() {
+        let world = World::new_with_seed(80, 25, 12);
+        let shared = world.to_share_string();
+        assert_eq!(World::from_share_string(&shared).unwrap(), world);
+
+        let glider = patterns::glider();
+        let mut sparse = World::empty(80, 25);
+        for row in 0..3 {
+            for col in 0..3 {
+                let idx = sparse.get_index(row + 10, col + 40);
+                sparse.grid[idx] = glider[glider.get_index(row, col)];
+            }
+        }
+
+        let shared = sparse.to_share_string();
+        assert!(shared.len() < 30, "{} is too long", shared);
+        assert_eq!(World::from_share_string(&shared).unwrap(), sparse);
+    }
+
+    #[test]
+    fn test_share_string_stays_short() {
+        let world = World::new_random(80, 25, 0.2).unwrap();
+        assert!(world.to_share_string().len() < 400);
+    }
+
+    #[test]
+    fn test_from_share_string_errors() {
+        let shared = World::new_with_seed(20, 20, 1).to_share_string();
+
+        let result = World::from_share_string("not base64!");
+        assert_eq!(result, Err(ShareError::InvalidBase64));
+
+        let result = World::from_share_string(&shared[..shared.len() - 4]);
+        assert_eq!(result, Err(ShareError::Truncated));
+
+        let result = World::from_share_string(&format!("{}AAAA", shared));
+        assert_eq!(result, Err(ShareError::TrailingData));
+
+        let result = World::from_share_string("");
+        assert_eq!(result, Err(ShareError::Truncated));
+
+        let result = World::from_share_string(&encode_base64(&[4, 1, 1, 0]));
+        assert_eq!(result, Err(ShareError::InvalidFlags(4)));
+
+        // A compressed run far longer than the grid is rejected.
+        let result = World::from_share_string(&encode_base64(&[1, 2, 2, 0, 0xff, 0x7f]));
+        assert_eq!(result, Err(ShareError::TrailingData));
+
+        let result =
+            World::from_share_string(&encode_base64(&[0, 0xff, 0xff, 0xff, 0xff, 0x7f, 1]));
+        assert_eq!(result, Err(ShareError::Malformed));
+
+        let result = World::from_share_string(&encode_base64(&[0, 0, 2]));
+        assert!(matches!(result, Err(ShareError::World(_))));
+    }
+}
@@ -0,0 +1,162 @@
+use super::World;
+
+impl World {
+    /// Moves every cell `d_row` rows down and `d_col` columns to the right,
+    /// with cells moved past an edge coming back in on the other side.
+    /// Negative offsets move up and to the left, and offsets larger than the
+    /// world go round more than once. Ages and heat move along with the
+    /// cells.
+    ///
+    /// Only whole rows move, so an odd `d_row` changes which rows of a
+    /// hexagonal world are shifted.
+    pub fn shift(&mut self, d_row: i32, d_col: i32) {
+        let (width, height) = (self.width, self.height);
+        rotate(&mut self.grid, width, height, d_row, d_col);
+        if let Some(ages) = &mut self.ages {
+            rotate(ages, width, height, d_row, d_col);
+        }
+        if let Some(heatmap) = &mut self.heatmap {
+            heatmap.shift(d_row, d_col);
+        }
+        self.changed = None;
+    }
+
+    /// Same as `shift`, but cells moved past an edge are dropped, and those
+    /// coming in from beyond it are dead, with no age or heat.
+    pub fn shift_clipped(&mut self, d_row: i32, d_col: i32) {
+        let (width, height) = (self.width, self.height);
+        let grid = clipped(&self.grid, width, height, d_row, d_col);
+        if let Some(ages) = &mut self.ages {
+            *ages = clipped(ages, width, height, d_row, d_col);
+        }
+        if let Some(heatmap) = &mut self.heatmap {
+            heatmap.shift_clipped(d_row, d_col);
+        }
+
+        self.population = Some(grid.iter().filter(|cell| cell.is_alive()).count());
+        self.grid = grid;
+        self.changed = None;
+    }
+}
+
+// Moves `values`, laid out like a grid, as `World::shift` moves the cells.
+pub(super) fn rotate<T>(values: &mut [T], width: u32, height: u32, d_row: i32, d_col: i32) {
+    let d_row = (d_row as i64).rem_euclid(height as i64) as usize;
+    let d_col = (d_col as i64).rem_euclid(width as i64) as usize;
+    let width = width as usize;
+
+    if d_col != 0 {
+        for row in values.chunks_mut(width) {
+            row.rotate_right(d_col);
+        }
+    }
+    values.rotate_right(d_row * width);
+}
+
+// Moves `values`, laid out like a grid, as `World::shift_clipped` moves the
+// cells, filling in the default for whatever comes in.
+pub(super) fn clipped<T: Copy + Default>(
+    values: &[T],
+    width: u32,
+    height: u32,
+    d_row: i32,
+    d_col: i32,
+) -> Vec<T> {
+    let (width, height) = (width as i64, height as i64);
+    let (d_row, d_col) = (d_row as i64, d_col as i64);
+
+    // The columns that stay in the world, before moving.
+    let first = (-d_col).clamp(0, width);
+    let last = (width - d_col).clamp(0, width);
+    let mut moved = vec![T::default(); values.len()];
+    if first < last {
+        let len = (last - first) as usize;
+        for row in 0..height {
+            let new_row = row + d_row;
+            if !(0..height).contains(&new_row) {
+                continue;
+            }
+            let from = (row * width + first) as usize;
+            let to = (new_row * width + first + d_col) as usize;
+            moved[to..to + len].copy_from_slice(&values[from..from + len]);
+        }
+    }
+    moved
+}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{world_parts::Cell::*, Pattern, World};
+
+    fn glider() -> World {
+        ".O....\n..O...\nOOO...\n......\n......".parse().unwrap()
+    }
+
+    #[test]
+    fn test_shift_wraps() {
+        let mut world = glider();
+        world.shift(-2, 6 + 1);
+        let expected: World = ".OOO..\n......\n......\n..O...\n...O..".parse().unwrap();
+        assert_eq!(world, expected);
+        let cells: Vec<_> = world.live_cells().collect();
+        assert_eq!(cells, [(0, 1), (0, 2), (0, 3), (3, 2), (4, 3)]);
+        assert_eq!(world.population(), 5);
+
+        // Going all the way round changes nothing.
+        let mut world = glider();
+        world.shift(5 * 3, -6 * 7);
+        assert_eq!(world, glider());
+    }
+
+    #[test]
+    fn test_shift_clipped() {
+        let mut world = glider();
+        world.shift_clipped(3, -1);
+        let expected: World = "......\n......\n......\nO.....\n.O....".parse().unwrap();
+        assert_eq!(world, expected);
+        assert_eq!(world.population(), 2);
+
+        world.shift_clipped(-10, 0);
+        assert!(world.is_empty());
+    }
+
+    #[test]
+    fn test_shift_evolves() {
+        let mut world = glider();
+        let mut shifted = glider();
+        shifted.shift(2, 3);
+        for _ in 0..12 {
+            world.evolve();
+            shifted.evolve();
+        }
+        world.shift(2, 3);
+        assert_eq!(shifted, world);
+    }
+
+    #[test]
+    fn test_shift_moves_ages_and_heat() {
+        let block: World = "....\n.OO.\n.OO.\n....".parse().unwrap();
+        let mut world = block.with_cells(6, 5, vec![Dead; 30]);
+        world.place(&Pattern::from(block), 0, 0).unwrap();
+        world.enable_ages();
+        world.enable_heatmap();
+        world.set(4, 5, Alive).unwrap();
+        world.evolve();
+        world.evolve();
+        assert_eq!(world.age(1, 1), Some(2));
+        let heat = world.heatmap().unwrap().get(4, 5);
+        assert_eq!(heat, Some(1));
+
+        world.shift(1, -1);
+        assert_eq!(world.age(2, 0), Some(2));
+        assert_eq!(world.age(1, 1), Some(0));
+        assert_eq!(world.heatmap().unwrap().get(0, 4), heat);
+
+        world.shift_clipped(0, 2);
+        assert_eq!(world.age(2, 2), Some(2));
+        assert_eq!(world.age(3, 3), Some(2));
+        assert_eq!(world.age(2, 0), Some(0));
+        assert_eq!(world.heatmap().unwrap().get(0, 4), Some(0));
+        assert_eq!(world.heatmap().unwrap().max(), 0);
+    }
+}
@@ -0,0 +1,340 @@
+use std::{
+    error::Error,
+    fmt,
+    io::{self, Read, Write},
+};
+
+use super::{check_dimensions, world_parts::Cell, World, WorldError};
+
+const MAGIC: &[u8; 4] = b"LIFE";
+const VERSION: u8 = 3;
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(io::Error),
+    BadMagic,
+    UnsupportedVersion(u8),
+    Truncated,
+    World(WorldError),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io(e) => write!(f, "{}", e),
+            SnapshotError::BadMagic => write!(f, "not a world snapshot"),
+            SnapshotError::UnsupportedVersion(v) => {
+                write!(f, "unsupported snapshot version {}", v)
+            }
+            SnapshotError::Truncated => write!(f, "snapshot data is truncated"),
+            SnapshotError::World(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SnapshotError::Io(e) => Some(e),
+            SnapshotError::World(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SnapshotError {
+    fn from(e: io::Error) -> Self {
+        match e.kind() {
+            io::ErrorKind::UnexpectedEof => SnapshotError::Truncated,
+            _ => SnapshotError::Io(e),
+        }
+    }
+}
+
+impl World {
+    /// Writes a compact binary snapshot of the world: the magic bytes `LIFE`,
+    /// a format version byte, width and height as little endian `u32`s, the
+    /// generation as a little endian `u64`, and the cells packed 8 to a byte
+    /// in row-major order. A byte follows that is 1 if ages are counted, see
+    /// `World::enable_ages`, in which case the age of every live cell comes
+    /// after it as a little endian `u32`, in the same order.
+    pub fn save_snapshot<W: Write>(&self, w: &mut W) -> io::Result<()> {
+        w.write_all(MAGIC)?;
+        w.write_all(&[VERSION])?;
+        w.write_all(&self.width.to_le_bytes())?;
+        w.write_all(&self.height.to_le_bytes())?;
+        w.write_all(&self.generation.to_le_bytes())?;
+        w.write_all(&pack_cells(&self.grid))?;
+
+        let Some(ages) = &self.ages else {
+            return w.write_all(&[0]);
+        };
+        w.write_all(&[1])?;
+        let live = self
+            .grid
+            .iter()
+            .zip(ages)
+            .filter(|(cell, _)| cell.is_alive());
+        let bytes: Vec<_> = live.flat_map(|(_, age)| age.to_le_bytes()).collect();
+        w.write_all(&bytes)
+    }
+
+    /// Reads a snapshot written by `save_snapshot`. Snapshots of version 1,
+    /// written before they held the generation, load at generation 0, and
+    /// those before version 3 without ages.
+    pub fn load_snapshot<R: Read>(r: &mut R) -> Result<Self, SnapshotError> {
+        let mut magic = [0; 4];
+        r.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(SnapshotError::BadMagic);
+        }
+
+        let mut version = [0; 1];
+        r.read_exact(&mut version)?;
+        if !(1..=VERSION).contains(&version[0]) {
+            return Err(SnapshotError::UnsupportedVersion(version[0]));
+        }
+
+        let mut dimension = [0; 4];
+        r.read_exact(&mut dimension)?;
+        let width = u32::from_le_bytes(dimension);
+        r.read_exact(&mut dimension)?;
+        let height = u32::from_le_bytes(dimension);
+        check_dimensions(width, height).map_err(SnapshotError::World)?;
+
+        let mut generation = [0; 8];
+        if version[0] >= 2 {
+            r.read_exact(&mut generation)?;
+        }
+
+        // The buffer grows with the data actually read, so a corrupt header
+        // can't trigger a huge allocation up front.
+        let len = width as usize * height as usize;
+        let mut packed = Vec::new();
+        r.take(len.div_ceil(8) as u64).read_to_end(&mut packed)?;
+        if packed.len() < len.div_ceil(8) {
+            return Err(SnapshotError::Truncated);
+        }
+
+        let mut world = World::from_cells(width, height, unpack_cells(&packed, len))
+            .map_err(SnapshotError::World)?;
+        world.generation = u64::from_le_bytes(generation);
+
+        let mut has_ages = [0; 1];
+        if version[0] >= 3 {
+            r.read_exact(&mut has_ages)?;
+        }
+        if has_ages[0] == 1 {
+            let len = world.population() * 4;
+            let mut bytes = Vec::new();
+            r.take(len as u64).read_to_end(&mut bytes)?;
+            if bytes.len() < len {
+                return Err(SnapshotError::Truncated);
+            }
+
+            world.enable_ages();
+            let mut ages = bytes
+                .chunks_exact(4)
+                .map(|age| u32::from_le_bytes(age.try_into().expect("chunks of 4 bytes")));
+            if let Some(all) = &mut world.ages {
+                for (age, cell) in all.iter_mut().zip(&world.grid) {
+                    if cell.is_alive() {
+                        *age = ages.next().unwrap_or(0);
+                    }
+                }
+            }
+        }
+        Ok(world)
+    }
+}
+
+// Packs cells 8 to a byte, the first cell in the most significant bit.
+pub(crate) fn pack_cells(cells: &[Cell]) -> Vec<u8> {
+    let mut packed = vec![0u8; cells.len().div_ceil(8)];
+    for (i, cell) in cells.iter().enumerate() {
+        if cell.is_alive() {
+            packed[i / 8] |= 0x80 >> (i % 8);
+        }
+    }
+    packed
+}
+
+pub(crate) fn unpack_cells(packed: &[u8], len: usize) -> Vec<Cell> {
+    (0..len)
+        .map(|i| Cell::from(packed[i / 8] & (0x80 >> (i % 8)) != 0))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::SnapshotError;
+    use crate::game::{world_parts::Cell::*, StopCondition, World, WorldError};
+
+    #[test]
+    fn test_save_snapshot() {
+        let mut world = World::from_cells(
+            3,
+            3,
+            vec![Alive, Dead, Dead, Dead, Dead, Dead, Dead, Dead, Alive],
+        )
+        .unwrap();
+        world.set_generation(7);
+        let mut bytes = Vec::new();
+        world.save_snapshot(&mut bytes).unwrap();
+        assert_eq!(
+            bytes,
+            vec![
+                b'L',
+                b'I',
+                b'F',
+                b'E',
+                3,
+                3,
+                0,
+                0,
+                0,
+                3,
+                0,
+                0,
+                0,
+                7,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0b1000_0000,
+                0b1000_0000,
+                0
+            ]
+        );
+
+        world.enable_ages();
+        // The two cells touch across the corner, and keep each other alive.
+        world.set_rule("B/S1".parse().unwrap());
+        world.evolve_n_until(2, StopCondition::Never);
+        let mut bytes = Vec::new();
+        world.save_snapshot(&mut bytes).unwrap();
+        assert_eq!(bytes[23..], [1, 2, 0, 0, 0, 2, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut world = World::new_with_seed(123, 45, 6);
+        world.evolve_n(3);
+        let mut bytes = Vec::new();
+        world.save_snapshot(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), 22 + (123 * 45usize).div_ceil(8));
+
+        let loaded = World::load_snapshot(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded, world);
+        assert_eq!(loaded.generation(), 3);
+        assert_eq!(loaded.max_age(), None);
+    }
+
+    #[test]
+    fn test_snapshot_round_trip_ages() {
+        let mut world = World::new_with_seed(40, 30, 2);
+        world.enable_ages();
+        world.evolve_n_until(30, StopCondition::Never);
+        let mut bytes = Vec::new();
+        world.save_snapshot(&mut bytes).unwrap();
+        assert_eq!(
+            bytes.len(),
+            22 + (40 * 30usize).div_ceil(8) + 4 * world.population()
+        );
+
+        let loaded = World::load_snapshot(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded, world);
+        assert!(loaded.max_age().unwrap() > 0);
+        for (row, col, _) in world.iter() {
+            assert_eq!(loaded.age(row, col), world.age(row, col));
+        }
+
+        let result = World::load_snapshot(&mut &bytes[..bytes.len() - 1]);
+        assert!(matches!(result, Err(SnapshotError::Truncated)));
+    }
+
+    #[test]
+    fn test_load_snapshot_version_2() {
+        let mut bytes = Vec::new();
+        World::new_with_seed(10, 10, 1)
+            .save_snapshot(&mut bytes)
+            .unwrap();
+        bytes[4] = 2;
+        bytes.pop();
+        let loaded = World::load_snapshot(&mut bytes.as_slice()).unwrap();
+        assert_eq!(loaded, World::new_with_seed(10, 10, 1));
+        assert_eq!(loaded.max_age(), None);
+    }
+
+    #[test]
+    fn test_load_snapshot_version_1() {
+        let bytes = [
+            b'L',
+            b'I',
+            b'F',
+            b'E',
+            1,
+            3,
+            0,
+            0,
+            0,
+            1,
+            0,
+            0,
+            0,
+            0b0100_0000,
+        ];
+        let loaded = World::load_snapshot(&mut &bytes[..]).unwrap();
+        assert_eq!(
+            loaded,
+            World::from_cells(3, 1, vec![Dead, Alive, Dead]).unwrap()
+        );
+        assert_eq!(loaded.generation(), 0);
+    }
+
+    #[test]
+    fn test_load_snapshot_errors() {
+        let mut bytes = Vec::new();
+        World::new_with_seed(10, 10, 1)
+            .save_snapshot(&mut bytes)
+            .unwrap();
+
+        let result = World::load_snapshot(&mut &bytes[..bytes.len() - 1]);
+        assert!(matches!(result, Err(SnapshotError::Truncated)));
+
+        let result = World::load_snapshot(&mut &bytes[..16]);
+        assert!(matches!(result, Err(SnapshotError::Truncated)));
+
+        let mut bad = bytes.clone();
+        bad[0] = b'X';
+        let result = World::load_snapshot(&mut bad.as_slice());
+        assert!(matches!(result, Err(SnapshotError::BadMagic)));
+
+        let mut bad = bytes.clone();
+        bad[4] = 9;
+        let result = World::load_snapshot(&mut bad.as_slice());
+        assert!(matches!(result, Err(SnapshotError::UnsupportedVersion(9))));
+
+        let header = [b'L', b'I', b'F', b'E', 1, 0, 0, 0, 0, 5, 0, 0, 0];
+        let result = World::load_snapshot(&mut &header[..]);
+        assert!(matches!(result, Err(SnapshotError::World(_))));
+
+        // A header that is too large is rejected before reading any cells.
+        let header = [
+            b'L', b'I', b'F', b'E', 1, 255, 255, 255, 255, 255, 255, 255, 255,
+        ];
+        let result = World::load_snapshot(&mut &header[..]);
+        assert!(matches!(
+            result,
+            Err(SnapshotError::World(WorldError::TooLarge { .. }))
+        ));
+
+        let header = [b'L', b'I', b'F', b'E', 1, 255, 255, 0, 0, 255, 255, 0, 0];
+        let result = World::load_snapshot(&mut &header[..]);
+        assert!(matches!(result, Err(SnapshotError::Truncated)));
+    }
+}
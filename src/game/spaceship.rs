@@ -0,0 +1,156 @@
+use super::{world_parts::Cell, Boundary, World};
+
+/// A pattern that comes back moved after some generations, see
+/// `World::detect_spaceship`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Spaceship {
+    /// The number of generations after which it comes back.
+    pub period: u32,
+    /// How far it has moved by then, as `(rows, cols)`, down and to the
+    /// right being positive.
+    pub displacement: (i32, i32),
+}
+
+impl World {
+    /// Looks for a period of up to `max_period` generations after which the
+    /// world is the same as now, only moved. Returns `None` if there is none,
+    /// which includes oscillators and still lifes as they don't move.
+    ///
+    /// On a torus the world is moved round the edges with `shift`, and the
+    /// shortest way round is reported. On other surfaces the moved pattern
+    /// has to stay clear of the edges.
+    pub fn detect_spaceship(&self, max_period: u32) -> Option<Spaceship> {
+        let torus = self.boundary == Boundary::TORUS;
+        let (width, height) = (self.width as i64, self.height as i64);
+        // Every cell that isn't dead has to turn up moved by the same amount,
+        // and the first live one has to land on a live cell.
+        let cells: Vec<_> = self
+            .iter()
+            .filter(|&(_, _, cell)| cell != Cell::Dead)
+            .map(|(row, col, cell)| (row as i64, col as i64, cell))
+            .collect();
+        let &(anchor_row, anchor_col, _) = cells.iter().find(|(_, _, cell)| cell.is_alive())?;
+
+        let mut world = self.clone();
+        for period in 1..=max_period {
+            world.evolve();
+            if world.population() != self.population()
+                || world
+                    .grid
+                    .iter()
+                    .filter(|&&cell| cell != Cell::Dead)
+                    .count()
+                    != cells.len()
+            {
+                continue;
+            }
+
+            // No cell gets further than the rule reaches in that many
+            // generations. The nearest places are tried first, so the
+            // shortest way round a torus is the one reported.
+            let reach = self.rule.radius() as i64 * period as i64;
+            let mut candidates: Vec<_> = world
+                .live_cells()
+                .map(|(row, col)| {
+                    let (d_row, d_col) = (row as i64 - anchor_row, col as i64 - anchor_col);
+                    if torus {
+                        (shortest(d_row, height), shortest(d_col, width))
+                    } else {
+                        (d_row, d_col)
+                    }
+                })
+                .filter(|&(d_row, d_col)| {
+                    (d_row, d_col) != (0, 0) && d_row.abs().max(d_col.abs()) <= reach
+                })
+                .collect();
+            candidates.sort_by_key(|&(d_row, d_col)| d_row.abs() + d_col.abs());
+
+            for (d_row, d_col) in candidates {
+                let moved = cells.iter().all(|&(row, col, cell)| {
+                    let (mut row, mut col) = (row + d_row, col + d_col);
+                    if torus {
+                        (row, col) = (row.rem_euclid(height), col.rem_euclid(width));
+                    } else if !(0..height).contains(&row) || !(0..width).contains(&col) {
+                        return false;
+                    }
+                    world.grid[world.get_index(row as u32, col as u32)] == cell
+                });
+                if moved {
+                    return Some(Spaceship {
+                        period,
+                        displacement: (d_row as i32, d_col as i32),
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+// The shortest way to move `d` cells round a loop of `len` cells, with ties
+// going forwards.
+fn shortest(d: i64, len: i64) -> i64 {
+    let d = d.rem_euclid(len);
+    if d > len / 2 {
+        d - len
+    } else {
+        d
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Spaceship;
+    use crate::game::{Boundary, EdgeMode, Pattern, World};
+    use crate::patterns;
+
+    fn placed(pattern: World, width: u32, height: u32, row: u32, col: u32) -> World {
+        let pattern = Pattern::from(pattern);
+        let mut world = World::empty(width, height);
+        world.place(&pattern, row, col).unwrap();
+        world
+    }
+
+    #[test]
+    fn test_detect_spaceship_glider() {
+        let glider = Spaceship {
+            period: 4,
+            displacement: (1, 1),
+        };
+        let world = placed(patterns::glider(), 20, 20, 5, 5);
+        assert_eq!(world.detect_spaceship(8), Some(glider));
+        assert_eq!(world.detect_spaceship(3), None);
+
+        // Lying across the corner of the torus.
+        let mut world = world;
+        world.shift(-6, -6);
+        assert_eq!(world.detect_spaceship(4), Some(glider));
+
+        let world = placed(patterns::glider(), 20, 20, 5, 5)
+            .with_boundary(Boundary::uniform(EdgeMode::Dead));
+        assert_eq!(world.detect_spaceship(4), Some(glider));
+    }
+
+    #[test]
+    fn test_detect_spaceship_lwss() {
+        let world = placed(patterns::lwss(), 20, 12, 4, 10);
+        assert_eq!(
+            world.detect_spaceship(4),
+            Some(Spaceship {
+                period: 4,
+                displacement: (0, -2)
+            })
+        );
+    }
+
+    #[test]
+    fn test_detect_spaceship_rejects_oscillators() {
+        // Oscillators and still lifes come back without moving, which
+        // doesn't make them spaceships.
+        let blinker = placed(patterns::blinker(), 10, 10, 4, 4);
+        assert_eq!(blinker.detect_spaceship(10), None);
+        let block = placed("OO\nOO".parse().unwrap(), 10, 10, 4, 4);
+        assert_eq!(block.detect_spaceship(10), None);
+        assert_eq!(World::empty(10, 10).detect_spaceship(10), None);
+    }
+}
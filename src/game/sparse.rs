@@ -0,0 +1,216 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{check_dimensions, world_parts::Cell, Rule, World, WorldError};
+
+/// An unbounded world that only stores its live cells, so patterns can grow
+/// without ever running into an edge, and memory use follows the population
+/// rather than the area.
+///
+/// Rules apply as in `World`, except that dying states of Generations rules
+/// aren't kept (cells die at once) and births without any live neighbours,
+/// which would fill the whole plane, never happen.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SparseWorld {
+    // Live cells as `(row, col)`.
+    cells: HashSet<(i64, i64)>,
+    rule: Rule,
+}
+
+impl SparseWorld {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the rule used by `evolve`, which is Conway's unless changed.
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rule = rule;
+        self
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn get(&self, row: i64, col: i64) -> Cell {
+        Cell::from(self.cells.contains(&(row, col)))
+    }
+
+    pub fn set(&mut self, row: i64, col: i64, cell: Cell) {
+        if cell.is_alive() {
+            self.cells.insert((row, col));
+        } else {
+            self.cells.remove(&(row, col));
+        }
+    }
+
+    /// The live cells as `(row, col)`, in no particular order.
+    pub fn live_cells(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.cells.iter().copied()
+    }
+
+    pub fn population(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The top left and bottom right corners of the smallest rectangle
+    /// holding every live cell, both inclusive, or `None` if there are none.
+    pub fn bounding_box(&self) -> Option<((i64, i64), (i64, i64))> {
+        let mut cells = self.cells.iter();
+        let &(row, col) = cells.next()?;
+        Some(cells.fold(
+            ((row, col), (row, col)),
+            |((top, left), (bottom, right)), &(row, col)| {
+                (
+                    (top.min(row), left.min(col)),
+                    (bottom.max(row), right.max(col)),
+                )
+            },
+        ))
+    }
+
+    pub fn evolve(&mut self) {
+        // Every live cell adds one to the count of each cell around it, so
+        // only cells next to a live one ever show up.
+        let radius = self.rule.radius() as i64;
+        let mut counts: HashMap<(i64, i64), u32> = HashMap::new();
+        for &(row, col) in &self.cells {
+            for d_row in -radius..=radius {
+                for d_col in -radius..=radius {
+                    if (d_row, d_col) != (0, 0) {
+                        *counts.entry((row + d_row, col + d_col)).or_default() += 1;
+                    }
+                }
+            }
+        }
+
+        let survivors = self.cells.iter().copied().filter(|cell| {
+            self.rule
+                .is_survival(counts.get(cell).copied().unwrap_or(0))
+        });
+        let births = counts
+            .iter()
+            .filter(|&(cell, &n)| !self.cells.contains(cell) && self.rule.is_birth(n))
+            .map(|(&cell, _)| cell);
+        self.cells = survivors.chain(births).collect();
+    }
+
+    /// Copies the live cells within the `width` by `height` rectangle whose
+    /// top left corner is at `(top, left)` into a world with the same rule.
+    /// Cells outside of it are left out.
+    pub fn to_world(
+        &self,
+        top: i64,
+        left: i64,
+        width: u32,
+        height: u32,
+    ) -> Result<World, WorldError> {
+        check_dimensions(width, height)?;
+        let mut world = World::empty(width, height).with_rule(self.rule);
+
+        for &(row, col) in &self.cells {
+            let (row, col) = (row - top, col - left);
+            if (0..height as i64).contains(&row) && (0..width as i64).contains(&col) {
+                let idx = world.get_index(row as u32, col as u32);
+                world.set_cell(idx, Cell::Alive);
+            }
+        }
+        Ok(world)
+    }
+}
+
+impl From<&World> for SparseWorld {
+    /// Takes over the live cells and the rule, with the world's top left
+    /// corner at `(0, 0)`.
+    fn from(world: &World) -> Self {
+        let cells = world
+            .live_cells()
+            .map(|(row, col)| (row as i64, col as i64))
+            .collect();
+        Self {
+            cells,
+            rule: world.rule,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SparseWorld;
+    use crate::game::{world_parts::Cell, Overflow, Rule, World, WorldError};
+    use crate::patterns::glider;
+
+    #[test]
+    fn test_sparse_set() {
+        let mut world = SparseWorld::new();
+        world.set(-5, 1_000_000, Cell::Alive);
+        world.set(3, -2, Cell::Alive);
+        assert_eq!(world.get(-5, 1_000_000), Cell::Alive);
+        assert_eq!(world.population(), 2);
+        assert_eq!(world.bounding_box(), Some(((-5, -2), (3, 1_000_000))));
+
+        world.set(-5, 1_000_000, Cell::Dead);
+        assert_eq!(world.get(-5, 1_000_000), Cell::Dead);
+        assert_eq!(world.bounding_box(), Some(((3, -2), (3, -2))));
+        assert_eq!(SparseWorld::new().bounding_box(), None);
+    }
+
+    #[test]
+    fn test_sparse_glider() {
+        let start = SparseWorld::from(&glider());
+        let mut world = start.clone();
+        for _ in 0..12 {
+            world.evolve();
+        }
+
+        let mut expected: Vec<_> = start.live_cells().map(|(r, c)| (r + 3, c + 3)).collect();
+        let mut cells: Vec<_> = world.live_cells().collect();
+        expected.sort();
+        cells.sort();
+        assert_eq!(cells, expected);
+
+        // Gliders keep going where a dense world would have wrapped around.
+        for _ in 0..400 {
+            world.evolve();
+        }
+        assert_eq!(world.population(), 5);
+        assert_eq!(world.bounding_box(), Some(((103, 103), (105, 105))));
+    }
+
+    #[test]
+    fn test_sparse_matches_dense() {
+        // Away from the edges both kinds of world evolve the same way.
+        let mut dense = World::empty(30, 30).with_rule(Rule::HIGHLIFE);
+        let seeded = World::new_with_seed(10, 10, 4);
+        dense.insert(&seeded, 10, 10, Overflow::Error).unwrap();
+        let mut sparse = SparseWorld::from(&dense);
+        assert_eq!(sparse.rule(), Rule::HIGHLIFE);
+
+        for _ in 0..5 {
+            dense.evolve();
+            sparse.evolve();
+            assert_eq!(sparse.to_world(0, 0, 30, 30).unwrap(), dense);
+        }
+    }
+
+    #[test]
+    fn test_sparse_to_world() {
+        let mut world = SparseWorld::from(&glider());
+        world.set(-1, -1, Cell::Alive);
+
+        // The cell outside of the rectangle is clipped.
+        let dense = world.to_world(0, 0, 3, 3).unwrap();
+        assert_eq!(dense, glider());
+
+        let dense = world.to_world(-1, -1, 4, 4).unwrap();
+        let expected: World = "O...\n..O.\n...O\n.OOO".parse().unwrap();
+        assert_eq!(dense, expected);
+
+        assert_eq!(
+            world.to_world(0, 0, 0, 3),
+            Err(WorldError::ZeroDimension {
+                width: 0,
+                height: 3
+            })
+        );
+    }
+}
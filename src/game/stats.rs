@@ -0,0 +1,82 @@
+use super::World;
+
+/// What happened in a generation, see `World::evolve_with_stats`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GenerationStats {
+    /// Cells that came to life.
+    pub births: u32,
+    /// Cells that were alive and no longer are, dying ones included.
+    pub deaths: u32,
+    /// Cells that were alive and stayed alive.
+    pub survivors: u32,
+    /// The number of live cells after the generation.
+    pub population: u64,
+    /// The generation the world is in afterwards.
+    pub generation: u64,
+}
+
+impl World {
+    /// Same as `evolve`, but also counts the cells that were born, died
+    /// and survived.
+    ///
+    /// The counts come from one pass over the new grid and the previous one
+    /// the scratch buffer still holds, so nothing is copied.
+    pub fn evolve_with_stats(&mut self) -> GenerationStats {
+        self.evolve();
+
+        // After evolving the scratch buffer holds the previous generation.
+        let mut stats = GenerationStats {
+            generation: self.generation,
+            ..GenerationStats::default()
+        };
+        for (now, before) in self.grid.iter().zip(&self.scratch) {
+            match (before.is_alive(), now.is_alive()) {
+                (false, true) => stats.births += 1,
+                (true, false) => stats.deaths += 1,
+                (true, true) => stats.survivors += 1,
+                (false, false) => {}
+            }
+        }
+        stats.population = (stats.survivors + stats.births) as u64;
+        stats
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::GenerationStats;
+    use crate::game::{Rule, World};
+
+    #[test]
+    fn test_evolve_with_stats_blinker() {
+        let mut world: World = ".....\n..O..\n..O..\n..O..\n.....".parse().unwrap();
+        assert_eq!(
+            world.evolve_with_stats(),
+            GenerationStats {
+                births: 2,
+                deaths: 2,
+                survivors: 1,
+                population: 3,
+                generation: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_evolve_with_stats_balances() {
+        for seed in 0..10 {
+            let rule = [Rule::CONWAY, Rule::HIGHLIFE, Rule::BRIANS_BRAIN][seed as usize % 3];
+            let mut world = World::new_with_seed(30, 20, seed).with_rule(rule);
+            for _ in 0..20 {
+                let before = world.population() as u64;
+                let stats = world.evolve_with_stats();
+                assert_eq!(
+                    stats.population,
+                    before + stats.births as u64 - stats.deaths as u64
+                );
+                assert_eq!(stats.population, world.population() as u64);
+                assert_eq!(stats.survivors as u64 + stats.deaths as u64, before);
+            }
+        }
+    }
+}
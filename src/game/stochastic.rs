@@ -0,0 +1,142 @@
+use rand::Rng;
+
+use super::{world_parts::Cell, World, WorldError};
+
+impl World {
+    /// Advances one generation where births only happen with probability
+    /// `birth_prob` and survivals with probability `survival_prob`. With both
+    /// at 1.0 this is the same as `evolve`.
+    ///
+    /// The random numbers come from `rng`, so a seeded generator makes runs
+    /// reproducible.
+    pub fn evolve_stochastic(
+        &mut self,
+        rng: &mut impl Rng,
+        birth_prob: f64,
+        survival_prob: f64,
+    ) -> Result<(), WorldError> {
+        for p in [birth_prob, survival_prob] {
+            // NaN fails the range check as well.
+            if !(0.0..=1.0).contains(&p) {
+                return Err(WorldError::InvalidProbability(p));
+            }
+        }
+
+        // Heat and ages are only recorded once the generation is final.
+        let (heatmap, ages) = (self.heatmap.take(), self.ages.take());
+        self.evolve();
+
+        // After evolving the scratch buffer holds the previous generation.
+        let mut reverted = 0;
+        for (cell, old) in self.grid.iter_mut().zip(&self.scratch) {
+            match (old, *cell) {
+                (Cell::Dead, Cell::Alive) if !rng.gen_bool(birth_prob) => {
+                    *cell = Cell::Dead;
+                    reverted += 1;
+                }
+                (Cell::Alive, Cell::Alive) if !rng.gen_bool(survival_prob) => {
+                    *cell = old.decay(&self.rule);
+                    reverted += 1;
+                }
+                _ => {}
+            }
+        }
+        self.population = self.population.map(|population| population - reverted);
+
+        (self.heatmap, self.ages) = (heatmap, ages);
+        self.record_heat();
+        self.record_ages();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::game::{world_parts::Cell, Rule, World, WorldError};
+
+    #[test]
+    fn test_evolve_stochastic_certain() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let mut world = World::new_with_seed(30, 20, 6);
+        let mut expected = world.clone();
+        for _ in 0..20 {
+            world.evolve_stochastic(&mut rng, 1.0, 1.0).unwrap();
+            expected.evolve();
+            assert_eq!(world, expected);
+        }
+    }
+
+    #[test]
+    fn test_evolve_stochastic_never() {
+        // Nothing is born and nothing survives, so a single step clears the
+        // world, and under a Generations rule leaves only dying cells.
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let mut world = World::new_with_seed(30, 20, 6);
+        world.evolve_stochastic(&mut rng, 0.0, 0.0).unwrap();
+        assert_eq!(world, World::empty(30, 20));
+
+        let mut world =
+            World::new_with_seed(30, 20, 6).with_rule(Rule::generations(&[2], &[3, 4], 3));
+        world.evolve_stochastic(&mut rng, 0.0, 0.0).unwrap();
+        assert!(world.grid.iter().all(|&cell| cell != Cell::Alive));
+    }
+
+    #[test]
+    fn test_evolve_stochastic_heat_and_ages() {
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let mut world = World::new_with_seed(30, 20, 6);
+        world.enable_heatmap();
+        world.enable_ages();
+        let mut heat = vec![0; 30 * 20];
+        let mut ages = vec![0; 30 * 20];
+        for _ in 0..10 {
+            let before = world.clone();
+            world.evolve_stochastic(&mut rng, 0.8, 0.9).unwrap();
+            for (i, ((row, col, now), (_, _, was))) in world.iter().zip(before.iter()).enumerate() {
+                heat[i] += (now != was) as u32;
+                ages[i] = if now.is_alive() && was.is_alive() {
+                    ages[i] + 1
+                } else {
+                    0
+                };
+                assert_eq!(
+                    world.age(row, col),
+                    Some(if now.is_alive() { ages[i] } else { 0 })
+                );
+            }
+        }
+        assert_eq!(world.heatmap().unwrap().counts(), heat);
+        assert!(world.max_age().unwrap() > 0);
+    }
+
+    #[test]
+    fn test_evolve_stochastic_seeded() {
+        let run = |seed| {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let mut world = World::new_with_seed(30, 20, 6);
+            for _ in 0..10 {
+                world.evolve_stochastic(&mut rng, 0.8, 0.9).unwrap();
+                world.population();
+            }
+            world
+        };
+        assert_eq!(run(3), run(3));
+        assert_ne!(run(3), run(4));
+    }
+
+    #[test]
+    fn test_evolve_stochastic_errors() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let mut world = World::new_with_seed(10, 10, 6);
+        let before = world.clone();
+        assert_eq!(
+            world.evolve_stochastic(&mut rng, 1.5, 1.0),
+            Err(WorldError::InvalidProbability(1.5))
+        );
+        assert!(world.evolve_stochastic(&mut rng, 1.0, f64::NAN).is_err());
+        assert_eq!(world, before);
+    }
+}
@@ -0,0 +1,91 @@
+use super::{world_parts::Cell, World};
+
+/// A cell whose state changed in a generation, see `World::evolve_tracked`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CellChange {
+    pub row: u32,
+    pub col: u32,
+    /// The state the cell changed to.
+    pub now: Cell,
+}
+
+impl World {
+    /// Same as `evolve`, but returns every cell that changed, in row order.
+    /// For a frontend that only repaints what changed.
+    pub fn evolve_tracked(&mut self) -> Vec<CellChange> {
+        self.evolve();
+
+        // After evolving the scratch buffer holds the previous generation.
+        let width = self.width as usize;
+        self.grid
+            .iter()
+            .zip(&self.scratch)
+            .enumerate()
+            .filter(|(_, (now, before))| now != before)
+            .map(|(idx, (&now, _))| CellChange {
+                row: (idx / width) as u32,
+                col: (idx % width) as u32,
+                now,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::CellChange;
+    use crate::game::{world_parts::Cell::*, Rule, World};
+
+    #[test]
+    fn test_evolve_tracked_blinker() {
+        let mut world: World = ".....\n..O..\n..O..\n..O..\n.....".parse().unwrap();
+        let changes = world.evolve_tracked();
+        assert_eq!(
+            changes,
+            vec![
+                CellChange {
+                    row: 1,
+                    col: 2,
+                    now: Dead
+                },
+                CellChange {
+                    row: 2,
+                    col: 1,
+                    now: Alive
+                },
+                CellChange {
+                    row: 2,
+                    col: 3,
+                    now: Alive
+                },
+                CellChange {
+                    row: 3,
+                    col: 2,
+                    now: Dead
+                },
+            ]
+        );
+
+        for _ in 0..10 {
+            assert_eq!(world.evolve_tracked().len(), 4);
+        }
+    }
+
+    #[test]
+    fn test_evolve_tracked_matches_evolve() {
+        let mut world = World::new_with_seed(30, 20, 3).with_rule(Rule::BRIANS_BRAIN);
+        for _ in 0..20 {
+            let before = world.clone();
+            let changes = world.evolve_tracked();
+
+            // Applying the changes to the old generation gives the new one.
+            let mut patched = before;
+            for change in changes {
+                let idx = patched.get_index(change.row, change.col);
+                assert_ne!(patched.grid[idx], change.now);
+                patched.grid[idx] = change.now;
+            }
+            assert_eq!(patched, world);
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use super::{world_parts::Cell, Boundary, Pattern, Topology, World, WorldError};
+
+/// A rotation (clockwise) or mirror image of a pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transform {
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Mirrors left and right.
+    FlipHorizontal,
+    /// Mirrors top and bottom.
+    FlipVertical,
+}
+
+// Applies the transform to a row-major block of cells, returning the new
+// width, height and cells. Quarter turns swap width and height.
+pub(crate) fn transform_cells(
+    width: u32,
+    height: u32,
+    cells: &[Cell],
+    transform: Transform,
+) -> (u32, u32, Vec<Cell>) {
+    let (new_width, new_height) = match transform {
+        Transform::Rotate90 | Transform::Rotate270 => (height, width),
+        _ => (width, height),
+    };
+
+    let mut out = Vec::with_capacity(cells.len());
+    for r in 0..new_height {
+        for c in 0..new_width {
+            // The cell of the original that ends up at (r, c).
+            let (row, col) = match transform {
+                Transform::Rotate90 => (height - 1 - c, r),
+                Transform::Rotate180 => (height - 1 - r, width - 1 - c),
+                Transform::Rotate270 => (c, width - 1 - r),
+                Transform::FlipHorizontal => (r, width - 1 - c),
+                Transform::FlipVertical => (height - 1 - r, c),
+            };
+            out.push(cells[row as usize * width as usize + col as usize]);
+        }
+    }
+
+    (new_width, new_height, out)
+}
+
+impl Pattern {
+    pub fn transformed(&self, transform: Transform) -> Pattern {
+        let (width, height, cells) =
+            transform_cells(self.width(), self.height(), self.cells(), transform);
+
+        let mut pattern = Pattern::new(width, height, cells).expect("dimensions stay valid");
+        pattern.name = self.name.clone();
+        pattern.author = self.author.clone();
+        pattern.comments = self.comments.clone();
+        pattern.rule = self.rule;
+        pattern
+    }
+
+    /// Rotates clockwise by `quarter_turns` times 90 degrees.
+    pub fn rotated(&self, quarter_turns: u32) -> Pattern {
+        match quarter_turns % 4 {
+            0 => self.clone(),
+            1 => self.transformed(Transform::Rotate90),
+            2 => self.transformed(Transform::Rotate180),
+            _ => self.transformed(Transform::Rotate270),
+        }
+    }
+
+    /// Mirrors left and right.
+    pub fn flipped(&self) -> Pattern {
+        self.transformed(Transform::FlipHorizontal)
+    }
+}
+
+impl World {
+    /// Places a transformed copy of the pattern, see `place`.
+    pub fn insert_transformed(
+        &mut self,
+        pattern: &Pattern,
+        row: u32,
+        col: u32,
+        transform: Transform,
+    ) -> Result<(), WorldError> {
+        self.place(&pattern.transformed(transform), row, col)
+    }
+
+    /// A rotated or mirrored copy of the whole world. Quarter turns swap the
+    /// width and height, and the edges along with them.
+    ///
+    /// The cells move as on a square grid, so a hexagonal world comes out
+    /// square. The copy starts out at origin `(0, 0)`.
+    pub fn transformed(&self, transform: Transform) -> World {
+        let (width, height, cells) =
+            transform_cells(self.width, self.height, &self.grid, transform);
+        let mut world = self.with_cells(width, height, cells);
+        if let Transform::Rotate90 | Transform::Rotate270 = transform {
+            world.boundary = Boundary::new(self.boundary.vertical, self.boundary.horizontal);
+        }
+        world.topology = Topology::Square;
+        world.origin = (0, 0);
+        world
+    }
+
+    /// Turns the world clockwise by a quarter, see `transformed`.
+    pub fn rotated_90(&self) -> World {
+        self.transformed(Transform::Rotate90)
+    }
+
+    pub fn rotated_180(&self) -> World {
+        self.transformed(Transform::Rotate180)
+    }
+
+    pub fn rotated_270(&self) -> World {
+        self.transformed(Transform::Rotate270)
+    }
+
+    /// Mirrors left and right, see `transformed`.
+    pub fn flipped_horizontal(&self) -> World {
+        self.transformed(Transform::FlipHorizontal)
+    }
+
+    /// Mirrors top and bottom, see `transformed`.
+    pub fn flipped_vertical(&self) -> World {
+        self.transformed(Transform::FlipVertical)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Transform;
+    use crate::game::{world_parts::Cell, Boundary, EdgeMode, Pattern, Topology, World};
+    use crate::patterns;
+
+    fn pattern(text: &str) -> Pattern {
+        Pattern::from(text.parse::<World>().unwrap())
+    }
+
+    fn evolved(mut world: World, generations: u32) -> World {
+        for _ in 0..generations {
+            world.evolve();
+        }
+        world
+    }
+
+    #[test]
+    fn test_transformed() {
+        // Non-square, so mixed up width and height would show.
+        let l = pattern("O.\nO.\nOO");
+        assert_eq!(l.transformed(Transform::Rotate90), pattern("OOO\nO.."));
+        assert_eq!(l.transformed(Transform::Rotate180), pattern("OO\n.O\n.O"));
+        assert_eq!(l.transformed(Transform::Rotate270), pattern("..O\nOOO"));
+        assert_eq!(
+            l.transformed(Transform::FlipHorizontal),
+            pattern(".O\n.O\nOO")
+        );
+        assert_eq!(
+            l.transformed(Transform::FlipVertical),
+            pattern("OO\nO.\nO.")
+        );
+
+        assert_eq!(l.rotated(4), l);
+        assert_eq!(l.rotated(3), l.transformed(Transform::Rotate270));
+        assert_eq!(l.flipped().flipped(), l);
+    }
+
+    #[test]
+    fn test_world_transformed() {
+        let world = World::new_with_seed(7, 4, 3).with_boundary(Boundary::CYLINDER);
+        let turned = world.rotated_90();
+        assert_eq!((turned.width(), turned.height()), (4, 7));
+        assert_eq!(
+            turned.boundary(),
+            Boundary::new(EdgeMode::Dead, EdgeMode::Wrap)
+        );
+        assert_eq!(turned.get(0, 0), world.get(3, 0));
+        assert_eq!(turned.population(), world.population());
+
+        assert_eq!(turned.rotated_90().rotated_90().rotated_90(), world);
+        assert_eq!(world.rotated_180(), world.rotated_90().rotated_90());
+        assert_eq!(world.rotated_270(), world.rotated_180().rotated_90());
+        assert_eq!(world.flipped_horizontal().flipped_horizontal(), world);
+        assert_eq!(
+            world.flipped_vertical(),
+            world.flipped_horizontal().rotated_180()
+        );
+
+        let hex = World::empty(4, 4).with_topology(Topology::Hex).unwrap();
+        assert_eq!(hex.flipped_vertical().topology(), Topology::Square);
+    }
+
+    #[test]
+    fn test_world_flipped_glider() {
+        // The glider heads down and to the right, its mirror image down and
+        // to the left.
+        let glider: World = "......\n..O...\n...O..\n.OOO..\n......\n......"
+            .parse()
+            .unwrap();
+        let flipped = evolved(glider.flipped_horizontal(), 4);
+        let moved = evolved(glider, 4);
+        assert_eq!(moved.get(4, 3), Some(Cell::Alive));
+        assert_eq!(flipped, moved.flipped_horizontal());
+        assert_eq!(flipped.get(4, 2), Some(Cell::Alive));
+    }
+
+    #[test]
+    fn test_transformed_keeps_metadata() {
+        let mut glider = Pattern::from(patterns::glider());
+        glider.name = Some("Glider".to_string());
+        assert_eq!(glider.rotated(1).name.as_deref(), Some("Glider"));
+    }
+
+    #[test]
+    fn test_insert_transformed_glider_direction() {
+        let glider = Pattern::from(patterns::glider());
+
+        // Unchanged, the glider heads down and to the right. A clockwise
+        // quarter turn sends it down and to the left, and so on.
+        let cases = [
+            (Transform::Rotate90, (1, -1)),
+            (Transform::Rotate180, (-1, -1)),
+            (Transform::Rotate270, (-1, 1)),
+            (Transform::FlipHorizontal, (1, -1)),
+            (Transform::FlipVertical, (-1, 1)),
+        ];
+        for (transform, (d_row, d_col)) in cases {
+            let mut world = World::empty(12, 12);
+            world.insert_transformed(&glider, 5, 5, transform).unwrap();
+
+            let mut expected = World::empty(12, 12);
+            let (row, col) = ((5 + 2 * d_row) as u32, (5 + 2 * d_col) as u32);
+            expected
+                .insert_transformed(&glider, row, col, transform)
+                .unwrap();
+
+            assert_eq!(evolved(world, 8), expected, "{:?}", transform);
+        }
+    }
+}
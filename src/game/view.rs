@@ -0,0 +1,230 @@
+use std::{fmt, ops::Range};
+
+use super::{
+    world_parts::{Cell, Row},
+    Topology, World, WorldError,
+};
+
+/// A rectangle of a world, borrowed rather than copied, see `World::view`.
+#[derive(Clone, Copy, Debug)]
+pub struct WorldView<'a> {
+    world: &'a World,
+    top: u32,
+    left: u32,
+    width: u32,
+    height: u32,
+}
+
+impl World {
+    /// The `width` by `height` rectangle of the world whose top left corner
+    /// is at `(top, left)`.
+    ///
+    /// Returns an error if the rectangle is empty or doesn't fit in the
+    /// world.
+    pub fn view(
+        &self,
+        top: u32,
+        left: u32,
+        height: u32,
+        width: u32,
+    ) -> Result<WorldView<'_>, WorldError> {
+        if width == 0 || height == 0 {
+            return Err(WorldError::ZeroDimension { width, height });
+        }
+        let bottom = top as u64 + height as u64;
+        let right = left as u64 + width as u64;
+        if bottom > self.height as u64 || right > self.width as u64 {
+            return Err(WorldError::OutOfBounds {
+                row: (bottom - 1).min(u32::MAX as u64) as u32,
+                col: (right - 1).min(u32::MAX as u64) as u32,
+                width: self.width,
+                height: self.height,
+            });
+        }
+
+        Ok(WorldView {
+            world: self,
+            top,
+            left,
+            width,
+            height,
+        })
+    }
+}
+
+impl<'a> WorldView<'a> {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The cell at `(row, col)` of the view, or `None` if it lies outside
+    /// of it.
+    pub fn get(&self, row: u32, col: u32) -> Option<Cell> {
+        if row >= self.height || col >= self.width {
+            return None;
+        }
+        self.world.get(self.top + row, self.left + col)
+    }
+
+    /// Row `row` of the view, ready for printing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row lies outside the view.
+    pub fn row(&self, row: u32) -> Row<'a> {
+        assert!(
+            row < self.height,
+            "row {} lies outside the {}x{} view",
+            row,
+            self.width,
+            self.height
+        );
+        let cells = self.world.get_row(self.top + row);
+        let start = self.left as usize;
+        let row = self.top + row;
+        Row::new(&cells[start..start + self.width as usize])
+            .with_indent(self.world.topology == Topology::Hex && row % 2 == 1)
+    }
+
+    /// Every row of the view from top to bottom, see `row`.
+    pub fn rows(&self) -> ViewRows<'a> {
+        ViewRows {
+            view: *self,
+            rows: 0..self.height,
+        }
+    }
+
+    /// The number of live cells within the view.
+    pub fn population(&self) -> usize {
+        self.rows().map(|row| row.population()).sum()
+    }
+
+    /// Copies the view into a world of its own, with the same rule and
+    /// edges. The origin moves along, so cells keep their coordinates.
+    ///
+    /// Only a view starting at an even row with an even height keeps a
+    /// hexagonal world's topology, any other comes out square.
+    pub fn to_world(&self) -> World {
+        let cells = self.rows().flat_map(|row| row.iter()).collect();
+        let mut world = self.world.with_cells(self.width, self.height, cells);
+        if !(self.top.is_multiple_of(2) && self.height.is_multiple_of(2)) {
+            world.topology = Topology::Square;
+        }
+        world.origin = (
+            world.origin.0 + self.top as i64,
+            world.origin.1 + self.left as i64,
+        );
+        world
+    }
+}
+
+impl<'a> fmt::Display for WorldView<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+/// The rows of a view from top to bottom, see `WorldView::rows`.
+pub struct ViewRows<'a> {
+    view: WorldView<'a>,
+    rows: Range<u32>,
+}
+
+impl<'a> Iterator for ViewRows<'a> {
+    type Item = Row<'a>;
+
+    fn next(&mut self) -> Option<Row<'a>> {
+        self.rows.next().map(|row| self.view.row(row))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for ViewRows<'a> {
+    fn next_back(&mut self) -> Option<Row<'a>> {
+        self.rows.next_back().map(|row| self.view.row(row))
+    }
+}
+
+impl<'a> ExactSizeIterator for ViewRows<'a> {}
+
+#[cfg(test)]
+mod test {
+    use crate::game::{world_parts::Cell::*, Rule, Topology, World, WorldError};
+
+    fn soup() -> World {
+        "......\n.O.O..\n..OO..\n..O..O\n......".parse().unwrap()
+    }
+
+    #[test]
+    fn test_view() {
+        let world = soup();
+        let view = world.view(1, 1, 3, 4).unwrap();
+        assert_eq!((view.width(), view.height()), (4, 3));
+        assert_eq!(view.get(0, 0), Some(Alive));
+        assert_eq!(view.get(2, 1), Some(Alive));
+        assert_eq!(view.get(2, 4), None);
+        assert_eq!(view.get(3, 0), None);
+        assert_eq!(view.population(), 5);
+        assert_eq!(view.to_string(), "# # \n ## \n #  ");
+        assert_eq!(view.rows().len(), 3);
+        assert_eq!(view.rows().next_back().unwrap().to_string(), " #  ");
+    }
+
+    #[test]
+    fn test_view_to_world() {
+        let world = soup().with_rule(Rule::HIGHLIFE);
+        let copy = world.view(1, 2, 3, 2).unwrap().to_world();
+        let expected: World = ".O\nOO\nO.".parse().unwrap();
+        assert_eq!(copy.grid, expected.grid);
+        assert_eq!(copy.rule(), Rule::HIGHLIFE);
+        assert_eq!(copy.origin(), (1, 2));
+        assert_eq!(copy.population(), 4);
+
+        let hex = World::empty(4, 4).with_topology(Topology::Hex).unwrap();
+        assert_eq!(
+            hex.view(0, 1, 2, 2).unwrap().to_world().topology(),
+            Topology::Hex
+        );
+        assert_eq!(
+            hex.view(1, 0, 2, 2).unwrap().to_world().topology(),
+            Topology::Square
+        );
+        assert_eq!(hex.view(1, 0, 2, 2).unwrap().row(0).to_string(), "   ");
+    }
+
+    #[test]
+    fn test_view_errors() {
+        let world = soup();
+        assert_eq!(
+            world.view(3, 4, 3, 2).unwrap_err(),
+            WorldError::OutOfBounds {
+                row: 5,
+                col: 5,
+                width: 6,
+                height: 5
+            }
+        );
+        assert!(world.view(0, u32::MAX, 1, 2).is_err());
+        assert_eq!(
+            world.view(0, 0, 0, 3).unwrap_err(),
+            WorldError::ZeroDimension {
+                width: 3,
+                height: 0
+            }
+        );
+        assert!(world.view(0, 0, 5, 6).is_ok());
+    }
+}
@@ -0,0 +1,268 @@
+use super::{world_parts::Row, Topology, World};
+
+/// What a `Viewport` does when moved past the edge of the world.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PanMode {
+    /// Stops at the edge.
+    #[default]
+    Clamp,
+    /// Comes back in on the other side, for worlds on a torus.
+    Wrap,
+}
+
+/// The part of a world that fits on a screen, which can be moved around
+/// over a world larger than that.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Viewport {
+    top: u32,
+    left: u32,
+    height: u32,
+    width: u32,
+    // The size of the world it lies on.
+    world_height: u32,
+    world_width: u32,
+    mode: PanMode,
+}
+
+impl Viewport {
+    /// A `width` by `height` viewport on the top left of `world`. It
+    /// shrinks to the world's size if that's smaller.
+    pub fn new(world: &World, height: u32, width: u32) -> Self {
+        let mut viewport = Self {
+            top: 0,
+            left: 0,
+            height,
+            width,
+            world_height: world.height(),
+            world_width: world.width(),
+            mode: PanMode::default(),
+        };
+        viewport.clamp();
+        viewport
+    }
+
+    /// Changes what happens when moving past an edge, which is to stop
+    /// unless changed.
+    pub fn with_mode(mut self, mode: PanMode) -> Self {
+        self.mode = mode;
+        self.clamp();
+        self
+    }
+
+    pub fn top(&self) -> u32 {
+        self.top
+    }
+
+    pub fn left(&self) -> u32 {
+        self.left
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn mode(&self) -> PanMode {
+        self.mode
+    }
+
+    /// Changes the size of the viewport, after the screen was resized.
+    pub fn resize(&mut self, height: u32, width: u32) {
+        self.height = height;
+        self.width = width;
+        self.clamp();
+    }
+
+    /// Follows a change in the size of the world, keeping the viewport in
+    /// the same place as far as it fits.
+    pub fn fit(&mut self, world: &World) {
+        self.world_height = world.height();
+        self.world_width = world.width();
+        self.clamp();
+    }
+
+    /// Moves the viewport `d_row` rows down and `d_col` columns to the
+    /// right, negative moving up and to the left.
+    pub fn pan(&mut self, d_row: i32, d_col: i32) {
+        self.top = self.moved(self.top, d_row as i64, self.height, self.world_height);
+        self.left = self.moved(self.left, d_col as i64, self.width, self.world_width);
+    }
+
+    /// Moves the viewport so the cell at `(row, col)` lies in its middle,
+    /// or as close to it as the edges allow.
+    pub fn center_on(&mut self, row: u32, col: u32) {
+        let top = row as i64 - (self.height / 2) as i64;
+        let left = col as i64 - (self.width / 2) as i64;
+        self.top = self.moved(0, top, self.height, self.world_height);
+        self.left = self.moved(0, left, self.width, self.world_width);
+    }
+
+    /// Centers the viewport on the middle of the world's live cells, see
+    /// `World::bounding_box`. Stays where it is if there are none.
+    pub fn center_on_bounding_box(&mut self, world: &World) {
+        if let Some(rect) = world.bounding_box() {
+            let row = (rect.top as u64 + rect.bottom as u64) / 2;
+            let col = (rect.left as u64 + rect.right as u64) / 2;
+            self.center_on(row as u32, col as u32);
+        }
+    }
+
+    /// Whether the cell at `(row, col)` of the world is in view.
+    pub fn contains(&self, row: u32, col: u32) -> bool {
+        let within = |i: u32, start: u32, len: u32, world_len: u32| {
+            (i as u64 + world_len as u64 - start as u64) % (world_len as u64) < len as u64
+        };
+        within(row, self.top, self.height, self.world_height)
+            && within(col, self.left, self.width, self.world_width)
+    }
+
+    /// Where the cell at `(row, col)` of the viewport lies in the world, or
+    /// `None` if it lies outside the viewport.
+    pub fn world_position(&self, row: u32, col: u32) -> Option<(u32, u32)> {
+        if row >= self.height || col >= self.width {
+            return None;
+        }
+        Some((
+            (self.top + row) % self.world_height,
+            (self.left + col) % self.world_width,
+        ))
+    }
+
+    /// The rows of `world` in view, ready for printing, see `World::row`.
+    pub fn rows(&self, world: &World) -> Vec<String> {
+        if self.top + self.height <= world.height() && self.left + self.width <= world.width() {
+            if let Ok(view) = world.view(self.top, self.left, self.height, self.width) {
+                return view.rows().map(|row| row.to_string()).collect();
+            }
+        }
+
+        // Lying across an edge, so the rows have to be put together.
+        let hex = world.topology() == Topology::Hex;
+        (0..self.height)
+            .map(|i| {
+                let row = (self.top + i) % world.height();
+                let cells: Vec<_> = (0..self.width)
+                    .map(|j| world[(row, (self.left + j) % world.width())])
+                    .collect();
+                Row::new(&cells)
+                    .with_indent(hex && row % 2 == 1)
+                    .to_string()
+            })
+            .collect()
+    }
+
+    /// The rows in view, one per line, see `rows`.
+    pub fn render(&self, world: &World) -> String {
+        self.rows(world).join("\n")
+    }
+
+    // Where a side starting at `start` ends up `by` cells further.
+    fn moved(&self, start: u32, by: i64, len: u32, world_len: u32) -> u32 {
+        let start = start as i64 + by;
+        match self.mode {
+            PanMode::Clamp => start.clamp(0, (world_len - len) as i64) as u32,
+            PanMode::Wrap => start.rem_euclid(world_len as i64) as u32,
+        }
+    }
+
+    // Keeps the viewport within the world at its current size.
+    fn clamp(&mut self) {
+        self.height = self.height.clamp(1, self.world_height);
+        self.width = self.width.clamp(1, self.world_width);
+        self.top = self.moved(self.top, 0, self.height, self.world_height);
+        self.left = self.moved(self.left, 0, self.width, self.world_width);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PanMode, Viewport};
+    use crate::game::{Pattern, World};
+    use crate::patterns;
+
+    #[test]
+    fn test_viewport_pan_clamps() {
+        let world = World::empty(100, 50);
+        let mut viewport = Viewport::new(&world, 20, 30);
+        viewport.pan(-5, -5);
+        assert_eq!((viewport.top(), viewport.left()), (0, 0));
+        viewport.pan(10, 20);
+        assert_eq!((viewport.top(), viewport.left()), (10, 20));
+        viewport.pan(1000, 1000);
+        assert_eq!((viewport.top(), viewport.left()), (30, 70));
+
+        // Growing the viewport pulls it back within the world.
+        viewport.resize(40, 90);
+        assert_eq!((viewport.top(), viewport.left()), (10, 10));
+        viewport.resize(500, 0);
+        assert_eq!((viewport.height(), viewport.width()), (50, 1));
+        assert_eq!(viewport.top(), 0);
+    }
+
+    #[test]
+    fn test_viewport_pan_wraps() {
+        let world: World = "O...\n....\n...O".parse().unwrap();
+        let mut viewport = Viewport::new(&world, 2, 2).with_mode(PanMode::Wrap);
+        viewport.pan(-1, -1);
+        assert_eq!((viewport.top(), viewport.left()), (2, 3));
+        assert!(viewport.contains(0, 0));
+        assert!(viewport.contains(2, 3));
+        assert!(!viewport.contains(1, 1));
+        assert_eq!(viewport.world_position(0, 1), Some((2, 0)));
+        assert_eq!(viewport.world_position(2, 0), None);
+        assert_eq!(viewport.render(&world), "# \n #");
+
+        viewport.pan(1, 1);
+        assert_eq!(viewport.render(&world), "# \n  ");
+        assert_eq!(
+            viewport.rows(&world),
+            world
+                .view(0, 0, 2, 2)
+                .unwrap()
+                .rows()
+                .map(|row| row.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_viewport_center_on() {
+        let world = World::empty(100, 50);
+        let mut viewport = Viewport::new(&world, 11, 21);
+        viewport.center_on(25, 50);
+        assert_eq!((viewport.top(), viewport.left()), (20, 40));
+        assert!(viewport.contains(25, 50));
+
+        viewport.center_on(0, 99);
+        assert_eq!((viewport.top(), viewport.left()), (0, 79));
+
+        let mut viewport = viewport.with_mode(PanMode::Wrap);
+        viewport.center_on(0, 0);
+        assert_eq!((viewport.top(), viewport.left()), (45, 90));
+    }
+
+    #[test]
+    fn test_viewport_follows_glider() {
+        let glider = Pattern::from(patterns::glider());
+        let mut world = World::empty(60, 60);
+        world.place(&glider, 2, 2).unwrap();
+        let mut viewport = Viewport::new(&world, 6, 6);
+
+        for _ in 0..120 {
+            world.evolve();
+            viewport.center_on_bounding_box(&world);
+            assert!(world
+                .live_cells()
+                .all(|(row, col)| viewport.contains(row, col)));
+        }
+        assert_eq!(viewport.render(&world).matches('#').count(), 5);
+
+        let mut viewport = Viewport::new(&World::empty(5, 5), 2, 2);
+        viewport.pan(1, 1);
+        viewport.center_on_bounding_box(&World::empty(5, 5));
+        assert_eq!((viewport.top(), viewport.left()), (1, 1));
+    }
+}
@@ -1,28 +1,370 @@
-use std::fmt;
+use std::{
+    env, fmt, iter,
+    ops::{Index, Range},
+    slice, vec,
+};
 
+use super::{Rule, World};
+
+/// The cells of a row, see `World::row`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Row<'a> {
     cells: &'a [Cell],
+    indent: bool,
 }
 
 impl<'a> Row<'a> {
     pub fn new(cells: &'a [Cell]) -> Row<'a> {
-        Self { cells }
+        Self {
+            cells,
+            indent: false,
+        }
+    }
+
+    /// Starts the row with an extra space, which is how the shifted rows of
+    /// a hexagonal world are drawn.
+    pub fn with_indent(mut self, indent: bool) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The cell in column `col`, or `None` past the end of the row.
+    pub fn get(&self, col: usize) -> Option<Cell> {
+        self.cells.get(col).copied()
+    }
+
+    /// The cells from left to right.
+    pub fn iter(&self) -> iter::Copied<slice::Iter<'a, Cell>> {
+        self.cells.iter().copied()
+    }
+
+    /// The number of live cells.
+    pub fn population(&self) -> usize {
+        self.cells.iter().filter(|cell| cell.is_alive()).count()
     }
 }
 
-impl<'a> fmt::Display for Row<'a> {
+impl<'a> Index<usize> for Row<'a> {
+    type Output = Cell;
+
+    fn index(&self, col: usize) -> &Cell {
+        &self.cells[col]
+    }
+}
+
+impl<'a> IntoIterator for Row<'a> {
+    type Item = Cell;
+    type IntoIter = iter::Copied<slice::Iter<'a, Cell>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The rows of a world from top to bottom, see `World::rows`.
+pub struct Rows<'a> {
+    world: &'a World,
+    rows: Range<u32>,
+}
+
+impl<'a> Rows<'a> {
+    pub(crate) fn new(world: &'a World) -> Self {
+        Self {
+            world,
+            rows: 0..world.height(),
+        }
+    }
+}
+
+impl<'a> Iterator for Rows<'a> {
+    type Item = Row<'a>;
+
+    fn next(&mut self) -> Option<Row<'a>> {
+        self.rows.next().map(|row| self.world.row(row))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Rows<'a> {
+    fn next_back(&mut self) -> Option<Row<'a>> {
+        self.rows.next_back().map(|row| self.world.row(row))
+    }
+}
+
+impl<'a> ExactSizeIterator for Rows<'a> {}
+
+/// Every cell of a world with its row and column, taking the world apart.
+/// See `World::into_iter`.
+pub struct IntoCells {
+    cells: iter::Enumerate<vec::IntoIter<Cell>>,
+    width: usize,
+}
+
+impl IntoCells {
+    pub(crate) fn new(cells: Vec<Cell>, width: u32) -> Self {
+        Self {
+            cells: cells.into_iter().enumerate(),
+            width: width as usize,
+        }
+    }
+
+    fn locate(&self, (i, cell): (usize, Cell)) -> (u32, u32, Cell) {
+        ((i / self.width) as u32, (i % self.width) as u32, cell)
+    }
+}
+
+impl Iterator for IntoCells {
+    type Item = (u32, u32, Cell);
+
+    fn next(&mut self) -> Option<(u32, u32, Cell)> {
+        let next = self.cells.next()?;
+        Some(self.locate(next))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cells.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for IntoCells {
+    fn next_back(&mut self) -> Option<(u32, u32, Cell)> {
+        let next = self.cells.next_back()?;
+        Some(self.locate(next))
+    }
+}
+
+impl ExactSizeIterator for IntoCells {}
+
+/// The characters a row is drawn with, see `Row::display_with`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DisplayConfig {
+    pub alive: char,
+    pub dead: char,
+    /// For the cells of a Generations rule that are dying.
+    pub dying: char,
+    /// Written between every two cells of a row, if any.
+    pub separator: Option<char>,
+}
+
+impl DisplayConfig {
+    /// `O` and `.`, the same as plaintext `.cells` files.
+    pub const PLAINTEXT: DisplayConfig = DisplayConfig {
+        alive: 'O',
+        dead: '.',
+        dying: '+',
+        separator: None,
+    };
+
+    pub(crate) fn symbol(&self, cell: Cell) -> char {
+        match cell {
+            Cell::Alive => self.alive,
+            Cell::Dying(_) => self.dying,
+            Cell::Dead => self.dead,
+        }
+    }
+}
+
+/// `#` for live cells, `+` for dying ones and a space for dead ones.
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            alive: '#',
+            dead: ' ',
+            dying: '+',
+            separator: None,
+        }
+    }
+}
+
+/// A row drawn with the characters of a `DisplayConfig`, see
+/// `Row::display_with`.
+pub struct RowDisplay<'a> {
+    row: Row<'a>,
+    config: &'a DisplayConfig,
+}
+
+impl<'a> Row<'a> {
+    /// Draws the row with the characters of `config` rather than the
+    /// default ones.
+    pub fn display_with(self, config: &'a DisplayConfig) -> RowDisplay<'a> {
+        RowDisplay { row: self, config }
+    }
+}
+
+impl<'a> fmt::Display for RowDisplay<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.row.indent {
+            write!(f, " ")?;
+        }
+        for (col, cell) in self.row.iter().enumerate() {
+            if let Some(separator) = self.config.separator.filter(|_| col > 0) {
+                write!(f, "{}", separator)?;
+            }
+            write!(f, "{}", self.config.symbol(cell))?;
+        }
+        Ok(())
+    }
+}
+
+/// A world drawn with the characters of a `DisplayConfig`, see
+/// `World::display_with`.
+pub struct WorldDisplay<'a> {
+    world: &'a World,
+    config: &'a DisplayConfig,
+}
+
+impl<'a> WorldDisplay<'a> {
+    pub(crate) fn new(world: &'a World, config: &'a DisplayConfig) -> Self {
+        Self { world, config }
+    }
+}
+
+impl<'a> fmt::Display for WorldDisplay<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for cell in self.cells {
-            let symbol = if cell.is_alive() { '#' } else { ' ' };
-            write!(f, "{}", symbol)?;
+        for (i, row) in self.world.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", row.display_with(self.config))?;
         }
         Ok(())
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+/// The colors a row is drawn with in a terminal, see `Row::display_ansi`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnsiStyle {
+    pub alive: [u8; 3],
+    pub dead: [u8; 3],
+    /// For the cells of a Generations rule that are dying.
+    pub dying: [u8; 3],
+    /// Draws the characters of `DisplayConfig::default()` instead of colors,
+    /// for terminals or users that don't want them.
+    pub no_color: bool,
+}
+
+impl AnsiStyle {
+    /// The default colors, without them if the `NO_COLOR` environment
+    /// variable is set to anything, see <https://no-color.org>.
+    pub fn from_env() -> Self {
+        Self {
+            no_color: env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()),
+            ..Self::default()
+        }
+    }
+
+    pub(super) fn color(&self, cell: Cell) -> [u8; 3] {
+        match cell {
+            Cell::Alive => self.alive,
+            Cell::Dying(_) => self.dying,
+            Cell::Dead => self.dead,
+        }
+    }
+}
+
+/// White live cells and grey dying ones on black.
+impl Default for AnsiStyle {
+    fn default() -> Self {
+        Self {
+            alive: [255, 255, 255],
+            dead: [0, 0, 0],
+            dying: [128, 128, 128],
+            no_color: false,
+        }
+    }
+}
+
+/// A row drawn with the colors of an `AnsiStyle`, see `Row::display_ansi`.
+pub struct AnsiRow<'a> {
+    row: Row<'a>,
+    style: &'a AnsiStyle,
+}
+
+impl<'a> Row<'a> {
+    /// Draws every cell as a space with the background color of `style`,
+    /// for terminals that understand 24-bit ANSI colors. A color is only
+    /// written where it changes, and the row ends by resetting it.
+    pub fn display_ansi(self, style: &'a AnsiStyle) -> AnsiRow<'a> {
+        AnsiRow { row: self, style }
+    }
+}
+
+impl<'a> fmt::Display for AnsiRow<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.style.no_color {
+            return write!(f, "{}", self.row);
+        }
+
+        if self.row.indent {
+            write!(f, " ")?;
+        }
+        let mut current = None;
+        for cell in self.row.iter() {
+            let color = self.style.color(cell);
+            if current != Some(color) {
+                let [r, g, b] = color;
+                write!(f, "\x1b[48;2;{};{};{}m", r, g, b)?;
+                current = Some(color);
+            }
+            write!(f, " ")?;
+        }
+        if current.is_some() {
+            write!(f, "\x1b[0m")?;
+        }
+        Ok(())
+    }
+}
+
+/// A world drawn with the colors of an `AnsiStyle`, see
+/// `World::display_ansi`.
+pub struct AnsiWorld<'a> {
+    world: &'a World,
+    style: &'a AnsiStyle,
+}
+
+impl<'a> AnsiWorld<'a> {
+    pub(crate) fn new(world: &'a World, style: &'a AnsiStyle) -> Self {
+        Self { world, style }
+    }
+}
+
+impl<'a> fmt::Display for AnsiWorld<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.world.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", row.display_ansi(self.style))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for Row<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display_with(&DisplayConfig::default()))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub enum Cell {
     Alive,
+    /// A cell that stopped being alive under a Generations rule. It counts
+    /// as dead for its neighbours and can't be born again until it has
+    /// decayed, with the age going from 1 up to `states - 2`.
+    Dying(u8),
+    #[default]
     Dead,
 }
 
@@ -30,30 +372,195 @@ impl Cell {
     pub fn is_alive(&self) -> bool {
         match self {
             Cell::Alive => true,
-            Cell::Dead => false,
+            Cell::Dying(_) | Cell::Dead => false,
         }
     }
 
-    pub fn set_state(&self, n: u8) -> Self {
-        match (self, n) {
-            (Self::Alive, 3) => Self::Alive,
-            (Self::Alive, 2) => Self::Alive,
-            (Self::Dead, 3) => Self::Alive,
-            (_, _) => Self::Dead,
+    /// A live cell for a dead or dying one, and a dead cell for a live one.
+    pub fn toggled(self) -> Cell {
+        Cell::from(!self.is_alive())
+    }
+
+    pub fn set_state(&self, n: u32, rule: &Rule) -> Self {
+        match *self {
+            Self::Alive if rule.is_survival(n) => Self::Alive,
+            Self::Dead if rule.is_birth(n) => Self::Alive,
+            _ => self.decay(rule),
+        }
+    }
+
+    // The next state of a cell that is neither born nor survives.
+    pub(crate) fn decay(&self, rule: &Rule) -> Self {
+        match *self {
+            Self::Alive if rule.states() > 2 => Self::Dying(1),
+            Self::Dying(age) if age + 1 < rule.states() - 1 => Self::Dying(age + 1),
+            _ => Self::Dead,
         }
     }
 }
 
+/// `Alive` for `true`, and `Dead` for `false`.
+impl From<bool> for Cell {
+    fn from(alive: bool) -> Self {
+        if alive {
+            Cell::Alive
+        } else {
+            Cell::Dead
+        }
+    }
+}
+
+/// Whether the cell is alive, see `Cell::is_alive`.
+impl From<Cell> for bool {
+    fn from(cell: Cell) -> Self {
+        cell.is_alive()
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::Cell::{Alive, Dead};
+    use super::Cell::{self, Alive, Dead, Dying};
+    use super::{AnsiStyle, DisplayConfig, Row};
+    use crate::game::Rule;
+
+    #[test]
+    fn test_cell_bool_conversions() {
+        for alive in [true, false] {
+            assert_eq!(bool::from(Cell::from(alive)), alive);
+        }
+        assert_eq!(Cell::from(true), Alive);
+        assert_eq!(Cell::default(), Dead);
+        assert!(!bool::from(Dying(2)));
+
+        assert_eq!(Alive.toggled(), Dead);
+        assert_eq!(Dead.toggled(), Alive);
+        assert_eq!(Dying(1).toggled(), Alive);
+        assert_eq!(Alive.toggled().toggled(), Alive);
+    }
 
     #[test]
     fn test_cell_is_alive() {
         let a = Alive;
-        assert_eq!(a.is_alive(), true);
+        assert!(a.is_alive());
 
         let b = Dead;
-        assert_eq!(b.is_alive(), false);
+        assert!(!b.is_alive());
+        assert!(!Dying(1).is_alive());
+    }
+
+    #[test]
+    fn test_cell_set_state() {
+        let conway = Rule::CONWAY;
+        let alive: Vec<u32> = (0..=8)
+            .filter(|&n| Alive.set_state(n, &conway).is_alive())
+            .collect();
+        let born: Vec<u32> = (0..=8)
+            .filter(|&n| Dead.set_state(n, &conway).is_alive())
+            .collect();
+        assert_eq!(alive, vec![2, 3]);
+        assert_eq!(born, vec![3]);
+
+        let highlife = Rule::new(&[3, 6], &[2, 3]);
+        assert_eq!(Dead.set_state(6, &highlife), Alive);
+        assert_eq!(Dead.set_state(6, &conway), Dead);
+    }
+
+    #[test]
+    fn test_cell_set_state_generations() {
+        let rule = Rule::generations(&[2], &[3, 4, 5], 4);
+        assert_eq!(Alive.set_state(3, &rule), Alive);
+        assert_eq!(Alive.set_state(2, &rule), Dying(1));
+        // Dying cells ignore their neighbours until they are dead.
+        assert_eq!(Dying(1).set_state(2, &rule), Dying(2));
+        assert_eq!(Dying(2).set_state(2, &rule), Dead);
+        assert_eq!(Dead.set_state(2, &rule), Alive);
+    }
+
+    #[test]
+    fn test_row_display() {
+        let cells = [Alive, Dying(1), Dead, Alive];
+        assert_eq!(Row::new(&cells).to_string(), "#+ #");
+        assert_eq!(Row::new(&cells).with_indent(true).to_string(), " #+ #");
+    }
+
+    #[test]
+    fn test_row_display_with() {
+        let cells = [Alive, Dying(1), Dead, Alive];
+        let row = Row::new(&cells);
+        assert_eq!(
+            row.display_with(&DisplayConfig::default()).to_string(),
+            row.to_string()
+        );
+        assert_eq!(
+            row.display_with(&DisplayConfig::PLAINTEXT).to_string(),
+            "O+.O"
+        );
+
+        let emoji = DisplayConfig {
+            alive: '🟩',
+            dead: '⬛',
+            dying: '🟨',
+            separator: Some('|'),
+        };
+        assert_eq!(
+            row.with_indent(true).display_with(&emoji).to_string(),
+            " 🟩|🟨|⬛|🟩"
+        );
+    }
+
+    #[test]
+    fn test_row_cells() {
+        let cells = [Alive, Dying(1), Dead, Alive];
+        let row = Row::new(&cells).with_indent(true);
+        assert_eq!(row.len(), 4);
+        assert!(!row.is_empty());
+        assert_eq!(row.get(1), Some(Dying(1)));
+        assert_eq!(row.get(4), None);
+        assert_eq!(row[3], Alive);
+        assert_eq!(row.population(), 2);
+        assert_eq!(row.iter().collect::<Vec<_>>(), cells);
+        let total: usize = row.into_iter().map(|cell| cell.is_alive() as usize).sum();
+        assert_eq!(total, 2);
+        assert!(Row::new(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_row_display_ansi() {
+        let cells = [Alive, Alive, Dead, Dying(1), Dead, Dead, Alive];
+        let style = AnsiStyle::default();
+        assert_eq!(
+            Row::new(&cells).display_ansi(&style).to_string(),
+            concat!(
+                "\x1b[48;2;255;255;255m  ",
+                "\x1b[48;2;0;0;0m ",
+                "\x1b[48;2;128;128;128m ",
+                "\x1b[48;2;0;0;0m  ",
+                "\x1b[48;2;255;255;255m ",
+                "\x1b[0m"
+            )
+        );
+
+        let style = AnsiStyle {
+            alive: [0, 200, 0],
+            dying: [0, 0, 0],
+            ..AnsiStyle::default()
+        };
+        assert_eq!(
+            Row::new(&cells[2..])
+                .with_indent(true)
+                .display_ansi(&style)
+                .to_string(),
+            " \x1b[48;2;0;0;0m    \x1b[48;2;0;200;0m \x1b[0m"
+        );
+        assert_eq!(Row::new(&[]).display_ansi(&style).to_string(), "");
+
+        let style = AnsiStyle {
+            no_color: true,
+            ..style
+        };
+        assert_eq!(
+            Row::new(&cells).display_ansi(&style).to_string(),
+            Row::new(&cells).to_string()
+        );
     }
 }
@@ -14,8 +14,6 @@ use crossterm::{
 };
 use game::World;
 
-use crate::game::world_parts::Row;
-
 // TODO: Add documentation
 // TODO: Refactor game module
 // TODO: Add Crossterm for terminal access
@@ -25,7 +23,10 @@ use crate::game::world_parts::Row;
 // TODO: Add user options
 
 mod cli;
-mod game;
+pub mod game;
+pub mod patterns;
+#[cfg(feature = "terminal")]
+pub mod runner;
 
 type MyResult<T> = Result<T, Box<dyn Error>>;
 
@@ -35,8 +36,8 @@ pub fn run() -> MyResult<()> {
     let mut world = World::new(60, 20);
     let screen = terminal::size()?;
     let cursor = (
-        screen.0 / 2 - (world.width / 2) as u16,
-        screen.1 / 2 - (world.height / 2) as u16,
+        screen.0 / 2 - (world.width() / 2) as u16,
+        screen.1 / 2 - (world.height() / 2) as u16,
     );
 
     let mut n = 0;
@@ -44,9 +45,8 @@ pub fn run() -> MyResult<()> {
     loop {
         queue!(stdout, Clear(ClearType::All))?;
 
-        for (i, row) in world.grid.chunks(world.width as usize).enumerate() {
-            let cells = Row::new(row);
-            queue!(stdout, MoveTo(cursor.0, cursor.1 + i as u16), Print(&cells))?;
+        for (i, row) in world.rows().enumerate() {
+            queue!(stdout, MoveTo(cursor.0, cursor.1 + i as u16), Print(&row))?;
         }
 
         stdout.flush()?;
@@ -0,0 +1,168 @@
+//! Classic patterns, each as the smallest world that holds it.
+
+use crate::game::World;
+
+#[cfg(feature = "fetch")]
+pub use fetch::{fetch, fetch_cached, FetchError};
+
+#[cfg(feature = "fetch")]
+mod fetch;
+
+fn from_plaintext(text: &str) -> World {
+    World::from_plaintext(text).expect("built-in patterns are valid")
+}
+
+/// The smallest spaceship, 3×3. Travels one cell down and to the right every
+/// 4 generations.
+pub fn glider() -> World {
+    from_plaintext(".O.\n..O\nOOO\n")
+}
+
+/// A horizontal period 2 oscillator, 3×1.
+pub fn blinker() -> World {
+    from_plaintext("OOO\n")
+}
+
+/// A period 2 oscillator, 4×2.
+pub fn toad() -> World {
+    from_plaintext(".OOO\nOOO.\n")
+}
+
+/// A period 2 oscillator made of two diagonal blocks, 4×4.
+pub fn beacon() -> World {
+    from_plaintext("OO..\nOO..\n..OO\n..OO\n")
+}
+
+/// A period 3 oscillator, 13×13.
+pub fn pulsar() -> World {
+    from_plaintext(
+        "..OOO...OOO..
+.............
+O....O.O....O
+O....O.O....O
+O....O.O....O
+..OOO...OOO..
+.............
+..OOO...OOO..
+O....O.O....O
+O....O.O....O
+O....O.O....O
+.............
+..OOO...OOO..
+",
+    )
+}
+
+/// The lightweight spaceship, 5×4. Travels two cells to the left every 4
+/// generations.
+pub fn lwss() -> World {
+    from_plaintext(".O..O\nO....\nO...O\nOOOO.\n")
+}
+
+/// Bill Gosper's glider gun, 36×9. Emits a glider heading down and to the
+/// right every 30 generations.
+pub fn gosper_glider_gun() -> World {
+    World::from_rle(
+        "x = 36, y = 9, rule = B3/S23
+24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4b
+obo$10bo5bo7bo$11bo3bo$12b2o!",
+    )
+    .expect("built-in patterns are valid")
+}
+
+/// A methuselah that takes 1103 generations to stabilize, 3×3.
+pub fn r_pentomino() -> World {
+    from_plaintext(".OO\nOO.\n.O.\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::world_parts::Cell;
+
+    // Places `pattern` with its top left corner at (row, col) in an otherwise
+    // empty world.
+    fn place(pattern: &World, width: u32, height: u32, row: u32, col: u32) -> World {
+        let mut cells = vec![Cell::Dead; (width * height) as usize];
+        for r in 0..pattern.height() {
+            for (c, &cell) in pattern.get_row(r).iter().enumerate() {
+                let idx = ((row + r) % height * width + (col + c as u32) % width) as usize;
+                cells[idx] = cell;
+            }
+        }
+        World::from_cells(width, height, cells).unwrap()
+    }
+
+    fn population(world: &World) -> usize {
+        (0..world.height())
+            .flat_map(|row| world.get_row(row))
+            .filter(|cell| cell.is_alive())
+            .count()
+    }
+
+    fn evolved(world: &World, generations: u32) -> World {
+        let mut world = world.clone();
+        for _ in 0..generations {
+            world.evolve();
+        }
+        world
+    }
+
+    fn assert_period(pattern: &World, period: u32) {
+        let world = place(pattern, pattern.width() + 6, pattern.height() + 6, 3, 3);
+        for generation in 1..period {
+            assert_ne!(evolved(&world, generation), world);
+        }
+        assert_eq!(evolved(&world, period), world);
+    }
+
+    #[test]
+    fn test_glider() {
+        let glider = glider();
+        assert_eq!((glider.width(), glider.height()), (3, 3));
+
+        // On a torus the glider keeps moving across the seam.
+        let world = place(&glider, 8, 8, 6, 6);
+        assert_eq!(evolved(&world, 4), place(&glider, 8, 8, 7, 7));
+        assert_eq!(evolved(&world, 32), world);
+    }
+
+    #[test]
+    fn test_oscillators() {
+        assert_period(&blinker(), 2);
+        assert_period(&toad(), 2);
+        assert_period(&beacon(), 2);
+        assert_period(&pulsar(), 3);
+    }
+
+    #[test]
+    fn test_lwss() {
+        let lwss = lwss();
+        assert_eq!((lwss.width(), lwss.height()), (5, 4));
+
+        let world = place(&lwss, 20, 10, 3, 8);
+        assert_eq!(evolved(&world, 4), place(&lwss, 20, 10, 3, 6));
+    }
+
+    #[test]
+    fn test_gosper_glider_gun() {
+        let gun = gosper_glider_gun();
+        assert_eq!((gun.width(), gun.height()), (36, 9));
+        assert_eq!(population(&gun), 36);
+
+        // Every 30 generations the gun is back in its original phase with one
+        // more glider (5 cells) on the way out.
+        let world = place(&gun, 60, 40, 1, 1);
+        assert_eq!(population(&evolved(&world, 30)), 41);
+    }
+
+    #[test]
+    fn test_r_pentomino() {
+        let r_pentomino = r_pentomino();
+        assert_eq!(population(&r_pentomino), 5);
+
+        let world = place(&r_pentomino, 40, 40, 18, 18);
+        let populations: Vec<usize> = (0..6).map(|n| population(&evolved(&world, n))).collect();
+        assert_eq!(populations, vec![5, 6, 7, 9, 8, 9]);
+    }
+}
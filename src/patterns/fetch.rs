@@ -0,0 +1,140 @@
+use std::{
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::game::{formats::ParseError, Pattern};
+
+const BASE_URL: &str = "https://conwaylife.com/patterns";
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// Pattern names may only contain ASCII letters, digits, `-` and `_`.
+    InvalidName(String),
+    NotFound(String),
+    Http(u16),
+    Transport(ureq::Error),
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::InvalidName(name) => write!(f, "invalid pattern name {:?}", name),
+            FetchError::NotFound(name) => write!(f, "no pattern named {:?}", name),
+            FetchError::Http(status) => write!(f, "server responded with status {}", status),
+            FetchError::Transport(e) => write!(f, "{}", e),
+            FetchError::Io(e) => write!(f, "{}", e),
+            FetchError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for FetchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FetchError::Transport(e) => Some(e),
+            FetchError::Io(e) => Some(e),
+            FetchError::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Downloads a pattern from the LifeWiki pattern archive by name, e.g.
+/// `"gosperglidergun"`.
+pub fn fetch(name: &str) -> Result<Pattern, FetchError> {
+    let rle = download(&check_name(name)?)?;
+    Pattern::from_rle(&rle).map_err(FetchError::Parse)
+}
+
+/// Same as `fetch`, but keeps downloaded files in `cache_dir` and reads them
+/// from there on later calls instead of contacting the server again.
+pub fn fetch_cached(name: &str, cache_dir: impl AsRef<Path>) -> Result<Pattern, FetchError> {
+    let name = check_name(name)?;
+    let path = cache_path(cache_dir.as_ref(), &name);
+
+    match fs::read_to_string(&path) {
+        Ok(rle) => Pattern::from_rle(&rle).map_err(FetchError::Parse),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            // Only patterns that parse are kept, so a broken download is
+            // tried again next time.
+            let rle = download(&name)?;
+            let pattern = Pattern::from_rle(&rle).map_err(FetchError::Parse)?;
+            fs::create_dir_all(cache_dir.as_ref()).map_err(FetchError::Io)?;
+            fs::write(&path, &rle).map_err(FetchError::Io)?;
+            Ok(pattern)
+        }
+        Err(e) => Err(FetchError::Io(e)),
+    }
+}
+
+// Names end up in both a URL and a file path, so anything that could escape
+// either is rejected. The archive uses lowercase names throughout.
+fn check_name(name: &str) -> Result<String, FetchError> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if valid {
+        Ok(name.to_ascii_lowercase())
+    } else {
+        Err(FetchError::InvalidName(name.to_string()))
+    }
+}
+
+fn cache_path(cache_dir: &Path, name: &str) -> PathBuf {
+    cache_dir.join(format!("{}.rle", name))
+}
+
+fn download(name: &str) -> Result<String, FetchError> {
+    let url = format!("{}/{}.rle", BASE_URL, name);
+    match ureq::get(&url).call() {
+        Ok(mut response) => response
+            .body_mut()
+            .read_to_string()
+            .map_err(FetchError::Transport),
+        Err(ureq::Error::StatusCode(404)) => Err(FetchError::NotFound(name.to_string())),
+        Err(ureq::Error::StatusCode(status)) => Err(FetchError::Http(status)),
+        Err(e) => Err(FetchError::Transport(e)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use super::{check_name, fetch, fetch_cached, FetchError};
+
+    #[test]
+    fn test_check_name() {
+        assert_eq!(check_name("Glider").unwrap(), "glider");
+        assert_eq!(check_name("gosper_gun-2").unwrap(), "gosper_gun-2");
+
+        for name in ["", "../etc/passwd", "glider.rle", "a b", "glider?x=1"] {
+            assert!(matches!(check_name(name), Err(FetchError::InvalidName(_))));
+        }
+        assert!(matches!(fetch("../x"), Err(FetchError::InvalidName(_))));
+    }
+
+    #[test]
+    fn test_fetch_cached_reads_cache() {
+        let dir = std::env::temp_dir().join(format!("lifer-fetch-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("blinker.rle"), "#N Blinker\nx = 3, y = 1\n3o!\n").unwrap();
+
+        // Served from the cache, so no request is made.
+        let pattern = fetch_cached("Blinker", &dir).unwrap();
+        assert_eq!(pattern.name.as_deref(), Some("Blinker"));
+        assert_eq!((pattern.width(), pattern.height()), (3, 1));
+
+        fs::write(dir.join("broken.rle"), "x = 1, y = 1\nq!\n").unwrap();
+        let result = fetch_cached("broken", &dir);
+        assert!(matches!(result, Err(FetchError::Parse(_))));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,266 @@
+use std::{
+    io::{self, BufWriter, Write},
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use crate::game::{Viewport, World};
+
+pub use diff::DiffRenderer;
+
+mod diff;
+
+/// Runs a world in the terminal until the user quits.
+///
+/// The world takes over the whole terminal: q or Esc quits, space pauses and
+/// resumes, s steps a single generation and the arrow keys move the view
+/// over a world larger than the terminal.
+pub struct TerminalRunner {
+    world: World,
+    tick: Duration,
+    paused: bool,
+    viewport: Viewport,
+    renderer: DiffRenderer,
+}
+
+// What the runner does after a key press.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Control {
+    Continue,
+    Step,
+    Quit,
+}
+
+impl TerminalRunner {
+    /// A runner evolving `world` once every `tick`.
+    pub fn new(world: World, tick: Duration) -> Self {
+        Self {
+            viewport: Viewport::new(&world, world.height(), world.width()),
+            world,
+            tick,
+            paused: false,
+            renderer: DiffRenderer::new(),
+        }
+    }
+
+    /// Starts out paused, waiting for space or s.
+    pub fn paused(mut self) -> Self {
+        self.paused = true;
+        self
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Stops running, returning the world as it was left.
+    pub fn into_world(self) -> World {
+        self.world
+    }
+
+    /// Runs until the user quits. The terminal is put back the way it was
+    /// afterwards, even if this panics.
+    pub fn run(&mut self) -> io::Result<()> {
+        let _guard = TerminalGuard::new()?;
+        let mut stdout = BufWriter::new(io::stdout().lock());
+        let mut screen = terminal::size()?;
+        let mut next_tick = Instant::now() + self.tick;
+        queue!(stdout, Clear(ClearType::All))?;
+
+        loop {
+            self.fit_screen(screen);
+            self.draw(&mut stdout, screen)?;
+
+            let timeout = next_tick.saturating_duration_since(Instant::now());
+            if event::poll(timeout)? {
+                match event::read()? {
+                    Event::Key(key) if key.kind != KeyEventKind::Release => {
+                        match self.handle_key(key, screen) {
+                            Control::Quit => return Ok(()),
+                            Control::Step => self.world.evolve(),
+                            Control::Continue => {}
+                        }
+                    }
+                    Event::Resize(width, height) => {
+                        screen = (width, height);
+                        self.renderer.force_full_redraw();
+                        queue!(stdout, Clear(ClearType::All))?;
+                    }
+                    _ => {}
+                }
+                continue;
+            }
+
+            if !self.paused {
+                self.world.evolve();
+            }
+            next_tick = Instant::now() + self.tick;
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, screen: (u16, u16)) -> Control {
+        self.fit_screen(screen);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Control::Quit,
+            // Raw mode keeps Ctrl-C from stopping the process by itself.
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Control::Quit
+            }
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Char('s') => return Control::Step,
+            KeyCode::Up => self.viewport.pan(-1, 0),
+            KeyCode::Down => self.viewport.pan(1, 0),
+            KeyCode::Left => self.viewport.pan(0, -1),
+            KeyCode::Right => self.viewport.pan(0, 1),
+            _ => {}
+        }
+        Control::Continue
+    }
+
+    // The number of rows and columns of the world that fit on the screen,
+    // with the bottom line left for the status.
+    fn visible(&self, (width, height): (u16, u16)) -> (u32, u32) {
+        let rows = (height.saturating_sub(1) as u32).clamp(1, self.world.height());
+        let cols = (width as u32).clamp(1, self.world.width());
+        (rows, cols)
+    }
+
+    // Fits the view to the screen, which moves it back within the world
+    // once the terminal has grown.
+    fn fit_screen(&mut self, screen: (u16, u16)) {
+        let (rows, cols) = self.visible(screen);
+        self.viewport.fit(&self.world);
+        self.viewport.resize(rows, cols);
+    }
+
+    fn draw(&mut self, stdout: &mut impl Write, screen: (u16, u16)) -> io::Result<()> {
+        self.renderer
+            .draw_viewport(&self.world, &self.viewport, stdout)?;
+        queue!(
+            stdout,
+            MoveTo(0, screen.1.saturating_sub(1)),
+            Print(self.status()),
+            Clear(ClearType::UntilNewLine)
+        )?;
+        stdout.flush()
+    }
+
+    fn status(&self) -> String {
+        format!(
+            "generation {}  population {}{}  q quit  space pause  s step  arrows move",
+            self.world.generation(),
+            self.world.population(),
+            if self.paused { "  paused" } else { "" }
+        )
+    }
+}
+
+// Puts the terminal into raw mode on the alternate screen while it lives,
+// and back when it's dropped, which also happens while unwinding a panic.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn new() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        // Already in raw mode, so dropping the guard has to undo that.
+        let guard = TerminalGuard;
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        // Errors can't be reported from here, and there's nothing better
+        // to do than carry on undoing the rest.
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{Control, TerminalRunner};
+    use crate::game::World;
+
+    fn press(runner: &mut TerminalRunner, code: KeyCode, screen: (u16, u16)) -> Control {
+        runner.handle_key(KeyEvent::new(code, KeyModifiers::NONE), screen)
+    }
+
+    #[test]
+    fn test_keys() {
+        let mut runner = TerminalRunner::new(World::empty(10, 10), Duration::ZERO);
+        assert_eq!(
+            press(&mut runner, KeyCode::Char('q'), (80, 24)),
+            Control::Quit
+        );
+        assert_eq!(press(&mut runner, KeyCode::Esc, (80, 24)), Control::Quit);
+        assert_eq!(
+            runner.handle_key(
+                KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+                (80, 24)
+            ),
+            Control::Quit
+        );
+        assert_eq!(
+            press(&mut runner, KeyCode::Char('s'), (80, 24)),
+            Control::Step
+        );
+
+        assert!(!runner.paused);
+        press(&mut runner, KeyCode::Char(' '), (80, 24));
+        assert!(runner.paused);
+        assert!(runner
+            .status()
+            .ends_with("paused  q quit  space pause  s step  arrows move"));
+        press(&mut runner, KeyCode::Char(' '), (80, 24));
+        assert!(!runner.paused);
+    }
+
+    #[test]
+    fn test_panning() {
+        let mut runner = TerminalRunner::new(World::empty(100, 50), Duration::ZERO);
+        // 80 columns and 23 rows of the world fit beside the status line.
+        let screen = (80, 24);
+        assert_eq!(runner.visible(screen), (23, 80));
+
+        press(&mut runner, KeyCode::Up, screen);
+        press(&mut runner, KeyCode::Left, screen);
+        assert_eq!((runner.viewport.top(), runner.viewport.left()), (0, 0));
+        for _ in 0..100 {
+            press(&mut runner, KeyCode::Down, screen);
+            press(&mut runner, KeyCode::Right, screen);
+        }
+        assert_eq!(
+            (runner.viewport.top(), runner.viewport.left()),
+            (50 - 23, 100 - 80)
+        );
+
+        // A larger terminal shows more, so the view moves back.
+        runner.fit_screen((90, 40));
+        assert_eq!(
+            (runner.viewport.top(), runner.viewport.left()),
+            (50 - 39, 100 - 90)
+        );
+        runner.fit_screen((200, 100));
+        assert_eq!((runner.viewport.top(), runner.viewport.left()), (0, 0));
+
+        // A world smaller than the terminal doesn't move.
+        let mut runner = TerminalRunner::new(World::empty(10, 5), Duration::ZERO);
+        press(&mut runner, KeyCode::Down, screen);
+        press(&mut runner, KeyCode::Right, screen);
+        assert_eq!((runner.viewport.top(), runner.viewport.left()), (0, 0));
+        assert_eq!(runner.visible((0, 0)), (1, 1));
+    }
+}
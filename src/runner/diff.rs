@@ -0,0 +1,241 @@
+use std::io::{self, Write};
+
+use crossterm::{cursor::MoveTo, queue, style::Print};
+
+use crate::game::{
+    world_parts::{Cell, DisplayConfig},
+    Topology, Viewport, World,
+};
+
+/// Draws frames of a world to a terminal, writing only the cells that
+/// changed since the frame before rather than all of them, which flickers
+/// and is slow on a large world.
+///
+/// Every run of changed cells in a row is written as a cursor move followed
+/// by the cells, so anything implementing `io::Write` can take the output.
+#[derive(Clone, Debug, Default)]
+pub struct DiffRenderer {
+    config: DisplayConfig,
+    // The screen position of the top left cell, as `(col, row)`.
+    origin: (u16, u16),
+    // The frame drawn last, `None` if the next one is drawn in full.
+    previous: Option<Frame>,
+}
+
+#[derive(Clone, Debug)]
+struct Frame {
+    width: u32,
+    cells: Vec<Cell>,
+    // Whether each row is shifted to the right, as in a hexagonal world.
+    indents: Vec<bool>,
+}
+
+impl DiffRenderer {
+    /// A renderer drawing its first frame in full, from the top left corner
+    /// of the screen.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Draws the cells with the characters of `config` rather than the
+    /// default ones.
+    pub fn with_config(mut self, config: DisplayConfig) -> Self {
+        self.config = config;
+        self.previous = None;
+        self
+    }
+
+    /// Draws the top left cell at column `col` and row `row` of the screen.
+    pub fn with_origin(mut self, col: u16, row: u16) -> Self {
+        self.origin = (col, row);
+        self.previous = None;
+        self
+    }
+
+    /// Draws every cell of the next frame, for the first one after the
+    /// screen was cleared or resized. Anything outside of the frame is left
+    /// as it is.
+    pub fn force_full_redraw(&mut self) {
+        self.previous = None;
+    }
+
+    /// Draws `world`, writing the cells that changed since the last frame to
+    /// `out`. Returns how many cells that were.
+    pub fn draw(&mut self, world: &World, out: &mut impl Write) -> io::Result<usize> {
+        let hex = world.topology() == Topology::Hex;
+        let frame = Frame {
+            width: world.width(),
+            cells: world.iter().map(|(_, _, cell)| cell).collect(),
+            indents: (0..world.height()).map(|row| hex && row % 2 == 1).collect(),
+        };
+        self.draw_frame(frame, out)
+    }
+
+    /// Draws the part of `world` in `viewport`, see `draw`. Panning redraws
+    /// only the cells that look different afterwards.
+    pub fn draw_viewport(
+        &mut self,
+        world: &World,
+        viewport: &Viewport,
+        out: &mut impl Write,
+    ) -> io::Result<usize> {
+        let hex = world.topology() == Topology::Hex;
+        let mut cells = Vec::with_capacity((viewport.width() * viewport.height()) as usize);
+        let mut indents = Vec::with_capacity(viewport.height() as usize);
+        for row in 0..viewport.height() {
+            for col in 0..viewport.width() {
+                let (row, col) = viewport
+                    .world_position(row, col)
+                    .expect("lies in the viewport");
+                cells.push(world.get(row, col).unwrap_or_default());
+            }
+            let (row, _) = viewport
+                .world_position(row, 0)
+                .expect("lies in the viewport");
+            indents.push(hex && row % 2 == 1);
+        }
+
+        let frame = Frame {
+            width: viewport.width(),
+            cells,
+            indents,
+        };
+        self.draw_frame(frame, out)
+    }
+
+    fn draw_frame(&mut self, frame: Frame, out: &mut impl Write) -> io::Result<usize> {
+        // A frame of another size has nothing to compare against.
+        let previous = self.previous.take().filter(|previous| {
+            previous.width == frame.width && previous.indents.len() == frame.indents.len()
+        });
+        let width = frame.width as usize;
+        let step = if self.config.separator.is_some() {
+            2
+        } else {
+            1
+        };
+        let mut written = 0;
+
+        let rows = frame.cells.chunks(width).zip(&frame.indents).enumerate();
+        for (row, (cells, &indent)) in rows {
+            let y = self.origin.1 + row as u16;
+            let before = previous
+                .as_ref()
+                .filter(|previous| previous.indents[row] == indent)
+                .map(|previous| &previous.cells[row * width..(row + 1) * width]);
+
+            let Some(before) = before else {
+                // The whole row, with the indent, which also covers the cell
+                // left over on either side when it moved.
+                let was_indented = previous.as_ref().is_some_and(|p| p.indents[row]);
+                let mut line = String::from(if indent { " " } else { "" });
+                line.push_str(&self.run(cells));
+                if was_indented && !indent {
+                    line.push(' ');
+                }
+                queue!(out, MoveTo(self.origin.0, y), Print(line))?;
+                written += cells.len();
+                continue;
+            };
+
+            let mut col = 0;
+            while col < width {
+                if cells[col] == before[col] {
+                    col += 1;
+                    continue;
+                }
+                let start = col;
+                while col < width && cells[col] != before[col] {
+                    col += 1;
+                }
+                let x = self.origin.0 + indent as u16 + (start * step) as u16;
+                queue!(out, MoveTo(x, y), Print(self.run(&cells[start..col])))?;
+                written += col - start;
+            }
+        }
+
+        self.previous = Some(frame);
+        Ok(written)
+    }
+
+    // The characters of cells next to each other in a row.
+    fn run(&self, cells: &[Cell]) -> String {
+        let mut run = String::with_capacity(cells.len() * 2);
+        for (i, &cell) in cells.iter().enumerate() {
+            if let Some(separator) = self.config.separator.filter(|_| i > 0) {
+                run.push(separator);
+            }
+            run.push(self.config.symbol(cell));
+        }
+        run
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DiffRenderer;
+    use crate::game::{world_parts::DisplayConfig, Viewport, World};
+
+    fn draw(renderer: &mut DiffRenderer, world: &World) -> (usize, String) {
+        let mut out = Vec::new();
+        let written = renderer.draw(world, &mut out).unwrap();
+        (written, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_diff_renderer_blinker() {
+        let mut world: World = ".....\n..O..\n..O..\n..O..\n.....".parse().unwrap();
+        let mut renderer = DiffRenderer::new();
+        let (written, out) = draw(&mut renderer, &world);
+        assert_eq!(written, 25);
+        assert!(out.starts_with("\x1b[1;1H     \x1b[2;1H  #  "));
+
+        world.evolve();
+        let (written, out) = draw(&mut renderer, &world);
+        assert_eq!(written, 4);
+        assert_eq!(out, "\x1b[2;3H \x1b[3;2H#\x1b[3;4H#\x1b[4;3H ");
+
+        assert_eq!(draw(&mut renderer, &world), (0, String::new()));
+        renderer.force_full_redraw();
+        assert_eq!(draw(&mut renderer, &world).0, 25);
+
+        // A world of another size is drawn in full.
+        assert_eq!(draw(&mut renderer, &World::empty(3, 3)).0, 9);
+    }
+
+    #[test]
+    fn test_diff_renderer_runs() {
+        let config = DisplayConfig {
+            separator: Some(','),
+            ..DisplayConfig::PLAINTEXT
+        };
+        let mut renderer = DiffRenderer::new().with_config(config).with_origin(2, 5);
+        let (_, out) = draw(&mut renderer, &World::empty(4, 2));
+        assert_eq!(out, "\x1b[6;3H.,.,.,.\x1b[7;3H.,.,.,.");
+
+        // Cells changing next to each other are written together.
+        let world: World = "....\n.OO.".parse().unwrap();
+        assert_eq!(draw(&mut renderer, &world), (2, "\x1b[7;5HO,O".to_string()));
+    }
+
+    #[test]
+    fn test_diff_renderer_viewport() {
+        let world: World = "O.....\n......\n......\n.....O".parse().unwrap();
+        let mut viewport = Viewport::new(&world, 2, 3);
+        let mut renderer = DiffRenderer::new();
+        let mut out = Vec::new();
+        assert_eq!(
+            renderer.draw_viewport(&world, &viewport, &mut out).unwrap(),
+            6
+        );
+
+        // Only the corner cell looks different after moving right.
+        viewport.pan(0, 1);
+        let mut out = Vec::new();
+        assert_eq!(
+            renderer.draw_viewport(&world, &viewport, &mut out).unwrap(),
+            1
+        );
+        assert_eq!(out, b"\x1b[1;1H ");
+    }
+}